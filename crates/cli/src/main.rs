@@ -0,0 +1,397 @@
+//! Lumo CLI
+//!
+//! Queries the usage data in `~/.lumo/lumo.db` from the terminal, using the
+//! same aggregation queries as the app's dashboard.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
+use shared::analytics::{
+    format_agent_display_name, time_range, StatsAnalytics, TimeRange, ToolsAnalytics,
+    TrendsAnalytics,
+};
+use shared::calendar::Calendar;
+use shared::SessionRepository;
+use sqlx::SqlitePool;
+
+mod output;
+mod statusline;
+
+use output::{Cell, Format, Table};
+
+#[derive(Parser)]
+#[command(name = "lumo", version, about = "Query Lumo usage from the terminal")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Time range to report on
+    #[arg(long, short, value_enum, global = true, default_value_t = Range::Today)]
+    range: Range,
+    /// Start of a custom range: a date (YYYY-MM-DD) or an RFC 3339 time
+    #[arg(long, global = true, conflicts_with = "range", value_parser = parse_bound)]
+    since: Option<Bound>,
+    /// End of a custom range, inclusive: a date (YYYY-MM-DD) or an RFC 3339
+    /// time. Defaults to now.
+    #[arg(long, global = true, conflicts_with = "range", value_parser = parse_bound)]
+    until: Option<Bound>,
+    /// Only include usage from this agent (claude_code, codex)
+    #[arg(long, short, global = true)]
+    agent: Option<String>,
+    /// Output format
+    #[arg(long, short, value_enum, global = true, default_value_t = Format::Table)]
+    format: Format,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Cost, tokens, cache hit rate, sessions and code changes
+    Summary,
+    /// Cost, requests and token breakdown per model
+    Models,
+    /// Sessions active in the range, newest first
+    Sessions {
+        /// Maximum number of sessions to show
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Tool call counts, success rates and durations
+    Tools,
+    /// Cost and tokens per hour, day, week or month, depending on the range
+    Trends,
+    /// Claude Code `statusLine` command: reads the session JSON on stdin and
+    /// prints session cost, today's spend and cache hit rate
+    Statusline,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Range {
+    /// Since local midnight
+    Today,
+    /// Since Monday
+    Week,
+    /// Since the 1st of the month
+    Month,
+    /// Rolling last 24 hours
+    #[value(name = "last24h")]
+    Last24h,
+    /// Rolling last 7 days
+    #[value(name = "last7d")]
+    Last7d,
+    /// Rolling last 30 days
+    #[value(name = "last30d")]
+    Last30d,
+    /// Rolling last 90 days
+    #[value(name = "last90d")]
+    Last90d,
+    /// Since January 1st
+    Year,
+    /// Everything recorded
+    All,
+}
+
+impl From<Range> for TimeRange {
+    fn from(range: Range) -> Self {
+        match range {
+            Range::Today => TimeRange::Today,
+            Range::Week => TimeRange::Week,
+            Range::Month => TimeRange::Month,
+            Range::Last24h => TimeRange::Last24Hours,
+            Range::Last7d => TimeRange::Last7Days,
+            Range::Last30d => TimeRange::Last30Days,
+            Range::Last90d => TimeRange::Last90Days,
+            Range::Year => TimeRange::Year,
+            Range::All => TimeRange::All,
+        }
+    }
+}
+
+/// Edge of a `--since`/`--until` range
+#[derive(Debug, Clone, Copy)]
+enum Bound {
+    /// A whole local day
+    Date(NaiveDate),
+    Time(DateTime<Utc>),
+}
+
+fn parse_bound(value: &str) -> Result<Bound, String> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(Bound::Date(date));
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| Bound::Time(time.to_utc()))
+        .map_err(|_| "expected YYYY-MM-DD or an RFC 3339 time".to_string())
+}
+
+/// `TimeRange::Custom` for `--since`/`--until`, `None` when neither is set.
+/// A date starts at local midnight for `--since` and runs to the end of
+/// the day for `--until`.
+fn custom_range(
+    since: Option<Bound>,
+    until: Option<Bound>,
+    calendar: &Calendar,
+    now: DateTime<Utc>,
+) -> Result<Option<TimeRange>> {
+    if since.is_none() && until.is_none() {
+        return Ok(None);
+    }
+    let start = match since {
+        Some(Bound::Date(date)) => calendar.start_of_day(date).timestamp_millis(),
+        Some(Bound::Time(time)) => time.timestamp_millis(),
+        None => 0,
+    };
+    let end = match until {
+        Some(Bound::Date(date)) => {
+            calendar
+                .start_of_day(date + Duration::days(1))
+                .timestamp_millis()
+                - 1
+        }
+        Some(Bound::Time(time)) => time.timestamp_millis(),
+        None => now.timestamp_millis(),
+    };
+    if start > end {
+        bail!("--since must not be after --until");
+    }
+    Ok(Some(TimeRange::Custom { start, end }))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    if let Command::Statusline = cli.command {
+        statusline::run().await;
+        return Ok(());
+    }
+
+    let range = custom_range(cli.since, cli.until, &Calendar::current(), Utc::now())?
+        .unwrap_or_else(|| TimeRange::from(cli.range));
+    let pool = open_pool().await?;
+    let agent = cli.agent.as_deref();
+
+    match cli.command {
+        Command::Summary => summary(&pool, range, agent, cli.format).await,
+        Command::Models => models(&pool, range, agent, cli.format).await,
+        Command::Sessions { limit } => sessions(&pool, range, agent, limit, cli.format).await,
+        Command::Tools => tools(&pool, range, agent, cli.format).await,
+        Command::Trends => trends(&pool, range, agent, cli.format).await,
+        Command::Statusline => unreachable!("handled before opening the database"),
+    }
+}
+
+/// Open the daemon's database without migrating it; the daemon owns the schema
+async fn open_pool() -> Result<SqlitePool> {
+    let db_path = shared::get_db_path()?;
+    if !db_path.exists() {
+        bail!(
+            "No usage data yet: {} does not exist. Is lumo-daemon running?",
+            db_path.display()
+        );
+    }
+    Ok(shared::create_pool(&db_path).await?)
+}
+
+async fn summary(
+    pool: &SqlitePool,
+    range: TimeRange,
+    agent: Option<&str>,
+    format: Format,
+) -> Result<()> {
+    let s = StatsAnalytics::get_summary(pool, range, agent).await?;
+
+    let mut table = Table::new(vec!["Metric", "Value"]);
+    let rows: Vec<(&str, Cell)> = vec![
+        ("Cost", Cell::Cost(s.total_cost)),
+        ("Change vs previous", Cell::Percent(s.cost_change_percent)),
+        ("Tokens", Cell::Int(s.total_tokens)),
+        ("Cache read tokens", Cell::Int(s.cache_tokens)),
+        ("Cache hit rate", Cell::Percent(s.cache_percentage)),
+        ("Sessions", Cell::Int(s.total_sessions)),
+        ("Sessions today", Cell::Int(s.today_sessions)),
+        ("Active time", Cell::Duration(s.active_time_seconds * 1000)),
+        ("Lines added", Cell::Int(s.lines_of_code_added)),
+        ("Lines removed", Cell::Int(s.lines_of_code_removed)),
+        ("Commits", Cell::Int(s.commits)),
+        ("Pull requests", Cell::Int(s.pull_requests)),
+        ("Edits accepted", Cell::Int(s.code_edit_accepts)),
+        ("Edits rejected", Cell::Int(s.code_edit_rejects)),
+    ];
+    for (name, value) in rows {
+        table.row(vec![name.into(), value]);
+    }
+    output::print(format, &s, table)
+}
+
+async fn models(
+    pool: &SqlitePool,
+    range: TimeRange,
+    agent: Option<&str>,
+    format: Format,
+) -> Result<()> {
+    let models = StatsAnalytics::get_model_stats(pool, range, agent).await?;
+    let tokens = StatsAnalytics::get_token_stats(pool, range, agent).await?;
+
+    let mut table = Table::new(vec![
+        "Model",
+        "Cost",
+        "Requests",
+        "Input",
+        "Output",
+        "Cache read",
+        "Cache write",
+    ]);
+    for m in &models {
+        let t = tokens.iter().find(|t| t.model == m.model);
+        let tokens = |f: fn(&shared::analytics::TokenStats) -> i64| Cell::Int(t.map_or(0, f));
+        table.row(vec![
+            m.display_name.as_str().into(),
+            Cell::Cost(m.cost),
+            m.requests.into(),
+            tokens(|t| t.input),
+            tokens(|t| t.output),
+            tokens(|t| t.cache_read),
+            tokens(|t| t.cache_creation),
+        ]);
+    }
+    output::print(format, &models, table)
+}
+
+async fn sessions(
+    pool: &SqlitePool,
+    range: TimeRange,
+    agent: Option<&str>,
+    limit: usize,
+    format: Format,
+) -> Result<()> {
+    let (start_time, end_time) = time_range::get_time_range_bounds(range);
+    let sessions: Vec<_> = SessionRepository::find_by_time_range(pool, start_time, end_time)
+        .await?
+        .into_iter()
+        .filter(|s| agent.is_none_or(|agent| s.agent == agent))
+        .take(limit)
+        .collect();
+
+    let mut table = Table::new(vec![
+        "Session", "Agent", "Started", "Duration", "Requests", "Tokens", "Cost",
+    ]);
+    let calendar = Calendar::current();
+    for s in &sessions {
+        let started = calendar
+            .local_millis(s.start_time)
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        table.row(vec![
+            s.id.as_str().into(),
+            format_agent_display_name(&s.agent).into(),
+            started.into(),
+            Cell::Duration(s.duration_ms),
+            s.api_request_count.into(),
+            (s.total_input_tokens + s.total_output_tokens).into(),
+            Cell::Cost(s.total_cost_usd),
+        ]);
+    }
+    output::print(format, &sessions, table)
+}
+
+async fn tools(
+    pool: &SqlitePool,
+    range: TimeRange,
+    agent: Option<&str>,
+    format: Format,
+) -> Result<()> {
+    let tools = ToolsAnalytics::get_tool_usage_stats(pool, range, agent).await?;
+
+    let mut table = Table::new(vec!["Tool", "Calls", "Failures", "Success", "Avg time"]);
+    for t in &tools {
+        let success = if t.count > 0 {
+            t.successes as f64 / t.count as f64 * 100.0
+        } else {
+            0.0
+        };
+        table.row(vec![
+            t.tool_name.as_str().into(),
+            t.count.into(),
+            t.failures.into(),
+            Cell::Percent(success),
+            Cell::Millis(t.avg_duration_ms),
+        ]);
+    }
+    output::print(format, &tools, table)
+}
+
+async fn trends(
+    pool: &SqlitePool,
+    range: TimeRange,
+    agent: Option<&str>,
+    format: Format,
+) -> Result<()> {
+    let trends = TrendsAnalytics::get_usage_trends(pool, range, agent).await?;
+
+    let mut table = Table::new(vec![
+        "Date",
+        "Cost",
+        "Input",
+        "Output",
+        "Cache read",
+        "Cache write",
+    ]);
+    for t in &trends {
+        table.row(vec![
+            t.date.as_str().into(),
+            Cell::Cost(t.cost),
+            t.input_tokens.into(),
+            t.output_tokens.into(),
+            t.cache_read_tokens.into(),
+            t.cache_creation_tokens.into(),
+        ]);
+    }
+    output::print(format, &trends, table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_range() {
+        let calendar = Calendar::parse(Some("America/New_York"), None).unwrap();
+        let now = DateTime::parse_from_rfc3339("2026-03-10T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let bound = |value| parse_bound(value).unwrap();
+        let millis = |value| {
+            DateTime::parse_from_rfc3339(value)
+                .unwrap()
+                .timestamp_millis()
+        };
+
+        assert!(custom_range(None, None, &calendar, now).unwrap().is_none());
+        assert_eq!(
+            custom_range(
+                Some(bound("2026-03-01")),
+                Some(bound("2026-03-02")),
+                &calendar,
+                now
+            )
+            .unwrap(),
+            Some(TimeRange::Custom {
+                start: millis("2026-03-01T00:00:00-05:00"),
+                end: millis("2026-03-03T00:00:00-05:00") - 1,
+            })
+        );
+        assert_eq!(
+            custom_range(Some(bound("2026-03-09T08:30:00Z")), None, &calendar, now).unwrap(),
+            Some(TimeRange::Custom {
+                start: millis("2026-03-09T08:30:00Z"),
+                end: now.timestamp_millis(),
+            })
+        );
+        assert!(custom_range(
+            Some(bound("2026-03-02")),
+            Some(bound("2026-03-01")),
+            &calendar,
+            now
+        )
+        .is_err());
+        assert!(parse_bound("March 1").is_err());
+    }
+}
@@ -0,0 +1,244 @@
+//! Output rendering: aligned tables, JSON and CSV
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// Output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Aligned columns for reading in a terminal
+    Table,
+    /// Pretty-printed JSON, the same shape the app receives
+    Json,
+    /// CSV with a header row and unformatted values
+    Csv,
+}
+
+/// A table cell. Tables format values for reading; CSV keeps them raw.
+pub enum Cell {
+    Text(String),
+    Int(i64),
+    /// US dollars
+    Cost(f64),
+    Percent(f64),
+    /// Milliseconds
+    Duration(i64),
+    /// Milliseconds, if known
+    Millis(Option<f64>),
+}
+
+impl Cell {
+    fn display(&self) -> String {
+        match self {
+            Cell::Text(s) => s.clone(),
+            Cell::Int(n) => group_thousands(*n),
+            Cell::Cost(c) => format!("${:.2}", c),
+            Cell::Percent(p) => format!("{:.1}%", p),
+            Cell::Duration(ms) => format_duration(*ms),
+            Cell::Millis(Some(ms)) => format!("{:.0}ms", ms),
+            Cell::Millis(None) => "-".to_string(),
+        }
+    }
+
+    fn raw(&self) -> String {
+        match self {
+            Cell::Text(s) => s.clone(),
+            Cell::Int(n) | Cell::Duration(n) => n.to_string(),
+            Cell::Cost(c) => format!("{:.6}", c),
+            Cell::Percent(p) => format!("{:.2}", p),
+            Cell::Millis(ms) => ms.map(|ms| format!("{:.0}", ms)).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(s: &str) -> Self {
+        Cell::Text(s.to_string())
+    }
+}
+
+impl From<String> for Cell {
+    fn from(s: String) -> Self {
+        Cell::Text(s)
+    }
+}
+
+impl From<i64> for Cell {
+    fn from(n: i64) -> Self {
+        Cell::Int(n)
+    }
+}
+
+pub struct Table {
+    header: Vec<&'static str>,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    pub fn new(header: Vec<&'static str>) -> Self {
+        Self {
+            header,
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row(&mut self, cells: Vec<Cell>) {
+        self.rows.push(cells);
+    }
+
+    /// Columns padded to their widest cell; text left-aligned, numbers right
+    fn render_text(&self) -> String {
+        let rows: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(Cell::display).collect())
+            .collect();
+        let widths: Vec<usize> = (0..self.header.len())
+            .map(|i| {
+                rows.iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|cell| cell.chars().count())
+                    .chain([self.header[i].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let numeric: Vec<bool> = (0..self.header.len())
+            .map(|i| {
+                self.rows
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .all(|cell| !matches!(cell, Cell::Text(_)))
+            })
+            .collect();
+
+        let line = |cells: &[String]| {
+            let padded: Vec<String> = cells
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| {
+                    if numeric[i] {
+                        format!("{:>width$}", cell, width = widths[i])
+                    } else {
+                        format!("{:<width$}", cell, width = widths[i])
+                    }
+                })
+                .collect();
+            padded.join("  ").trim_end().to_string()
+        };
+
+        let header: Vec<String> = self.header.iter().map(|h| h.to_string()).collect();
+        let mut out = line(&header);
+        out.push('\n');
+        let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+        out.push_str(&rule.join("  "));
+        out.push('\n');
+        for row in &rows {
+            out.push_str(&line(row));
+            out.push('\n');
+        }
+        out
+    }
+
+    fn render_csv(&self) -> String {
+        let mut out = csv_line(self.header.iter().map(|h| h.to_string()));
+        for row in &self.rows {
+            out.push_str(&csv_line(row.iter().map(Cell::raw)));
+        }
+        out
+    }
+}
+
+/// Print `data` as JSON, or `table` as a table or CSV
+pub fn print<T: Serialize + ?Sized>(format: Format, data: &T, table: Table) -> Result<()> {
+    match format {
+        Format::Table => {
+            if table.rows.is_empty() {
+                println!("No usage in this range");
+            } else {
+                print!("{}", table.render_text());
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(data)?),
+        Format::Csv => print!("{}", table.render_csv()),
+    }
+    Ok(())
+}
+
+fn csv_line(cells: impl Iterator<Item = String>) -> String {
+    let cells: Vec<String> = cells
+        .map(|cell| {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell
+            }
+        })
+        .collect();
+    format!("{}\n", cells.join(","))
+}
+
+fn group_thousands(n: i64) -> String {
+    let digits = n.unsigned_abs().to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    if n < 0 {
+        out.insert(0, '-');
+    }
+    out
+}
+
+fn format_duration(ms: i64) -> String {
+    let secs = ms / 1000;
+    let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if h > 0 {
+        format!("{}h {:02}m", h, m)
+    } else if m > 0 {
+        format!("{}m {:02}s", m, s)
+    } else {
+        format!("{}s", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_and_csv_rendering() {
+        let mut table = Table::new(vec!["Model", "Cost", "Tokens"]);
+        table.row(vec!["Opus 4.5".into(), Cell::Cost(12.5), 1_234_567.into()]);
+        table.row(vec!["Haiku, \"fast\"".into(), Cell::Cost(0.25), 42.into()]);
+
+        assert_eq!(
+            table.render_text(),
+            "\
+Model            Cost     Tokens
+-------------  ------  ---------
+Opus 4.5       $12.50  1,234,567
+Haiku, \"fast\"   $0.25         42
+"
+        );
+        assert_eq!(
+            table.render_csv(),
+            "\
+Model,Cost,Tokens
+Opus 4.5,12.500000,1234567
+\"Haiku, \"\"fast\"\"\",0.250000,42
+"
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(12_400), "12s");
+        assert_eq!(format_duration(185_000), "3m 05s");
+        assert_eq!(format_duration(3_720_000), "1h 02m");
+    }
+}
@@ -0,0 +1,157 @@
+//! `lumo statusline` — Claude Code `statusLine` command
+//!
+//! Claude Code runs the command on every prompt update with the session as
+//! JSON on stdin and shows the first line it prints, e.g.
+//! `Opus 4.5 · session $0.42 · today $3.10 / $20.00 (16%) · cache 82%`.
+//! It must be fast and must always print something: a missing database, a
+//! failed query or one slower than [`SNAPSHOT_TIMEOUT`] (e.g. a locked
+//! database) falls back to what the stdin JSON carries.
+
+use std::io::Read;
+use std::time::Duration;
+
+use serde::Deserialize;
+use shared::analytics::{SpendAnalytics, SpendSnapshot};
+
+/// How long the database lookup may take before it is skipped
+const SNAPSHOT_TIMEOUT: Duration = Duration::from_millis(80);
+
+/// The parts of Claude Code's statusline JSON Lumo uses
+#[derive(Debug, Default, Deserialize)]
+struct StatusInput {
+    session_id: Option<String>,
+    model: Option<ModelInfo>,
+    cost: Option<CostInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelInfo {
+    display_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CostInfo {
+    total_cost_usd: Option<f64>,
+}
+
+/// `daily_spend_target` in `~/.lumo/config.json`, set from the app
+#[derive(Debug, Default, Deserialize)]
+struct AppConfig {
+    daily_spend_target: Option<f64>,
+}
+
+pub async fn run() {
+    let mut stdin = String::new();
+    let _ = std::io::stdin().read_to_string(&mut stdin);
+    let input: StatusInput = serde_json::from_str(&stdin).unwrap_or_default();
+
+    let snapshot = tokio::time::timeout(SNAPSHOT_TIMEOUT, snapshot(input.session_id.as_deref()))
+        .await
+        .ok()
+        .and_then(Result::ok)
+        .flatten();
+    println!(
+        "{}",
+        render(&input, snapshot.as_ref(), daily_spend_target())
+    );
+}
+
+/// None if Lumo has no database yet
+async fn snapshot(session_id: Option<&str>) -> anyhow::Result<Option<SpendSnapshot>> {
+    let db_path = shared::get_db_path()?;
+    if !db_path.exists() {
+        return Ok(None);
+    }
+    let pool = shared::create_pool(&db_path).await?;
+    let snapshot = SpendAnalytics::snapshot(&pool, session_id).await?;
+    pool.close().await;
+    Ok(Some(snapshot))
+}
+
+fn daily_spend_target() -> Option<f64> {
+    let path = dirs::home_dir()?.join(".lumo").join("config.json");
+    let content = std::fs::read_to_string(path).ok()?;
+    let config: AppConfig = serde_json::from_str(&content).ok()?;
+    config.daily_spend_target.filter(|target| *target > 0.0)
+}
+
+fn render(input: &StatusInput, snapshot: Option<&SpendSnapshot>, target: Option<f64>) -> String {
+    let mut parts = Vec::new();
+
+    if let Some(model) = input.model.as_ref().and_then(|m| m.display_name.as_deref()) {
+        parts.push(model.to_string());
+    }
+
+    // Telemetry reaches the daemon in batches; until the session's first
+    // request lands, Claude Code's own running total is the better number
+    let session_cost = snapshot
+        .and_then(|s| s.session_cost)
+        .or_else(|| input.cost.as_ref().and_then(|c| c.total_cost_usd));
+    if let Some(cost) = session_cost {
+        parts.push(format!("session ${:.2}", cost));
+    }
+
+    if let Some(snapshot) = snapshot {
+        let today = match target {
+            Some(target) => format!(
+                "today ${:.2} / ${:.2} ({:.0}%)",
+                snapshot.today_cost,
+                target,
+                snapshot.today_cost / target * 100.0
+            ),
+            None => format!("today ${:.2}", snapshot.today_cost),
+        };
+        parts.push(today);
+
+        if let Some(rate) = snapshot.cache_hit_rate {
+            parts.push(format!("cache {:.0}%", rate));
+        }
+    }
+
+    if parts.is_empty() {
+        "lumo".to_string()
+    } else {
+        parts.join(" · ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let input: StatusInput = serde_json::from_str(
+            r#"{
+                "session_id": "abc",
+                "model": { "id": "claude-opus-4-5", "display_name": "Opus 4.5" },
+                "cost": { "total_cost_usd": 0.35 }
+            }"#,
+        )
+        .unwrap();
+        let snapshot = SpendSnapshot {
+            session_cost: Some(0.42),
+            today_cost: 3.1,
+            cache_hit_rate: Some(82.4),
+        };
+
+        assert_eq!(
+            render(&input, Some(&snapshot), Some(20.0)),
+            "Opus 4.5 · session $0.42 · today $3.10 / $20.00 (16%) · cache 82%"
+        );
+
+        // Session not recorded yet, no target
+        let snapshot = SpendSnapshot {
+            session_cost: None,
+            cache_hit_rate: None,
+            ..snapshot
+        };
+        assert_eq!(
+            render(&input, Some(&snapshot), None),
+            "Opus 4.5 · session $0.35 · today $3.10"
+        );
+
+        // No database
+        assert_eq!(render(&input, None, None), "Opus 4.5 · session $0.35");
+    }
+}
@@ -1,30 +1,164 @@
 use anyhow::{Context, Result};
+use serde::Deserialize;
 use std::env;
+use std::path::PathBuf;
+
+use shared::analytics::AnomalyThresholds;
+use shared::daemon_address;
+
+use crate::logging::{LogConfig, LogFormat};
+use crate::services::{CaptureConfig, MappingProfile};
 
 #[derive(Debug, Clone)]
 pub struct Config {
     /// Server listening address (e.g., "127.0.0.1:4318")
     pub server_address: String,
 
+    /// Optional Unix domain socket to listen on in addition to TCP
+    pub socket_path: Option<PathBuf>,
+
+    /// Bearer token required on `/notify` when set
+    pub auth_token: Option<String>,
+
     /// Log level (e.g., "info", "debug", "trace")
     pub log_level: String,
+
+    /// Log file rotation and format
+    pub log: LogConfig,
+
+    /// Codex CLI session log directory to ingest, or None if disabled
+    pub codex_sessions_dir: Option<PathBuf>,
+
+    /// User-defined attribute mapping profiles for other OTEL-emitting tools
+    pub mapping_profiles: Vec<MappingProfile>,
+
+    /// Raw payload capture settings, or None if capture is off
+    pub capture: Option<CaptureConfig>,
+
+    /// When recent spend and errors raise an anomaly notification
+    pub anomalies: AnomalyThresholds,
+}
+
+/// Settings read from the daemon config file (`~/.lumo/daemon.json`)
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileConfig {
+    #[serde(default)]
+    server_address: Option<String>,
+    #[serde(default)]
+    socket_path: Option<PathBuf>,
+    #[serde(default)]
+    auth_token: Option<String>,
+    #[serde(default)]
+    mapping_profiles: Vec<MappingProfile>,
+    #[serde(default)]
+    capture: CaptureConfig,
+    #[serde(default)]
+    log: LogConfig,
+    #[serde(default)]
+    anomalies: AnomalyThresholds,
 }
 
 impl Config {
-    /// Load configuration from environment variables
+    /// Load configuration from environment variables and the daemon config file
     pub fn from_env() -> Result<Self> {
+        let file = Self::read_file_config();
+
+        // Same precedence as `shared::daemon_address::server_address`
         let server_address = env::var("LUMO_SERVER_ADDRESS")
-            .unwrap_or_else(|_| "127.0.0.1:4318".to_string());
+            .ok()
+            .or(file.server_address)
+            .unwrap_or_else(|| daemon_address::DEFAULT_SERVER_ADDRESS.to_string());
+
+        let socket_path = env::var("LUMO_SOCKET_PATH")
+            .map(PathBuf::from)
+            .ok()
+            .or(file.socket_path);
+
+        let auth_token = env::var("LUMO_AUTH_TOKEN")
+            .ok()
+            .or(file.auth_token)
+            .filter(|t| !t.is_empty());
 
         let log_level =
             env::var("RUST_LOG").unwrap_or_else(|_| "lumo_daemon=info,tower_http=info".to_string());
 
+        let mut log = file.log;
+        match env::var("LUMO_LOG_FORMAT").as_deref() {
+            Ok("json") => log.format = LogFormat::Json,
+            Ok("text") => log.format = LogFormat::Text,
+            _ => {}
+        }
+
+        let codex_sessions_dir = Self::codex_sessions_dir_from_env();
+
+        // LUMO_CAPTURE=1 turns capture on regardless of the config file
+        let mut capture = file.capture;
+        if matches!(env::var("LUMO_CAPTURE").as_deref(), Ok("1") | Ok("true")) {
+            capture.enabled = true;
+        }
+
         Ok(Config {
             server_address,
+            socket_path,
+            auth_token,
             log_level,
+            log,
+            codex_sessions_dir,
+            mapping_profiles: file.mapping_profiles,
+            capture: Some(capture).filter(|c| c.enabled),
+            anomalies: file.anomalies,
         })
     }
 
+    /// Path of the daemon config file: `LUMO_DAEMON_CONFIG` or `~/.lumo/daemon.json`
+    fn file_config_path() -> Option<PathBuf> {
+        daemon_address::daemon_config_path().ok()
+    }
+
+    /// Read the daemon config file. A missing, unreadable or malformed file
+    /// yields defaults, so a bad edit does not keep the daemon from starting.
+    /// Logging is not set up yet, so problems go to stderr.
+    fn read_file_config() -> FileConfig {
+        let Some(path) = Self::file_config_path().filter(|p| p.exists()) else {
+            return FileConfig::default();
+        };
+        let parsed = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))
+            .and_then(|content| {
+                serde_json::from_str(&content)
+                    .with_context(|| format!("Failed to parse {}", path.display()))
+            });
+        parsed.unwrap_or_else(|e| {
+            eprintln!("Ignoring daemon config file: {:#}", e);
+            FileConfig::default()
+        })
+    }
+
+    /// Resolve the Codex sessions directory.
+    ///
+    /// `LUMO_CODEX_INGEST=0` disables ingestion. Otherwise uses
+    /// `LUMO_CODEX_SESSIONS_DIR`, then `$CODEX_HOME/sessions`, then
+    /// `~/.codex/sessions`.
+    fn codex_sessions_dir_from_env() -> Option<PathBuf> {
+        if matches!(
+            env::var("LUMO_CODEX_INGEST").as_deref(),
+            Ok("0") | Ok("false")
+        ) {
+            return None;
+        }
+
+        if let Ok(dir) = env::var("LUMO_CODEX_SESSIONS_DIR") {
+            return Some(PathBuf::from(dir));
+        }
+
+        let codex_home = env::var("CODEX_HOME")
+            .map(PathBuf::from)
+            .ok()
+            .or_else(|| dirs::home_dir().map(|h| h.join(".codex")))?;
+        Some(codex_home.join("sessions"))
+    }
+
     /// Validate configuration
     pub fn validate(&self) -> Result<()> {
         // Parse address to ensure it's valid
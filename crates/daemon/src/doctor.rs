@@ -0,0 +1,57 @@
+//! `lumo-daemon doctor` — find out why usage isn't showing up.
+//!
+//! Runs the checks in `shared::doctor` against the Claude Code settings the
+//! app manages, the running daemon and `~/.lumo/lumo.db`, and prints each
+//! result with fixes. Exits non-zero when a check fails.
+
+use anyhow::Result;
+use shared::doctor::{self, CheckStatus, ClaudeConfigRoot, DoctorReport};
+
+pub async fn run(json: bool) -> Result<()> {
+    // Open the database without migrating, so pending migrations show up
+    let db_path = shared::get_db_path()?;
+    let pool = if db_path.exists() {
+        Some(shared::create_pool(&db_path).await?)
+    } else {
+        None
+    };
+
+    let roots = ClaudeConfigRoot::discover()?;
+    let report = doctor::run(&roots, pool.as_ref()).await;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    if report.has_failures() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn print_report(report: &DoctorReport) {
+    for check in &report.checks {
+        let tag = match check.status {
+            CheckStatus::Pass => "ok",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "FAIL",
+            CheckStatus::Skip => "skip",
+        };
+        println!("[{:>4}] {}: {}", tag, check.name, check.message);
+        for fix in &check.fixes {
+            println!("       fix: {}", fix);
+        }
+    }
+
+    let count = |status| report.checks.iter().filter(|c| c.status == status).count();
+    println!();
+    println!(
+        "{} passed, {} warning(s), {} failed, {} skipped",
+        count(CheckStatus::Pass),
+        count(CheckStatus::Warn),
+        count(CheckStatus::Fail),
+        count(CheckStatus::Skip)
+    );
+}
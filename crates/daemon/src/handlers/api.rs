@@ -0,0 +1,348 @@
+//! Read-only query API handlers
+//!
+//! `/api/v1/*` serves the same aggregations as the desktop app's dashboard
+//! (`shared::analytics`) as JSON, for scripts and integrations. Every
+//! endpoint takes a time range, either
+//! `range=today|week|month|last24h|last7d|last30d|last90d|year|all` or
+//! `start`/`end` in Unix milliseconds, and an optional `agent` filter.
+
+use axum::{
+    extract::{rejection::QueryRejection, FromRequestParts, Path, Query, State},
+    http::{request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::json;
+use shared::analytics::time_range::get_time_range_bounds;
+use shared::analytics::{StatsAnalytics, TimeRange, ToolsAnalytics, TrendsAnalytics};
+use shared::{EventRepository, SessionRepository};
+use tracing::error;
+
+use crate::server::AppState;
+
+/// Default and maximum page size for `/api/v1/sessions`
+const DEFAULT_SESSION_LIMIT: usize = 50;
+const MAX_SESSION_LIMIT: usize = 500;
+const DEFAULT_EVENT_LIMIT: usize = 500;
+const MAX_EVENT_LIMIT: usize = 5000;
+
+/// Named ranges, as in the app's range picker
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RangeParam {
+    Today,
+    Week,
+    Month,
+    Last24h,
+    Last7d,
+    Last30d,
+    Last90d,
+    Year,
+    All,
+}
+
+/// Time range and agent filter shared by every endpoint, and by the MCP
+/// server's tools
+#[derive(Debug, Deserialize)]
+pub struct RangeQuery {
+    pub range: Option<RangeParam>,
+    /// Unix milliseconds; takes precedence over `range`
+    pub start: Option<i64>,
+    /// Unix milliseconds, defaults to now
+    pub end: Option<i64>,
+    pub agent: Option<String>,
+}
+
+impl RangeQuery {
+    pub fn time_range(&self) -> Result<TimeRange, String> {
+        match (self.start, self.end, self.range) {
+            (Some(_), _, Some(_)) => Err("use either range or start/end, not both".to_string()),
+            (Some(start), end, None) => {
+                let end = end.unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+                if start > end {
+                    return Err("start must not be after end".to_string());
+                }
+                Ok(TimeRange::Custom { start, end })
+            }
+            (None, Some(_), _) => Err("end requires start".to_string()),
+            (None, None, range) => Ok(match range.unwrap_or(RangeParam::Today) {
+                RangeParam::Today => TimeRange::Today,
+                RangeParam::Week => TimeRange::Week,
+                RangeParam::Month => TimeRange::Month,
+                RangeParam::Last24h => TimeRange::Last24Hours,
+                RangeParam::Last7d => TimeRange::Last7Days,
+                RangeParam::Last30d => TimeRange::Last30Days,
+                RangeParam::Last90d => TimeRange::Last90Days,
+                RangeParam::Year => TimeRange::Year,
+                RangeParam::All => TimeRange::All,
+            }),
+        }
+    }
+
+    pub fn agent(&self) -> Option<&str> {
+        self.agent.as_deref()
+    }
+}
+
+/// `group_by=...`, with the groupings each endpoint supports as `G`
+#[derive(Debug, Deserialize)]
+pub struct GroupQuery<G> {
+    pub group_by: Option<G>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SummaryGroup {
+    Agent,
+    Model,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolsGroup {
+    #[default]
+    Tool,
+    Language,
+    Date,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrendsGroup {
+    #[default]
+    Date,
+    Model,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PageQuery {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl PageQuery {
+    /// `(limit, offset)` for SQL, with the limit defaulted and capped
+    fn bounds(&self, default_limit: usize, max_limit: usize) -> (i64, i64) {
+        let limit = self.limit.unwrap_or(default_limit).min(max_limit);
+        let offset = self.offset.unwrap_or(0).min(i64::MAX as usize);
+        (limit as i64, offset as i64)
+    }
+}
+
+/// Query string extractor that rejects with a JSON [`ApiError`].
+///
+/// Each parameter group is its own extractor: serde's `flatten` would hand
+/// numbers to `serde_urlencoded` as strings, which it cannot parse.
+pub struct ApiQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ApiQuery<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state).await?;
+        Ok(Self(value))
+    }
+}
+
+/// JSON error body: `{"status": "error", "message": ...}`
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::bad_request(rejection.body_text())
+    }
+}
+
+impl From<shared::Error> for ApiError {
+    fn from(e: shared::Error) -> Self {
+        error!("Query API request failed: {}", e);
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: e.to_string(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            Json(json!({
+                "status": "error",
+                "message": self.message,
+            })),
+        )
+            .into_response()
+    }
+}
+
+type ApiResult = Result<Response, ApiError>;
+
+fn ok<T: Serialize>(data: T) -> ApiResult {
+    Ok(Json(data).into_response())
+}
+
+/// GET /api/v1/summary — totals, or per agent / model with `group_by`
+pub async fn api_summary(
+    State(state): State<AppState>,
+    ApiQuery(range): ApiQuery<RangeQuery>,
+    ApiQuery(group): ApiQuery<GroupQuery<SummaryGroup>>,
+) -> ApiResult {
+    let time_range = range.time_range().map_err(ApiError::bad_request)?;
+    let agent = range.agent();
+
+    match group.group_by {
+        None => ok(StatsAnalytics::get_summary(&state.db, time_range, agent).await?),
+        Some(SummaryGroup::Agent) => {
+            ok(StatsAnalytics::get_agent_stats(&state.db, time_range).await?)
+        }
+        Some(SummaryGroup::Model) => {
+            ok(StatsAnalytics::get_model_stats(&state.db, time_range, agent).await?)
+        }
+    }
+}
+
+/// GET /api/v1/models — cost, requests and token breakdown per model
+pub async fn api_models(
+    State(state): State<AppState>,
+    ApiQuery(range): ApiQuery<RangeQuery>,
+) -> ApiResult {
+    let time_range = range.time_range().map_err(ApiError::bad_request)?;
+    let agent = range.agent();
+
+    let models = StatsAnalytics::get_model_stats(&state.db, time_range, agent).await?;
+    let tokens = StatsAnalytics::get_token_stats(&state.db, time_range, agent).await?;
+    ok(json!({
+        "models": models,
+        "tokens": tokens,
+    }))
+}
+
+/// GET /api/v1/sessions — sessions active in the range, newest first
+pub async fn api_sessions(
+    State(state): State<AppState>,
+    ApiQuery(range): ApiQuery<RangeQuery>,
+    ApiQuery(page): ApiQuery<PageQuery>,
+) -> ApiResult {
+    let (start_time, end_time) =
+        get_time_range_bounds(range.time_range().map_err(ApiError::bad_request)?);
+    let (limit, offset) = page.bounds(DEFAULT_SESSION_LIMIT, MAX_SESSION_LIMIT);
+
+    let sessions = SessionRepository::find_by_time_range_paginated(
+        &state.db,
+        start_time,
+        end_time,
+        range.agent(),
+        limit,
+        offset,
+    )
+    .await?;
+    ok(sessions)
+}
+
+/// GET /api/v1/sessions/{id}/events — a session's events, oldest first
+pub async fn api_session_events(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+    ApiQuery(page): ApiQuery<PageQuery>,
+) -> ApiResult {
+    let (limit, offset) = page.bounds(DEFAULT_EVENT_LIMIT, MAX_EVENT_LIMIT);
+
+    let events =
+        EventRepository::find_by_session_paginated(&state.db, &session_id, limit, offset).await?;
+    if events.is_empty() && offset == 0 {
+        return Err(ApiError {
+            status: StatusCode::NOT_FOUND,
+            message: format!("Session not found: {}", session_id),
+        });
+    }
+    ok(events)
+}
+
+/// GET /api/v1/tools — per tool, per language of code edits, or per date
+pub async fn api_tools(
+    State(state): State<AppState>,
+    ApiQuery(range): ApiQuery<RangeQuery>,
+    ApiQuery(group): ApiQuery<GroupQuery<ToolsGroup>>,
+) -> ApiResult {
+    let time_range = range.time_range().map_err(ApiError::bad_request)?;
+    let agent = range.agent();
+
+    match group.group_by.unwrap_or_default() {
+        ToolsGroup::Tool => {
+            ok(ToolsAnalytics::get_tool_usage_stats(&state.db, time_range, agent).await?)
+        }
+        ToolsGroup::Language => {
+            ok(ToolsAnalytics::get_code_edit_by_language(&state.db, time_range, agent).await?)
+        }
+        ToolsGroup::Date => {
+            ok(ToolsAnalytics::get_tool_trends(&state.db, time_range, agent).await?)
+        }
+    }
+}
+
+/// GET /api/v1/trends — cost and tokens per hour or day, or cost per model
+pub async fn api_trends(
+    State(state): State<AppState>,
+    ApiQuery(range): ApiQuery<RangeQuery>,
+    ApiQuery(group): ApiQuery<GroupQuery<TrendsGroup>>,
+) -> ApiResult {
+    let time_range = range.time_range().map_err(ApiError::bad_request)?;
+    let agent = range.agent();
+
+    match group.group_by.unwrap_or_default() {
+        TrendsGroup::Date => {
+            ok(TrendsAnalytics::get_usage_trends(&state.db, time_range, agent).await?)
+        }
+        TrendsGroup::Model => {
+            ok(TrendsAnalytics::get_cost_by_model_trends(&state.db, time_range, agent).await?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range_query(query: &str) -> RangeQuery {
+        let uri = format!("/api/v1/summary?{}", query).parse().unwrap();
+        Query::try_from_uri(&uri).unwrap().0
+    }
+
+    #[test]
+    fn test_time_range_params() {
+        assert_eq!(range_query("").time_range().ok(), Some(TimeRange::Today));
+        assert_eq!(
+            range_query("range=week&agent=codex").time_range().ok(),
+            Some(TimeRange::Week)
+        );
+        assert_eq!(
+            range_query("start=1000&end=2000").time_range().ok(),
+            Some(TimeRange::Custom {
+                start: 1000,
+                end: 2000
+            })
+        );
+
+        assert!(range_query("start=2000&end=1000").time_range().is_err());
+        assert!(range_query("end=1000").time_range().is_err());
+        assert!(range_query("range=month&start=1000").time_range().is_err());
+    }
+}
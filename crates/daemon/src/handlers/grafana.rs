@@ -0,0 +1,264 @@
+//! Grafana JSON datasource handlers
+//!
+//! Implements the simple JSON datasource protocol (`/search`, `/query`,
+//! `/annotations`) under `/grafana`, so Grafana's JSON and Infinity
+//! datasources can chart Lumo data without opening the SQLite database.
+//! Targets are [`SeriesTarget`] names; sessions become annotations.
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared::analytics::{format_agent_display_name, SeriesAnalytics, SeriesTarget};
+use shared::SessionRepository;
+use tracing::error;
+
+use crate::server::AppState;
+
+/// Most annotations returned for one panel, newest sessions first
+const MAX_ANNOTATIONS: usize = 1000;
+
+#[derive(Debug, Deserialize)]
+pub struct GrafanaRange {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SearchRequest {
+    #[serde(default)]
+    pub target: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRequest {
+    pub range: GrafanaRange,
+    pub interval_ms: Option<i64>,
+    pub max_data_points: Option<i64>,
+    #[serde(default)]
+    pub targets: Vec<QueryTarget>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryTarget {
+    #[serde(default)]
+    pub target: String,
+    #[serde(rename = "type", default)]
+    pub kind: TargetKind,
+    /// Free-form target options: `{"agent": "codex"}` filters by agent
+    #[serde(default)]
+    pub payload: Value,
+    #[serde(default)]
+    pub hide: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetKind {
+    #[default]
+    Timeserie,
+    Table,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AnnotationsRequest {
+    pub range: GrafanaRange,
+    pub annotation: Value,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Annotation {
+    pub annotation: Value,
+    pub time: i64,
+    pub time_end: i64,
+    pub title: String,
+    pub text: String,
+    pub tags: Vec<String>,
+}
+
+/// GET /grafana - datasource connection test
+pub async fn grafana_health() -> impl IntoResponse {
+    StatusCode::OK
+}
+
+/// POST /grafana/search - target names matching the typed text
+pub async fn grafana_search(body: Option<Json<SearchRequest>>) -> impl IntoResponse {
+    let filter = body.map(|Json(b)| b.target).unwrap_or_default();
+    let names: Vec<&str> = SeriesTarget::ALL
+        .into_iter()
+        .map(SeriesTarget::name)
+        .filter(|name| name.contains(filter.as_str()))
+        .collect();
+    Json(names)
+}
+
+/// POST /grafana/query - time series or tables for each target
+pub async fn grafana_query(
+    State(state): State<AppState>,
+    Json(request): Json<QueryRequest>,
+) -> impl IntoResponse {
+    let start_time = request.range.from.timestamp_millis();
+    let end_time = request.range.to.timestamp_millis();
+    let interval_ms = request.interval_ms.unwrap_or_else(|| {
+        // Fall back to an interval that fits the requested number of points
+        let points = request.max_data_points.unwrap_or(1000).max(1);
+        (end_time - start_time) / points
+    });
+
+    let mut response = Vec::new();
+    for target in request
+        .targets
+        .iter()
+        .filter(|t| !t.hide && !t.target.is_empty())
+    {
+        let Some(series_target) = SeriesTarget::from_name(&target.target) else {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Unknown target: {}", target.target),
+            );
+        };
+        let agent = target.payload.get("agent").and_then(Value::as_str);
+
+        let series = match SeriesAnalytics::query(
+            &state.db,
+            series_target,
+            start_time,
+            end_time,
+            interval_ms,
+            agent,
+        )
+        .await
+        {
+            Ok(series) => series,
+            Err(e) => {
+                error!("Grafana query failed: {}", e);
+                return error_response(StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+            }
+        };
+
+        match target.kind {
+            TargetKind::Timeserie => {
+                response.extend(series.into_iter().map(|s| {
+                    let datapoints: Vec<(f64, i64)> =
+                        s.points.into_iter().map(|(t, v)| (v, t)).collect();
+                    json!({ "target": s.name, "datapoints": datapoints })
+                }));
+            }
+            TargetKind::Table => {
+                let mut rows: Vec<Value> = series
+                    .into_iter()
+                    .flat_map(|s| {
+                        s.points
+                            .into_iter()
+                            .map(move |(t, v)| json!([t, s.name.clone(), v]))
+                    })
+                    .collect();
+                rows.sort_by_key(|row| row[0].as_i64());
+                response.push(json!({
+                    "type": "table",
+                    "columns": [
+                        { "text": "Time", "type": "time" },
+                        { "text": "Series", "type": "string" },
+                        { "text": target.target, "type": "number" },
+                    ],
+                    "rows": rows,
+                }));
+            }
+        }
+    }
+
+    (StatusCode::OK, Json(Value::Array(response)))
+}
+
+/// POST /grafana/annotations - one annotation per session in the range.
+/// A non-empty annotation query filters sessions by agent.
+pub async fn grafana_annotations(
+    State(state): State<AppState>,
+    Json(request): Json<AnnotationsRequest>,
+) -> impl IntoResponse {
+    let start_time = request.range.from.timestamp_millis();
+    let end_time = request.range.to.timestamp_millis();
+    let agent = request
+        .annotation
+        .get("query")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|q| !q.is_empty());
+
+    let sessions =
+        match SessionRepository::find_by_time_range(&state.db, start_time, end_time).await {
+            Ok(sessions) => sessions,
+            Err(e) => {
+                error!("Grafana annotations query failed: {}", e);
+                return error_response(StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+            }
+        };
+
+    let annotations: Vec<Annotation> = sessions
+        .into_iter()
+        .filter(|s| agent.is_none_or(|agent| s.agent == agent))
+        .take(MAX_ANNOTATIONS)
+        .map(|s| Annotation {
+            annotation: request.annotation.clone(),
+            time: s.start_time,
+            time_end: s.end_time,
+            title: format!("{} session", format_agent_display_name(&s.agent)),
+            text: format!(
+                "{}<br>${:.2} · {} requests · {} tool calls · {} errors",
+                s.id, s.total_cost_usd, s.api_request_count, s.tool_use_count, s.error_count
+            ),
+            tags: vec![s.agent],
+        })
+        .collect();
+
+    (StatusCode::OK, Json(json!(annotations)))
+}
+
+fn error_response(status: StatusCode, message: String) -> (StatusCode, Json<Value>) {
+    (
+        status,
+        Json(json!({
+            "status": "error",
+            "message": message,
+        })),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_grafana_query() {
+        let request: QueryRequest = serde_json::from_str(
+            r#"{
+                "panelId": 1,
+                "range": {
+                    "from": "2026-01-01T00:00:00.000Z",
+                    "to": "2026-01-02T00:00:00.000Z",
+                    "raw": { "from": "now-24h", "to": "now" }
+                },
+                "intervalMs": 60000,
+                "maxDataPoints": 1440,
+                "targets": [
+                    { "target": "cost_by_model", "refId": "A", "type": "timeserie" },
+                    { "target": "tokens_by_type", "refId": "B", "type": "table",
+                      "payload": { "agent": "codex" } }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(request.range.from.timestamp_millis(), 1_767_225_600_000);
+        assert_eq!(request.interval_ms, Some(60_000));
+        assert_eq!(
+            SeriesTarget::from_name(&request.targets[0].target),
+            Some(SeriesTarget::CostByModel)
+        );
+        assert!(matches!(request.targets[1].kind, TargetKind::Table));
+        assert_eq!(request.targets[1].payload["agent"], "codex");
+    }
+}
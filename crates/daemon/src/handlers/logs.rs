@@ -5,11 +5,11 @@
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
 use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
 use serde_json::json;
-use shared::EventRepository;
-use tracing::{error, info};
+use shared::{EventRepository, QuarantineRepository};
+use tracing::{error, info, warn};
 
 use crate::server::AppState;
-use crate::services::parse_logs_to_events;
+use crate::services::{parse_logs_to_events, Signal};
 
 /// POST /v1/logs - OTLP logs/events endpoint
 pub async fn export_logs(
@@ -19,10 +19,30 @@ pub async fn export_logs(
     info!("Received OTLP logs export request");
 
     // Parse OTLP logs into our event entities
-    let events = parse_logs_to_events(&payload);
+    let batch = parse_logs_to_events(&payload, &state.mappings);
+
+    // Keep records that failed validation out of events, for later inspection
+    if !batch.quarantined.is_empty() {
+        warn!("Quarantined {} malformed events", batch.quarantined.len());
+        if let Err(e) = QuarantineRepository::insert_batch(&state.db, &batch.quarantined).await {
+            error!("Failed to quarantine events: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "status": "error",
+                    "message": format!("Failed to store quarantined events: {}", e),
+                })),
+            );
+        }
+    }
+
+    let events = batch.records;
     let count = events.len();
 
     if count == 0 {
+        state
+            .stats
+            .record_ingest(Signal::Logs, 0, batch.quarantined.len());
         return (
             StatusCode::OK,
             Json(json!({
@@ -47,6 +67,10 @@ pub async fn export_logs(
     }
 
     info!("Stored {} events", count);
+    state
+        .stats
+        .record_ingest(Signal::Logs, count, batch.quarantined.len());
+    state.alerts.request();
 
     (
         StatusCode::OK,
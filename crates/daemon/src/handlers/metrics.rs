@@ -5,11 +5,11 @@
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
 use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
 use serde_json::json;
-use shared::MetricRepository;
-use tracing::{error, info};
+use shared::{MetricRepository, QuarantineRepository};
+use tracing::{error, info, warn};
 
 use crate::server::AppState;
-use crate::services::parse_metrics;
+use crate::services::{parse_metrics, Signal};
 
 /// POST /v1/metrics - OTLP metrics endpoint
 pub async fn export_metrics(
@@ -19,10 +19,30 @@ pub async fn export_metrics(
     info!("Received OTLP metrics export request");
 
     // Parse OTLP metrics into our entities
-    let metrics = parse_metrics(&payload);
+    let batch = parse_metrics(&payload, &state.mappings);
+
+    // Keep records that failed validation out of metrics, for later inspection
+    if !batch.quarantined.is_empty() {
+        warn!("Quarantined {} malformed metrics", batch.quarantined.len());
+        if let Err(e) = QuarantineRepository::insert_batch(&state.db, &batch.quarantined).await {
+            error!("Failed to quarantine metrics: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "status": "error",
+                    "message": format!("Failed to store quarantined metrics: {}", e),
+                })),
+            );
+        }
+    }
+
+    let metrics = batch.records;
     let count = metrics.len();
 
     if count == 0 {
+        state
+            .stats
+            .record_ingest(Signal::Metrics, 0, batch.quarantined.len());
         return (
             StatusCode::OK,
             Json(json!({
@@ -47,6 +67,9 @@ pub async fn export_metrics(
     }
 
     info!("Stored {} metrics", count);
+    state
+        .stats
+        .record_ingest(Signal::Metrics, count, batch.quarantined.len());
 
     (
         StatusCode::OK,
@@ -1,11 +1,19 @@
 //! HTTP request handlers
 
+mod api;
+mod grafana;
 mod health;
 mod logs;
 mod metrics;
 mod notify;
+mod status;
 
+pub use api::{
+    api_models, api_session_events, api_sessions, api_summary, api_tools, api_trends, RangeQuery,
+};
+pub use grafana::{grafana_annotations, grafana_health, grafana_query, grafana_search};
 pub use health::health_check;
 pub use logs::export_logs;
 pub use metrics::export_metrics;
-pub use notify::notify;
+pub use notify::{notify, NotifyRequest};
+pub use status::status;
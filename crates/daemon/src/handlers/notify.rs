@@ -7,6 +7,7 @@ use shared::{NewNotification, NotificationRepository};
 use tracing::{error, info};
 
 use crate::server::AppState;
+use crate::services::Signal;
 
 /// Request payload from Claude Code hooks.
 /// Hook stdin sends snake_case JSON. Different events carry different fields.
@@ -23,30 +24,34 @@ pub struct NotifyRequest {
     pub transcript_path: Option<String>,
 }
 
+impl From<NotifyRequest> for NewNotification {
+    fn from(payload: NotifyRequest) -> Self {
+        Self {
+            session_id: payload.session_id,
+            hook_event: payload.hook_event.unwrap_or_else(|| "Unknown".to_string()),
+            notification_type: payload.notification_type,
+            title: payload.title,
+            message: payload.message,
+            agent_type: payload.agent_type,
+            cwd: payload.cwd,
+            transcript_path: payload.transcript_path,
+        }
+    }
+}
+
 /// POST /notify — receive a notification from a Claude Code hook.
 /// Stores raw hook data as-is. Formatting is done by the Tauri notification poller.
 pub async fn notify(
     State(state): State<AppState>,
     Json(payload): Json<NotifyRequest>,
 ) -> impl IntoResponse {
-    let hook_event = payload
-        .hook_event
-        .unwrap_or_else(|| "Unknown".to_string());
-
-    let notif = NewNotification {
-        session_id: payload.session_id,
-        hook_event: hook_event.clone(),
-        notification_type: payload.notification_type,
-        title: payload.title,
-        message: payload.message,
-        agent_type: payload.agent_type,
-        cwd: payload.cwd,
-        transcript_path: payload.transcript_path,
-    };
+    let notif = NewNotification::from(payload);
+    let hook_event = notif.hook_event.clone();
 
     match NotificationRepository::insert(&state.db, &notif).await {
         Ok(id) => {
             info!(id, hook_event = %hook_event, "Notification stored");
+            state.stats.record_ingest(Signal::Notify, 1, 0);
             (
                 StatusCode::OK,
                 Json(json!({
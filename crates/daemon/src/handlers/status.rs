@@ -0,0 +1,61 @@
+//! Detailed status handler
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use serde_json::json;
+use std::path::Path;
+
+use crate::server::AppState;
+
+/// GET /v1/status - Uptime, ingest counters, database and listener details
+pub async fn status(State(state): State<AppState>) -> impl IntoResponse {
+    let db_status = sqlx::query("SELECT 1").execute(&state.db).await.is_ok();
+
+    // Ask SQLite for the file it has open, so scratch databases report correctly
+    let db_path: Option<String> =
+        sqlx::query_scalar("SELECT file FROM pragma_database_list WHERE name = 'main'")
+            .fetch_optional(&state.db)
+            .await
+            .ok()
+            .flatten();
+    let file_size = |path: &str| std::fs::metadata(Path::new(path)).map(|m| m.len()).ok();
+    let db_size = db_path.as_deref().and_then(file_size);
+    let wal_size = db_path
+        .as_deref()
+        .and_then(|p| file_size(&format!("{}-wal", p)));
+
+    let migration_version: Option<i64> =
+        sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1")
+            .fetch_one(&state.db)
+            .await
+            .ok()
+            .flatten();
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let started_at = state.stats.started_at();
+
+    let status = if db_status {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status,
+        Json(json!({
+            "status": if db_status { "healthy" } else { "unhealthy" },
+            "service": "lumo-daemon",
+            "version": env!("CARGO_PKG_VERSION"),
+            "started_at": started_at,
+            "uptime_seconds": (now - started_at) / 1000,
+            "listeners": state.stats.listeners(),
+            "ingest": state.stats.snapshot(),
+            "database": {
+                "status": if db_status { "connected" } else { "disconnected" },
+                "path": db_path,
+                "size_bytes": db_size,
+                "wal_size_bytes": wal_size,
+                "migration_version": migration_version,
+            },
+        })),
+    )
+}
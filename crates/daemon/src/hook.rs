@@ -0,0 +1,149 @@
+//! `lumo-daemon hook <event>` — forward a Claude Code hook payload to the
+//! daemon.
+//!
+//! Installed as the hook command in `~/.claude/settings.json`. Reads the hook
+//! JSON from stdin and posts it to `/notify` over the configured Unix socket
+//! or TCP address, with the auth token if one is set. When the daemon is
+//! unreachable the payload is appended to the hook spool, which the daemon
+//! drains once it is back. Always exits successfully so a down daemon never
+//! blocks Claude Code.
+
+use anyhow::{bail, Context, Result};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::services::hook_spool;
+
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Outcome of a delivery attempt
+enum Delivery {
+    Delivered,
+    /// The daemon answered but refused the payload; retrying will not help
+    Rejected(String),
+}
+
+pub fn run(event: &str) -> Result<()> {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+
+    let body = match prepare_payload(&input, event) {
+        Ok(body) => body,
+        Err(e) => {
+            eprintln!("lumo hook: ignoring {} payload: {}", event, e);
+            return Ok(());
+        }
+    };
+
+    match Config::from_env().and_then(|config| send(&config, &body)) {
+        Ok(Delivery::Delivered) => {}
+        Ok(Delivery::Rejected(status)) => {
+            eprintln!("lumo hook: daemon rejected {} payload: {}", event, status);
+        }
+        Err(e) => {
+            eprintln!("lumo hook: daemon unreachable ({}), spooling", e);
+            if let Err(e) = hook_spool::append(&body) {
+                eprintln!("lumo hook: failed to spool payload: {}", e);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parse the hook JSON and fill in the event name if the payload lacks it
+fn prepare_payload(input: &str, event: &str) -> Result<String> {
+    let mut payload: serde_json::Value =
+        serde_json::from_str(input).context("stdin is not valid JSON")?;
+    let Some(object) = payload.as_object_mut() else {
+        bail!("stdin is not a JSON object");
+    };
+    object
+        .entry("hook_event_name")
+        .or_insert_with(|| event.into());
+    Ok(serde_json::to_string(&payload)?)
+}
+
+/// POST the payload to `/notify`, preferring the Unix socket when configured
+fn send(config: &Config, body: &str) -> Result<Delivery> {
+    let request = build_request(config.auth_token.as_deref(), body);
+
+    #[cfg(unix)]
+    if let Some(path) = config.socket_path.as_ref().filter(|p| p.exists()) {
+        let mut stream = std::os::unix::net::UnixStream::connect(path)
+            .with_context(|| format!("connect {}", path.display()))?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        return exchange(&mut stream, &request);
+    }
+
+    let addr = shared::daemon_address::client_address(&config.server_address)?;
+    let mut stream =
+        TcpStream::connect_timeout(&addr, TIMEOUT).with_context(|| format!("connect {}", addr))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    exchange(&mut stream, &request)
+}
+
+fn build_request(auth_token: Option<&str>, body: &str) -> String {
+    let auth = auth_token
+        .map(|token| format!("Authorization: Bearer {}\r\n", token))
+        .unwrap_or_default();
+    format!(
+        "POST /notify HTTP/1.1\r\n\
+         Host: localhost\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\
+         {}\r\n\
+         {}",
+        body.len(),
+        auth,
+        body
+    )
+}
+
+/// Write the request and classify the response status line
+fn exchange<S: Read + Write>(stream: &mut S, request: &str) -> Result<Delivery> {
+    stream.write_all(request.as_bytes())?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+    let response = String::from_utf8_lossy(&response);
+    let status = response
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse::<u16>().ok())
+        .context("malformed HTTP response")?;
+
+    match status {
+        200..=299 => Ok(Delivery::Delivered),
+        // Server-side failures are worth retrying from the spool
+        500..=599 => bail!("HTTP {}", status),
+        _ => Ok(Delivery::Rejected(format!("HTTP {}", status))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prepare_payload_fills_missing_event() {
+        let body = prepare_payload(r#"{"session_id":"s1"}"#, "Stop").unwrap();
+        let value: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(value["hook_event_name"], "Stop");
+
+        let body = prepare_payload(
+            r#"{"session_id":"s1","hook_event_name":"Notification"}"#,
+            "Stop",
+        )
+        .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(value["hook_event_name"], "Notification");
+
+        assert!(prepare_payload("[]", "Stop").is_err());
+    }
+}
@@ -0,0 +1,419 @@
+//! Daemon logging
+//!
+//! Logs go to rotating files in `~/.lumo/logs/` (see `shared::daemon_logs`)
+//! instead of growing the service manager's stdout redirect without bound.
+//! A file is rotated when it exceeds `maxFileBytes` or the rotation period
+//! (local hour/day) changes, and the oldest files beyond `maxFiles` are
+//! deleted. File names carry the UTC creation time, so they sort in order
+//! across DST changes.
+//! Output is plain text or, with `format: "json"`, one JSON object per line.
+//!
+//! Configure in `~/.lumo/daemon.json`; `LUMO_LOG_FORMAT` overrides the format:
+//!
+//! ```json
+//! { "log": { "format": "json", "rotation": "daily", "maxFileBytes": 10485760, "maxFiles": 10 } }
+//! ```
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, MakeWriter};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+use shared::daemon_logs::{self, LOG_FILE_EXTENSION, LOG_FILE_PREFIX};
+
+const DEFAULT_MAX_FILE_BYTES: u64 = 10 * 1024 * 1024;
+const DEFAULT_MAX_FILES: usize = 10;
+
+/// Log line format
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Time-based rotation period
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rotation {
+    Hourly,
+    #[default]
+    Daily,
+    /// Rotate by size only
+    Never,
+}
+
+impl Rotation {
+    /// Key that changes when a new local period starts
+    fn period_key(self, time: DateTime<Utc>) -> String {
+        let time = time.with_timezone(&Local);
+        match self {
+            Rotation::Hourly => time.format("%Y%m%d%H").to_string(),
+            Rotation::Daily => time.format("%Y%m%d").to_string(),
+            Rotation::Never => String::new(),
+        }
+    }
+}
+
+/// Log settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogConfig {
+    #[serde(default)]
+    pub format: LogFormat,
+    /// Log directory, defaults to `~/.lumo/logs`
+    #[serde(default)]
+    pub dir: Option<PathBuf>,
+    #[serde(default)]
+    pub rotation: Rotation,
+    /// Rotate to a new file once the current one exceeds this size
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
+    /// Oldest files beyond this count are deleted on rotation
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+}
+
+fn default_max_file_bytes() -> u64 {
+    DEFAULT_MAX_FILE_BYTES
+}
+
+fn default_max_files() -> usize {
+    DEFAULT_MAX_FILES
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::default(),
+            dir: None,
+            rotation: Rotation::default(),
+            max_file_bytes: DEFAULT_MAX_FILE_BYTES,
+            max_files: DEFAULT_MAX_FILES,
+        }
+    }
+}
+
+/// Install the global subscriber for the server: rotating files, plus
+/// stdout when attached to a terminal (or when the files can't be opened).
+pub fn init(config: &LogConfig, filter: &str) {
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| filter.to_string().into());
+
+    let file_writer = match RotatingFileWriter::new(config) {
+        Ok(writer) => Some(writer),
+        Err(e) => {
+            eprintln!("File logging disabled: {:#}", e);
+            None
+        }
+    };
+    let to_stdout = file_writer.is_none() || std::io::stdout().is_terminal();
+    let dir = file_writer.as_ref().map(|w| w.dir.clone());
+
+    let (text_file, json_file) = match (file_writer, config.format) {
+        (Some(writer), LogFormat::Text) => (
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(writer),
+            ),
+            None,
+        ),
+        (Some(writer), LogFormat::Json) => (
+            None,
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .event_format(JsonFormat)
+                    .with_writer(writer),
+            ),
+        ),
+        (None, _) => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(text_file)
+        .with(json_file)
+        .with(to_stdout.then(tracing_subscriber::fmt::layer))
+        .init();
+
+    if let Some(dir) = dir {
+        tracing::info!("Logging to {}", dir.display());
+    }
+}
+
+/// Appends log lines to size/time-rotated files
+pub struct RotatingFileWriter {
+    dir: PathBuf,
+    rotation: Rotation,
+    max_file_bytes: u64,
+    max_files: usize,
+    current: Mutex<Option<LogFile>>,
+}
+
+struct LogFile {
+    file: File,
+    written: u64,
+    period: String,
+}
+
+impl RotatingFileWriter {
+    /// Create a writer, creating the log directory if needed
+    pub fn new(config: &LogConfig) -> Result<Self> {
+        let dir = match &config.dir {
+            Some(dir) => dir.clone(),
+            None => daemon_logs::default_log_dir()?,
+        };
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        Ok(Self {
+            dir,
+            rotation: config.rotation,
+            max_file_bytes: config.max_file_bytes,
+            max_files: config.max_files.max(1),
+            current: Mutex::new(None),
+        })
+    }
+
+    fn write_line(&self, buf: &[u8]) -> std::io::Result<()> {
+        let now = Utc::now();
+        let period = self.rotation.period_key(now);
+
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        let needs_rotation = match current.as_ref() {
+            None => true,
+            Some(log) => log.written >= self.max_file_bytes || log.period != period,
+        };
+        if needs_rotation {
+            *current = Some(self.open(current.is_none(), now, period)?);
+        }
+
+        let log = current.as_mut().expect("log file opened above");
+        log.file.write_all(buf)?;
+        log.written += buf.len() as u64;
+        Ok(())
+    }
+
+    /// Open the file to write to. On the first write after startup the
+    /// newest file is reused if it is in the current period and not full,
+    /// so restarts don't each start a file.
+    fn open(&self, resume: bool, now: DateTime<Utc>, period: String) -> std::io::Result<LogFile> {
+        let files = daemon_logs::log_files(&self.dir).map_err(std::io::Error::other)?;
+
+        if resume {
+            if let Some(latest) = files.last() {
+                let size = fs::metadata(latest)?.len();
+                let same_period =
+                    file_time(latest).is_some_and(|time| self.rotation.period_key(time) == period);
+                if same_period && size < self.max_file_bytes {
+                    let file = OpenOptions::new().append(true).open(latest)?;
+                    return Ok(LogFile {
+                        file,
+                        written: size,
+                        period,
+                    });
+                }
+            }
+        }
+
+        let path = self.dir.join(format!(
+            "{}{}.{}",
+            LOG_FILE_PREFIX,
+            now.format("%Y%m%d-%H%M%S-%3f"),
+            LOG_FILE_EXTENSION
+        ));
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        // `files` excludes the new file, so keep one fewer
+        let excess = (files.len() + 1).saturating_sub(self.max_files);
+        for old in &files[..excess.min(files.len())] {
+            let _ = fs::remove_file(old);
+        }
+
+        Ok(LogFile {
+            file,
+            written: 0,
+            period,
+        })
+    }
+}
+
+/// UTC creation time embedded in a log file name
+fn file_time(path: &Path) -> Option<DateTime<Utc>> {
+    let stem = path.file_stem()?.to_str()?.strip_prefix(LOG_FILE_PREFIX)?;
+    let time = NaiveDateTime::parse_from_str(stem.get(..15)?, "%Y%m%d-%H%M%S").ok()?;
+    Some(time.and_utc())
+}
+
+impl Write for &RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_line(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingFileWriter {
+    type Writer = &'a RotatingFileWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self
+    }
+}
+
+/// One JSON object per event:
+/// `{"timestamp", "level", "target", "message", "fields", "spans"}`
+struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut fields = JsonVisitor::default();
+        event.record(&mut fields);
+        let mut fields = fields.0;
+
+        let mut line = Map::new();
+        line.insert(
+            "timestamp".to_string(),
+            Value::String(chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, true)),
+        );
+        line.insert(
+            "level".to_string(),
+            Value::String(metadata.level().to_string()),
+        );
+        line.insert(
+            "target".to_string(),
+            Value::String(metadata.target().to_string()),
+        );
+        line.insert(
+            "message".to_string(),
+            fields
+                .remove("message")
+                .unwrap_or(Value::String(String::new())),
+        );
+        if !fields.is_empty() {
+            line.insert("fields".to_string(), Value::Object(fields));
+        }
+        if let Some(scope) = ctx.event_scope() {
+            let spans: Vec<Value> = scope
+                .from_root()
+                .map(|span| Value::String(span.name().to_string()))
+                .collect();
+            line.insert("spans".to_string(), Value::Array(spans));
+        }
+
+        writeln!(writer, "{}", Value::Object(line))
+    }
+}
+
+/// Collects event fields as JSON values
+#[derive(Default)]
+struct JsonVisitor(Map<String, Value>);
+
+impl Visit for JsonVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(
+            field.name().to_string(),
+            Value::String(format!("{:?}", value)),
+        );
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0
+            .insert(field.name().to_string(), Value::String(value.to_string()));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_rotation_keeps_max_files() {
+        let dir = std::env::temp_dir().join(format!("lumo-logs-{}", uuid::Uuid::new_v4()));
+        let writer = RotatingFileWriter::new(&LogConfig {
+            dir: Some(dir.clone()),
+            rotation: Rotation::Never,
+            max_file_bytes: 10,
+            max_files: 2,
+            ..LogConfig::default()
+        })
+        .unwrap();
+
+        for i in 0..4 {
+            (&writer)
+                .write_all(format!("line {}\n", i).as_bytes())
+                .unwrap();
+            (&writer).write_all(b"more\n").unwrap();
+            // Distinct file names need distinct milliseconds
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+
+        let files = daemon_logs::log_files(&dir).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(
+            fs::read_to_string(files.last().unwrap()).unwrap(),
+            "line 3\nmore\n"
+        );
+
+        // A restart resumes the newest file while it has room
+        drop(writer);
+        let writer = RotatingFileWriter::new(&LogConfig {
+            dir: Some(dir.clone()),
+            rotation: Rotation::Never,
+            max_file_bytes: 100,
+            max_files: 2,
+            ..LogConfig::default()
+        })
+        .unwrap();
+        (&writer).write_all(b"after restart\n").unwrap();
+        let files = daemon_logs::log_files(&dir).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(
+            fs::read_to_string(files.last().unwrap()).unwrap(),
+            "line 3\nmore\nafter restart\n"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -4,11 +4,19 @@
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use tracing::{error, info};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use tracing::{error, info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod config;
+mod doctor;
 mod handlers;
+mod hook;
+mod logging;
+mod mcp;
+mod quarantine;
+mod replay;
 mod routes;
 mod server;
 mod services;
@@ -32,6 +40,32 @@ enum Command {
         #[arg(long)]
         delete_data: bool,
     },
+    /// Forward a Claude Code hook payload (stdin) to the daemon
+    Hook {
+        /// Hook event name, e.g. Notification or Stop
+        event: String,
+    },
+    /// Inspect, re-attribute or purge quarantined telemetry
+    Quarantine {
+        #[command(subcommand)]
+        command: quarantine::QuarantineCommand,
+    },
+    /// Diagnose why usage isn't showing up: settings, daemon, auth, database
+    Doctor {
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Serve Lumo usage to Claude Code as an MCP server over stdio
+    Mcp,
+    /// Re-post captured request payloads into a scratch database
+    Replay {
+        /// Capture directory (e.g. ~/.lumo/capture) or a single capture file
+        dir: PathBuf,
+        /// Database to replay into; defaults to a fresh file in the temp directory
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
 }
 
 #[tokio::main]
@@ -46,6 +80,20 @@ async fn main() -> Result<()> {
                 .init();
             uninstall::run(delete_data).await
         }
+        Some(Command::Hook { event }) => hook::run(&event),
+        Some(Command::Quarantine { command }) => quarantine::run(command).await,
+        Some(Command::Doctor { json }) => doctor::run(json).await,
+        Some(Command::Mcp) => mcp::run().await,
+        Some(Command::Replay { dir, db }) => {
+            tracing_subscriber::registry()
+                .with(
+                    tracing_subscriber::EnvFilter::try_from_default_env()
+                        .unwrap_or_else(|_| "lumo_daemon=info".into()),
+                )
+                .with(tracing_subscriber::fmt::layer())
+                .init();
+            replay::run(&dir, db).await
+        }
         None => run_server().await,
     }
 }
@@ -56,13 +104,7 @@ async fn run_server() -> Result<()> {
     config.validate()?;
 
     // Initialize tracing/logging
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| config.log_level.clone().into()),
-        )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+    logging::init(&config.log, &config.log_level);
 
     info!("Starting Lumo Daemon v{}", env!("CARGO_PKG_VERSION"));
 
@@ -77,8 +119,41 @@ async fn run_server() -> Result<()> {
     shared::run_migrations(&pool).await?;
     info!("Database migrations completed");
 
+    for profile in &config.mapping_profiles {
+        info!("Loaded attribute mapping profile: {}", profile.name);
+    }
+
     // Create application state
-    let state = AppState::new(pool, config.clone());
+    let mut state = AppState::new(pool, config.clone());
+
+    // Opt-in raw payload capture
+    if let Some(capture) = &config.capture {
+        match services::CaptureWriter::new(capture) {
+            Ok(writer) => {
+                info!("Capturing raw payloads to {}", writer.dir().display());
+                state = state.with_capture(writer);
+            }
+            Err(e) => warn!("Payload capture disabled: {}", e),
+        }
+    }
+
+    // Check budgets and anomalies as events arrive
+    state.alerts.start(state.db.clone(), config.anomalies.clone());
+
+    // Start Codex CLI session log ingestion
+    if let Some(dir) = &config.codex_sessions_dir {
+        services::codex_ingest::start(
+            state.db.clone(),
+            dir.clone(),
+            state.stats.clone(),
+            state.alerts.clone(),
+        );
+    }
+
+    // Store hook payloads spooled while the daemon was down
+    services::hook_spool::start(state.db.clone(), state.stats.clone());
+
+    let stats = state.stats.clone();
 
     // Create Axum app
     let app = create_app(state);
@@ -91,18 +166,74 @@ async fn run_server() -> Result<()> {
             e
         })?;
 
+    stats.add_listener(format!("http://{}", listener.local_addr()?));
+
     info!("Server listening on http://{}", listener.local_addr()?);
     info!("Health check: http://{}/health", listener.local_addr()?);
+    info!("Status:       http://{}/v1/status", listener.local_addr()?);
+    info!("Query API:    http://{}/api/v1 (loopback only)", listener.local_addr()?);
+    info!("Grafana:      http://{}/grafana (loopback only)", listener.local_addr()?);
     info!("OTLP endpoints:");
     info!("  - Metrics: http://{}/v1/metrics", listener.local_addr()?);
     info!("  - Logs:    http://{}/v1/logs", listener.local_addr()?);
+    if config.auth_token.is_some() {
+        info!("Hook endpoint requires bearer token");
+    }
     info!("Press Ctrl+C to stop");
 
-    // Run server with graceful shutdown
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
+    // One shutdown signal fans out to every listener
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(());
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        let _ = shutdown_tx.send(());
+    });
+    let shutdown = move || {
+        let mut rx = shutdown_rx.clone();
+        async move {
+            let _ = rx.changed().await;
+        }
+    };
+
+    // Optional Unix domain socket alongside TCP
+    #[cfg(unix)]
+    if let Some(path) = &config.socket_path {
+        // A socket file left by an unclean exit blocks bind
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        let uds = tokio::net::UnixListener::bind(path).map_err(|e| {
+            error!("Failed to bind to {}: {}", path.display(), e);
+            e
+        })?;
+        stats.add_listener(format!("unix:{}", path.display()));
+        info!("Server listening on unix:{}", path.display());
+
+        let app = app.clone();
+        let shutdown = shutdown.clone();
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(uds, app)
+                .with_graceful_shutdown(shutdown())
+                .await
+            {
+                error!("Unix socket server failed: {}", e);
+            }
+        });
+    }
+
+    // Run server with graceful shutdown. The peer address lets the query API
+    // refuse non-loopback clients.
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+        .with_graceful_shutdown(shutdown())
         .await?;
 
+    #[cfg(unix)]
+    if let Some(path) = &config.socket_path {
+        let _ = std::fs::remove_file(path);
+    }
+
     info!("Server shut down gracefully");
     Ok(())
 }
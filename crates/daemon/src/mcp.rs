@@ -0,0 +1,445 @@
+//! `lumo-daemon mcp` — Model Context Protocol server over stdio
+//!
+//! Lets Claude Code answer questions like "how much did this session cost
+//! so far" from Lumo's database. Claude Code starts the server from its MCP
+//! config and exchanges newline-delimited JSON-RPC 2.0 messages on
+//! stdin/stdout; stdout carries nothing else. The tools are read-only and
+//! use the same queries as the app and the `/api/v1` endpoints.
+
+use anyhow::{bail, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared::analytics::{SpendAnalytics, StatsAnalytics, ToolsAnalytics};
+use shared::{EventRepository, SessionRepository};
+use sqlx::SqlitePool;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::handlers::RangeQuery;
+
+/// Newest protocol revision this server implements
+const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Revisions a client may ask for; the tools used here are the same in all
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26", "2025-06-18"];
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+const MAX_DAILY_SPEND_DAYS: u32 = 90;
+
+pub async fn run() -> Result<()> {
+    // Only the running daemon creates and migrates the database
+    let db_path = shared::get_db_path()?;
+    if !db_path.exists() {
+        bail!(
+            "No usage data yet: {} does not exist. Is lumo-daemon running?",
+            db_path.display()
+        );
+    }
+    let pool = shared::create_pool(&db_path).await?;
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => handle_message(&pool, &message).await,
+            Err(e) => Some(error_response(
+                Value::Null,
+                PARSE_ERROR,
+                format!("Parse error: {}", e),
+            )),
+        };
+        if let Some(response) = response {
+            stdout.write_all(response.to_string().as_bytes()).await?;
+            stdout.write_all(b"\n").await?;
+            stdout.flush().await?;
+        }
+    }
+    Ok(())
+}
+
+/// Answer one JSON-RPC message. Notifications get no response.
+async fn handle_message(pool: &SqlitePool, message: &Value) -> Option<Value> {
+    let id = message.get("id").cloned();
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        // Responses to requests we never send, or garbage
+        return id.map(|id| error_response(id, INVALID_REQUEST, "Invalid request".to_string()));
+    };
+    let id = id?;
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match method {
+        "initialize" => Ok(initialize(&params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => call_tool(pool, &params).await,
+        _ => Err((METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+    };
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, message),
+    })
+}
+
+fn initialize(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let version = requested
+        .filter(|v| SUPPORTED_PROTOCOL_VERSIONS.contains(v))
+        .unwrap_or(PROTOCOL_VERSION);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": { "listChanged": false } },
+        "serverInfo": { "name": "lumo", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "Lumo records Claude Code and Codex usage from OpenTelemetry. \
+            Use these tools for cost, token, session and tool-usage questions. \
+            Costs are in US dollars.",
+    })
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+/// JSON Schema of the time range arguments shared by several tools
+fn range_properties() -> Value {
+    json!({
+        "range": {
+            "type": "string",
+            "enum": ["today", "week", "month", "last24h", "last7d", "last30d", "last90d", "year", "all"],
+            "description": "Since local midnight, Monday, the 1st of the month or January 1st; a rolling window; or all time. Defaults to today.",
+        },
+        "start": {
+            "type": "integer",
+            "description": "Start of a custom range in Unix milliseconds, instead of range",
+        },
+        "end": {
+            "type": "integer",
+            "description": "End of a custom range in Unix milliseconds. Defaults to now.",
+        },
+        "agent": {
+            "type": "string",
+            "description": "Only count usage from this agent: claude_code or codex",
+        },
+    })
+}
+
+fn tool_definitions() -> Value {
+    let with_range = |extra: Value| {
+        let mut properties = range_properties();
+        if let (Some(properties), Value::Object(extra)) = (properties.as_object_mut(), extra) {
+            properties.extend(extra);
+        }
+        json!({ "type": "object", "properties": properties })
+    };
+
+    json!([
+        {
+            "name": "get_usage_summary",
+            "description": "Total cost, tokens, cache hit rate, sessions and code changes \
+                for a time range, with cost per model.",
+            "inputSchema": with_range(json!({})),
+        },
+        {
+            "name": "get_session_cost",
+            "description": "Cost, tokens, requests and duration of one session, with cost \
+                per model. Without session_id, reports the most recently active session, \
+                which is usually the current one.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "session_id": { "type": "string", "description": "Claude Code session ID" },
+                },
+            },
+        },
+        {
+            "name": "list_top_tools",
+            "description": "Tools ranked by calls, failures or failure rate, with success \
+                counts and average duration.",
+            "inputSchema": with_range(json!({
+                "sort_by": {
+                    "type": "string",
+                    "enum": ["calls", "failures", "failure_rate"],
+                    "description": "Ranking. Defaults to calls.",
+                },
+                "limit": { "type": "integer", "description": "Defaults to 10" },
+            })),
+        },
+        {
+            "name": "get_daily_spend",
+            "description": "API spend per local calendar day, including days without usage.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "days": {
+                        "type": "integer",
+                        "description": "Number of days up to and including today. Defaults to 7, at most 90.",
+                    },
+                    "agent": {
+                        "type": "string",
+                        "description": "Only count usage from this agent: claude_code or codex",
+                    },
+                },
+            },
+        },
+    ])
+}
+
+#[derive(Debug, Deserialize)]
+struct CallParams {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SessionArgs {
+    session_id: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TopToolsArgs {
+    sort_by: Option<ToolSort>,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ToolSort {
+    Calls,
+    Failures,
+    FailureRate,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct DailySpendArgs {
+    days: Option<u32>,
+    agent: Option<String>,
+}
+
+/// `tools/call`. Unknown tools and malformed arguments are protocol errors;
+/// failures while answering are reported in the result with `isError` so
+/// the model sees them.
+async fn call_tool(pool: &SqlitePool, params: &Value) -> Result<Value, (i64, String)> {
+    let call: CallParams = serde_json::from_value(params.clone())
+        .map_err(|e| (INVALID_PARAMS, format!("Invalid tools/call params: {}", e)))?;
+    let arguments = if call.arguments.is_null() {
+        json!({})
+    } else {
+        call.arguments
+    };
+    let invalid = |e: serde_json::Error| (INVALID_PARAMS, format!("Invalid arguments: {}", e));
+
+    let output = match call.name.as_str() {
+        "get_usage_summary" => {
+            let range: RangeQuery = serde_json::from_value(arguments).map_err(invalid)?;
+            usage_summary(pool, &range).await
+        }
+        "get_session_cost" => {
+            let args: SessionArgs = serde_json::from_value(arguments).map_err(invalid)?;
+            session_cost(pool, args.session_id.as_deref()).await
+        }
+        "list_top_tools" => {
+            let range: RangeQuery = serde_json::from_value(arguments.clone()).map_err(invalid)?;
+            let args: TopToolsArgs = serde_json::from_value(arguments).map_err(invalid)?;
+            top_tools(pool, &range, &args).await
+        }
+        "get_daily_spend" => {
+            let args: DailySpendArgs = serde_json::from_value(arguments).map_err(invalid)?;
+            daily_spend(pool, &args).await
+        }
+        name => return Err((INVALID_PARAMS, format!("Unknown tool: {}", name))),
+    };
+
+    Ok(match output {
+        Ok(value) => json!({
+            "content": [{ "type": "text", "text": serde_json::to_string_pretty(&value).unwrap_or_default() }],
+            "isError": false,
+        }),
+        Err(message) => json!({
+            "content": [{ "type": "text", "text": message }],
+            "isError": true,
+        }),
+    })
+}
+
+type ToolResult = std::result::Result<Value, String>;
+
+async fn usage_summary(pool: &SqlitePool, range: &RangeQuery) -> ToolResult {
+    let time_range = range.time_range()?;
+    let summary = StatsAnalytics::get_summary(pool, time_range, range.agent())
+        .await
+        .map_err(|e| e.to_string())?;
+    let models = StatsAnalytics::get_model_stats(pool, time_range, range.agent())
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(json!({ "summary": summary, "models": models }))
+}
+
+async fn session_cost(pool: &SqlitePool, session_id: Option<&str>) -> ToolResult {
+    let session = match session_id {
+        Some(id) => match SessionRepository::find_by_id(pool, id).await {
+            Ok(session) => Some(session),
+            Err(shared::Error::NotFound(_)) => None,
+            Err(e) => return Err(e.to_string()),
+        },
+        None => SessionRepository::find_most_recent(pool)
+            .await
+            .map_err(|e| e.to_string())?,
+    };
+    let Some(session) = session else {
+        return Err(match session_id {
+            Some(id) => format!("No usage recorded for session {}", id),
+            None => "No sessions recorded yet".to_string(),
+        });
+    };
+
+    // Cost per model, largest first
+    let mut models: Vec<(String, f64, i64)> = Vec::new();
+    for event in EventRepository::find_api_requests(pool, &session.id)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        let model = event.model.unwrap_or_else(|| "unknown".to_string());
+        let cost = event.cost_usd.unwrap_or(0.0);
+        match models.iter_mut().find(|(m, _, _)| *m == model) {
+            Some(entry) => {
+                entry.1 += cost;
+                entry.2 += 1;
+            }
+            None => models.push((model, cost, 1)),
+        }
+    }
+    models.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    Ok(json!({
+        "session": session,
+        "models": models
+            .into_iter()
+            .map(|(model, cost, requests)| json!({ "model": model, "cost": cost, "requests": requests }))
+            .collect::<Vec<_>>(),
+    }))
+}
+
+async fn top_tools(pool: &SqlitePool, range: &RangeQuery, args: &TopToolsArgs) -> ToolResult {
+    let mut tools = ToolsAnalytics::get_tool_usage_stats(pool, range.time_range()?, range.agent())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let failure_rate = |count: i64, failures: i64| {
+        if count > 0 {
+            failures as f64 / count as f64
+        } else {
+            0.0
+        }
+    };
+    match args.sort_by.unwrap_or(ToolSort::Calls) {
+        ToolSort::Calls => tools.sort_by_key(|t| std::cmp::Reverse(t.count)),
+        ToolSort::Failures => tools.sort_by_key(|t| std::cmp::Reverse(t.failures)),
+        ToolSort::FailureRate => tools.sort_by(|a, b| {
+            failure_rate(b.count, b.failures).total_cmp(&failure_rate(a.count, a.failures))
+        }),
+    }
+    tools.truncate(args.limit.unwrap_or(10));
+
+    Ok(json!(tools
+        .into_iter()
+        .map(|t| json!({
+            "tool": t.tool_name,
+            "calls": t.count,
+            "successes": t.successes,
+            "failures": t.failures,
+            "failureRate": failure_rate(t.count, t.failures),
+            "avgDurationMs": t.avg_duration_ms,
+        }))
+        .collect::<Vec<_>>()))
+}
+
+async fn daily_spend(pool: &SqlitePool, args: &DailySpendArgs) -> ToolResult {
+    let days = args.days.unwrap_or(7).clamp(1, MAX_DAILY_SPEND_DAYS);
+    let spend = SpendAnalytics::daily_spend(pool, days, args.agent.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+    let total: f64 = spend.iter().map(|d| d.cost).sum();
+    Ok(json!({ "days": spend, "total": total }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn request(pool: &SqlitePool, message: Value) -> Option<Value> {
+        handle_message(pool, &message).await
+    }
+
+    #[tokio::test]
+    async fn test_protocol_messages() {
+        // Nothing here touches the database
+        let pool = SqlitePool::connect_lazy("sqlite::memory:").unwrap();
+
+        let init = request(
+            &pool,
+            json!({
+                "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                "params": { "protocolVersion": "2025-03-26", "capabilities": {},
+                            "clientInfo": { "name": "claude-code", "version": "2.0.0" } }
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(init["id"], 1);
+        assert_eq!(init["result"]["protocolVersion"], "2025-03-26");
+        assert_eq!(init["result"]["serverInfo"]["name"], "lumo");
+
+        let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert_eq!(request(&pool, notification).await, None);
+
+        let list = request(
+            &pool,
+            json!({ "jsonrpc": "2.0", "id": "a", "method": "tools/list" }),
+        )
+        .await
+        .unwrap();
+        let names: Vec<&str> = list["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "get_usage_summary",
+                "get_session_cost",
+                "list_top_tools",
+                "get_daily_spend"
+            ]
+        );
+
+        let unknown = request(
+            &pool,
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "resources/list" }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+
+        let bad_tool = request(
+            &pool,
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "tools/call",
+                    "params": { "name": "drop_tables", "arguments": {} } }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(bad_tool["error"]["code"], INVALID_PARAMS);
+    }
+}
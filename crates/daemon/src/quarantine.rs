@@ -0,0 +1,100 @@
+//! `lumo-daemon quarantine` — inspect, re-attribute and purge telemetry that
+//! failed validation during ingest.
+
+use anyhow::{bail, Result};
+use clap::Subcommand;
+use shared::QuarantineRepository;
+
+#[derive(Subcommand)]
+pub enum QuarantineCommand {
+    /// List quarantined records (newest first) and counts per reason
+    List {
+        /// Only show records with this reason (e.g. missing_session_id)
+        #[arg(long)]
+        reason: Option<String>,
+        /// Maximum number of records to show
+        #[arg(long, default_value_t = 50)]
+        limit: i64,
+        /// Print the stored record payloads
+        #[arg(long)]
+        payload: bool,
+    },
+    /// Move records into events/metrics, optionally assigning a session
+    Reattribute {
+        /// Quarantine record IDs
+        #[arg(required = true)]
+        ids: Vec<i64>,
+        /// Session to attribute the records to (required if they have none)
+        #[arg(long)]
+        session: Option<String>,
+    },
+    /// Delete quarantined records
+    Purge {
+        /// Quarantine record IDs to delete
+        ids: Vec<i64>,
+        /// Delete every quarantined record (or every one with --reason)
+        #[arg(long, conflicts_with = "ids")]
+        all: bool,
+        /// With --all, only delete records with this reason
+        #[arg(long, requires = "all")]
+        reason: Option<String>,
+    },
+}
+
+pub async fn run(command: QuarantineCommand) -> Result<()> {
+    let pool = shared::create_pool(&shared::get_db_path()?).await?;
+    shared::run_migrations(&pool).await?;
+
+    match command {
+        QuarantineCommand::List {
+            reason,
+            limit,
+            payload,
+        } => {
+            let counts = QuarantineRepository::count_by_reason(&pool).await?;
+            if counts.is_empty() {
+                println!("Quarantine is empty");
+                return Ok(());
+            }
+            for c in &counts {
+                println!("{:>8}  {}", c.count, c.reason);
+            }
+            println!();
+
+            let records =
+                QuarantineRepository::find_recent(&pool, reason.as_deref(), limit, 0).await?;
+            for r in records {
+                println!(
+                    "#{} {} {} {} session={} ts={} agent={}{}",
+                    r.id,
+                    r.signal,
+                    r.reason,
+                    r.name,
+                    r.session_id.as_deref().unwrap_or("-"),
+                    r.timestamp,
+                    r.agent,
+                    r.detail.map(|d| format!(" ({})", d)).unwrap_or_default(),
+                );
+                if payload {
+                    println!("    {}", r.payload);
+                }
+            }
+        }
+        QuarantineCommand::Reattribute { ids, session } => {
+            let moved = QuarantineRepository::reattribute(&pool, &ids, session.as_deref()).await?;
+            println!("Re-attributed {} record(s)", moved);
+        }
+        QuarantineCommand::Purge { ids, all, reason } => {
+            let deleted = if all {
+                QuarantineRepository::delete_all(&pool, reason.as_deref()).await?
+            } else if !ids.is_empty() {
+                QuarantineRepository::delete(&pool, &ids).await?
+            } else {
+                bail!("Pass record IDs or --all");
+            };
+            println!("Purged {} record(s)", deleted);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+//! `lumo-daemon replay` — re-post captured requests into a scratch database.
+//!
+//! Requests go through the same router and handlers as live traffic, so the
+//! result matches what the daemon would have stored at capture time (given
+//! the current parser and mapping profiles).
+
+use anyhow::{bail, Context, Result};
+use axum::body::Body;
+use axum::http::{header, Request};
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use tower::ServiceExt;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::server::{create_app, AppState};
+use crate::services::capture::{capture_files, read_capture_file};
+
+/// Replay every capture file in `dir` (or a single file) into `db`.
+///
+/// Without `db`, a fresh database is created in the system temp directory.
+pub async fn run(dir: &Path, db: Option<PathBuf>) -> Result<()> {
+    let files = if dir.is_file() {
+        vec![dir.to_path_buf()]
+    } else {
+        capture_files(dir)?
+    };
+    if files.is_empty() {
+        bail!("No capture files found in {}", dir.display());
+    }
+
+    let db_path = match db {
+        Some(path) => path,
+        None => std::env::temp_dir().join(format!(
+            "lumo-replay-{}.db",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        )),
+    };
+    if db_path == shared::get_db_path()? {
+        bail!("Refusing to replay into the live database; pass a scratch --db path");
+    }
+
+    let pool = shared::create_pool(&db_path).await?;
+    shared::run_migrations(&pool).await?;
+
+    let (replayed, failed) = replay(pool, Config::from_env()?, &files).await?;
+
+    info!(
+        "Replayed {} request(s) from {} file(s), {} failed",
+        replayed,
+        files.len(),
+        failed
+    );
+    info!("Database: {}", db_path.display());
+    Ok(())
+}
+
+/// Post every captured request in `files` through a fresh app on `pool`.
+/// Returns the number of requests that succeeded and failed.
+async fn replay(pool: SqlitePool, mut config: Config, files: &[PathBuf]) -> Result<(usize, usize)> {
+    // Live config for mapping profiles, but never capture or ingest files.
+    // Captures do not keep the `Authorization` header, so no token either.
+    config.capture = None;
+    config.codex_sessions_dir = None;
+    config.auth_token = None;
+    let app = create_app(AppState::new(pool, config));
+
+    let mut replayed = 0;
+    let mut failed = 0;
+    for file in files {
+        for captured in read_capture_file(file)? {
+            let mut request = Request::builder()
+                .method(captured.method.as_str())
+                .uri(&captured.path);
+            if let Some(content_type) = &captured.content_type {
+                request = request.header(header::CONTENT_TYPE, content_type);
+            }
+            let request = request
+                .body(Body::from(captured.body))
+                .context("Failed to build replay request")?;
+
+            let response = app.clone().oneshot(request).await?;
+            if response.status().is_success() {
+                replayed += 1;
+            } else {
+                failed += 1;
+                warn!(
+                    "{} {} (captured at {}) -> {}",
+                    captured.method,
+                    captured.path,
+                    captured.received_at,
+                    response.status()
+                );
+            }
+        }
+    }
+    Ok((replayed, failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::capture::{CaptureConfig, CaptureWriter, CapturedRequest};
+
+    #[tokio::test]
+    async fn test_replay_ignores_auth_token() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        shared::run_migrations(&pool).await.unwrap();
+
+        let dir = std::env::temp_dir().join(format!("lumo-replay-{}", uuid::Uuid::new_v4()));
+        let writer = CaptureWriter::new(&CaptureConfig {
+            enabled: true,
+            dir: Some(dir.clone()),
+            ..Default::default()
+        })
+        .unwrap();
+        writer
+            .record(&CapturedRequest {
+                received_at: 0,
+                method: "POST".to_string(),
+                path: "/notify".to_string(),
+                content_type: Some("application/json".to_string()),
+                body: r#"{"session_id":"s1","hook_event_name":"Stop"}"#.to_string(),
+            })
+            .unwrap();
+
+        let mut config = Config::from_env().unwrap();
+        config.auth_token = Some("secret".to_string());
+        let files = capture_files(&dir).unwrap();
+        assert_eq!(replay(pool, config, &files).await.unwrap(), (1, 0));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,20 @@
+//! Read-only query API routes
+
+use axum::{routing::get, Router};
+
+use crate::handlers;
+use crate::server::AppState;
+
+/// Create the `/api/v1` query routes
+pub fn api_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/summary", get(handlers::api_summary))
+        .route("/api/v1/models", get(handlers::api_models))
+        .route("/api/v1/sessions", get(handlers::api_sessions))
+        .route(
+            "/api/v1/sessions/{id}/events",
+            get(handlers::api_session_events),
+        )
+        .route("/api/v1/tools", get(handlers::api_tools))
+        .route("/api/v1/trends", get(handlers::api_trends))
+}
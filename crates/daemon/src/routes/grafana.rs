@@ -0,0 +1,18 @@
+//! Grafana JSON datasource routes
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::handlers;
+use crate::server::AppState;
+
+/// Create the `/grafana` datasource routes
+pub fn grafana_routes() -> Router<AppState> {
+    Router::new()
+        .route("/grafana", get(handlers::grafana_health))
+        .route("/grafana/search", post(handlers::grafana_search))
+        .route("/grafana/query", post(handlers::grafana_query))
+        .route("/grafana/annotations", post(handlers::grafana_annotations))
+}
@@ -5,7 +5,9 @@ use axum::{routing::get, Router};
 use crate::handlers;
 use crate::server::AppState;
 
-/// Create health check routes
+/// Create health check and status routes
 pub fn health_routes() -> Router<AppState> {
-    Router::new().route("/health", get(handlers::health_check))
+    Router::new()
+        .route("/health", get(handlers::health_check))
+        .route("/v1/status", get(handlers::status))
 }
@@ -2,10 +2,14 @@
 //!
 //! Organizes routes by functionality.
 
+mod api;
+mod grafana;
 mod health;
 mod notify;
 mod otlp;
 
+pub use api::api_routes;
+pub use grafana::grafana_routes;
 pub use health::health_routes;
 pub use notify::notify_routes;
 pub use otlp::otlp_routes;
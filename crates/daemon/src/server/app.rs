@@ -1,17 +1,46 @@
 //! Application router setup
 
-use axum::Router;
+use axum::{middleware, Router};
 use tower_http::trace::TraceLayer;
 
 use crate::routes;
+use crate::server::auth::require_token;
+use crate::server::capture::capture_requests;
+use crate::server::loopback::require_loopback;
+use crate::server::rejections::count_rejections;
 use crate::server::AppState;
 
 /// Create the Axum application router
 pub fn create_app(state: AppState) -> Router {
+    // Ingest routes, whose request bodies can be captured for replay
+    let ingest = Router::new()
+        .merge(routes::otlp_routes())
+        .merge(
+            routes::notify_routes()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_token)),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            capture_requests,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            count_rejections,
+        ));
+
+    // Read-only query API and Grafana datasource, for local clients only
+    let api = Router::new()
+        .merge(routes::api_routes())
+        .merge(routes::grafana_routes())
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_loopback,
+        ));
+
     Router::new()
         .merge(routes::health_routes())
-        .merge(routes::otlp_routes())
-        .merge(routes::notify_routes())
+        .merge(ingest)
+        .merge(api)
         .layer(TraceLayer::new_for_http())
         .with_state(state)
 }
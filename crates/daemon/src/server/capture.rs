@@ -0,0 +1,57 @@
+//! Raw payload capture middleware
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use shared::doctor::PROBE_HEADER;
+use tracing::warn;
+
+use crate::server::AppState;
+use crate::services::capture::CapturedRequest;
+
+/// Largest body we buffer for capture
+const MAX_CAPTURE_BODY_BYTES: usize = 64 * 1024 * 1024;
+
+/// Record the raw request body before handing the request on. Doctor's
+/// probes are not captured.
+pub async fn capture_requests(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(capture) = state
+        .capture
+        .clone()
+        .filter(|_| !request.headers().contains_key(PROBE_HEADER))
+    else {
+        return next.run(request).await;
+    };
+
+    let (parts, body) = request.into_parts();
+    let bytes = match to_bytes(body, MAX_CAPTURE_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+
+    let captured = CapturedRequest {
+        received_at: chrono::Utc::now().timestamp_millis(),
+        method: parts.method.to_string(),
+        path: parts.uri.path().to_string(),
+        content_type: parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from),
+        body: String::from_utf8_lossy(&bytes).into_owned(),
+    };
+    if let Err(e) = capture.record(&captured) {
+        warn!("Failed to capture request: {}", e);
+    }
+
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
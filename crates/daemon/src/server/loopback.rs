@@ -0,0 +1,92 @@
+//! Loopback-only middleware
+
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::server::AppState;
+
+/// Host names a local client addresses the daemon by
+const LOCAL_HOSTS: [&str; 3] = ["localhost", "127.0.0.1", "[::1]"];
+
+/// Reject TCP peers that are not on loopback, even when the daemon listens
+/// on all interfaces. Unix socket connections carry no peer address and are
+/// local by definition.
+///
+/// A loopback peer must also address the daemon by a local `Host`, or send
+/// the auth token: a browser page on a rebound DNS name reaches loopback
+/// too, but under its own host name.
+pub async fn require_loopback(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+
+    let Some(ip) = peer else {
+        return next.run(request).await;
+    };
+    if !ip.to_canonical().is_loopback() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let headers = request.headers();
+    let local_host = headers
+        .get(header::HOST)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(is_local_host);
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    let authorized = state
+        .config
+        .auth_token
+        .as_deref()
+        .is_some_and(|expected| token == Some(expected));
+
+    if local_host || authorized {
+        next.run(request).await
+    } else {
+        StatusCode::FORBIDDEN.into_response()
+    }
+}
+
+/// Whether a `Host` header names this machine, with or without a port
+fn is_local_host(host: &str) -> bool {
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if !name.is_empty() && port.bytes().all(|b| b.is_ascii_digit()) => name,
+        _ => host,
+    };
+    LOCAL_HOSTS
+        .iter()
+        .any(|local| name.eq_ignore_ascii_case(local))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_local_host() {
+        assert!(is_local_host("localhost"));
+        assert!(is_local_host("localhost:4318"));
+        assert!(is_local_host("127.0.0.1:4318"));
+        assert!(is_local_host("[::1]:4318"));
+        assert!(is_local_host("[::1]"));
+
+        assert!(!is_local_host("attacker.example:4318"));
+        assert!(!is_local_host("localhost.attacker.example"));
+        assert!(!is_local_host("127.0.0.2:4318"));
+        assert!(!is_local_host("::1"));
+        assert!(!is_local_host(""));
+    }
+}
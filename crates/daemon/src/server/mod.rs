@@ -3,6 +3,10 @@
 //! Contains the HTTP server setup, application state, and graceful shutdown.
 
 mod app;
+mod auth;
+mod capture;
+mod loopback;
+mod rejections;
 mod shutdown;
 mod state;
 
@@ -0,0 +1,33 @@
+//! Rejected request counting middleware
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+use shared::doctor::PROBE_HEADER;
+
+use crate::server::AppState;
+use crate::services::Signal;
+
+/// Count ingest requests that end in an error response, including bodies the
+/// JSON extractor rejects before a handler runs. Doctor's probes are not
+/// counted.
+pub async fn count_rejections(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let signal = Signal::from_path(request.uri().path())
+        .filter(|_| !request.headers().contains_key(PROBE_HEADER));
+    let response = next.run(request).await;
+
+    if let Some(signal) = signal {
+        if !response.status().is_success() {
+            state.stats.record_rejected(signal);
+        }
+    }
+
+    response
+}
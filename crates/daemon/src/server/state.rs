@@ -4,6 +4,7 @@ use sqlx::SqlitePool;
 use std::sync::Arc;
 
 use crate::config::Config;
+use crate::services::{AlertEvaluator, CaptureWriter, IngestStats, MappingRegistry};
 
 /// Shared application state
 #[derive(Clone)]
@@ -11,8 +12,15 @@ pub struct AppState {
     /// Database connection pool
     pub db: SqlitePool,
     /// Application configuration
-    #[allow(dead_code)]
     pub config: Arc<Config>,
+    /// Attribute mapping profiles for non-Claude telemetry
+    pub mappings: Arc<MappingRegistry>,
+    /// Ingest counters since startup, for `/v1/status`
+    pub stats: Arc<IngestStats>,
+    /// Raw payload capture, if enabled
+    pub capture: Option<Arc<CaptureWriter>>,
+    /// Budget and anomaly checks after new events
+    pub alerts: AlertEvaluator,
 }
 
 impl AppState {
@@ -20,7 +28,17 @@ impl AppState {
     pub fn new(db: SqlitePool, config: Config) -> Self {
         Self {
             db,
+            mappings: Arc::new(MappingRegistry::new(config.mapping_profiles.clone())),
             config: Arc::new(config),
+            stats: Arc::new(IngestStats::new()),
+            capture: None,
+            alerts: AlertEvaluator::new(),
         }
     }
+
+    /// Record raw ingest request bodies with the given writer
+    pub fn with_capture(mut self, writer: CaptureWriter) -> Self {
+        self.capture = Some(Arc::new(writer));
+        self
+    }
 }
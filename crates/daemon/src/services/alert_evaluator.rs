@@ -0,0 +1,54 @@
+//! Alert evaluator
+//!
+//! Budget and anomaly checks query spend over whole periods, too much to
+//! repeat for every ingested batch. Ingestion only requests a check; one
+//! background task runs them, at most once per [`MIN_INTERVAL`], however
+//! many batches arrived meanwhile.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use shared::analytics::{AnomalyThresholds, SessionCostMedian};
+use sqlx::SqlitePool;
+use tokio::sync::Notify;
+use tracing::warn;
+
+use super::{anomaly_alerts, budget_alerts};
+
+const MIN_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Handle to request budget and anomaly checks
+#[derive(Clone, Default)]
+pub struct AlertEvaluator {
+    requested: Arc<Notify>,
+}
+
+impl AlertEvaluator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check budgets and anomalies soon, after new events were stored.
+    /// Requests made while a check runs coalesce into one more check.
+    pub fn request(&self) {
+        self.requested.notify_one();
+    }
+
+    /// Start the task that runs requested checks
+    pub fn start(&self, pool: SqlitePool, thresholds: AnomalyThresholds) {
+        let requested = self.requested.clone();
+        tokio::spawn(async move {
+            let mut median = SessionCostMedian::default();
+            loop {
+                requested.notified().await;
+                if let Err(e) = budget_alerts::evaluate(&pool).await {
+                    warn!("Failed to evaluate budgets: {}", e);
+                }
+                if let Err(e) = anomaly_alerts::evaluate(&pool, &thresholds, &mut median).await {
+                    warn!("Failed to check for anomalies: {}", e);
+                }
+                tokio::time::sleep(MIN_INTERVAL).await;
+            }
+        });
+    }
+}
@@ -0,0 +1,168 @@
+//! Anomaly alerts
+//!
+//! Checks recent events for cost spikes, API error bursts, retry storms and
+//! unusually expensive sessions after new events are stored, and inserts a
+//! notification naming the offending session for each anomaly found. Cost
+//! spikes fire once per hour, error bursts and retry storms once per session
+//! and hour, expensive sessions once per session: notified anomalies are
+//! recorded in `anomaly_alerts`.
+
+use anyhow::Result;
+use shared::analytics::{
+    Anomaly, AnomalyAnalytics, AnomalyKind, AnomalyThresholds, SessionCostMedian,
+};
+use shared::{AnomalyAlertRepository, NewNotification, NotificationRepository};
+use sqlx::SqlitePool;
+use tracing::info;
+
+/// Hook event anomaly notifications are filed under, for notification
+/// settings
+pub const ANOMALY_HOOK_EVENT: &str = "Anomaly";
+
+/// Check for anomalies, returning the number of notifications sent
+pub async fn evaluate(
+    pool: &SqlitePool,
+    thresholds: &AnomalyThresholds,
+    median: &mut SessionCostMedian,
+) -> Result<usize> {
+    let mut sent = 0;
+    for anomaly in AnomalyAnalytics::detect(pool, thresholds, median).await? {
+        let session_id = anomaly.session_id.clone().unwrap_or_default();
+        // A spike is the hour's total spend, whichever session leads it
+        let alert_session = match anomaly.kind {
+            AnomalyKind::CostSpike => "",
+            _ => session_id.as_str(),
+        };
+
+        let mut tx = pool.begin().await?;
+        if !AnomalyAlertRepository::record(
+            &mut *tx,
+            anomaly.kind.as_str(),
+            alert_session,
+            anomaly.window_start,
+        )
+        .await?
+        {
+            continue;
+        }
+
+        info!(
+            "Anomaly {} in session {}: {} against {}",
+            anomaly.kind.as_str(),
+            session_id,
+            anomaly.observed,
+            anomaly.baseline
+        );
+        NotificationRepository::insert(&mut *tx, &notification(&anomaly, thresholds, session_id))
+            .await?;
+        tx.commit().await?;
+        sent += 1;
+    }
+    Ok(sent)
+}
+
+fn notification(
+    anomaly: &Anomaly,
+    thresholds: &AnomalyThresholds,
+    session_id: String,
+) -> NewNotification {
+    let ratio = anomaly.observed / anomaly.baseline;
+    let (title, message) = match anomaly.kind {
+        AnomalyKind::CostSpike if anomaly.baseline > 0.0 => (
+            "Spend spike",
+            format!(
+                "${:.2} in the last hour, {:.1}× the usual ${:.2}",
+                anomaly.observed, ratio, anomaly.baseline
+            ),
+        ),
+        AnomalyKind::CostSpike => (
+            "Spend spike",
+            format!(
+                "${:.2} in the last hour, with no spend in the {} days before",
+                anomaly.observed, thresholds.baseline_days
+            ),
+        ),
+        AnomalyKind::ErrorBurst => (
+            "API error burst",
+            format!(
+                "{} API errors in the last {} minutes",
+                anomaly.observed, thresholds.burst_window_minutes
+            ),
+        ),
+        AnomalyKind::RetryStorm => (
+            "Retry storm",
+            format!(
+                "{} retried API calls in the last {} minutes",
+                anomaly.observed, thresholds.burst_window_minutes
+            ),
+        ),
+        AnomalyKind::ExpensiveSession => (
+            "Expensive session",
+            format!(
+                "${:.2} so far, {:.1}× the median session (${:.2})",
+                anomaly.observed, ratio, anomaly.baseline
+            ),
+        ),
+    };
+
+    NewNotification {
+        session_id,
+        hook_event: ANOMALY_HOOK_EVENT.to_string(),
+        notification_type: Some(anomaly.kind.as_str().to_string()),
+        title: Some(title.to_string()),
+        message: Some(message),
+        agent_type: None,
+        cwd: anomaly.cwd.clone(),
+        transcript_path: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_notifies_once_per_window() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        shared::run_migrations(&pool).await.unwrap();
+
+        let now = chrono::Utc::now().timestamp_millis();
+        for i in 0..10 {
+            sqlx::query(
+                "INSERT INTO events (session_id, name, timestamp) VALUES ('loop', 'claude_code.api_error', ?)",
+            )
+            .bind(now - i * 1000)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO notifications (session_id, hook_event, title, message, cwd) VALUES ('loop', 'Stop', 't', 'm', '/work/loop')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let thresholds = AnomalyThresholds::default();
+        let mut median = SessionCostMedian::default();
+        assert_eq!(evaluate(&pool, &thresholds, &mut median).await.unwrap(), 1);
+        assert_eq!(evaluate(&pool, &thresholds, &mut median).await.unwrap(), 0);
+
+        let notifications = NotificationRepository::find_unnotified(&pool)
+            .await
+            .unwrap();
+        let burst = notifications
+            .iter()
+            .find(|n| n.hook_event == ANOMALY_HOOK_EVENT)
+            .unwrap();
+        assert_eq!(burst.notification_type.as_deref(), Some("error_burst"));
+        assert_eq!(burst.session_id, "loop");
+        assert_eq!(burst.cwd.as_deref(), Some("/work/loop"));
+        assert_eq!(burst.message, "10 API errors in the last 10 minutes");
+    }
+}
@@ -0,0 +1,129 @@
+//! Budget threshold alerts
+//!
+//! Checks every enabled budget after new spend is stored. When spend
+//! crosses 50%, 80% or 100% of a budget's limit, a notification is inserted
+//! for the app's poller to deliver. Each threshold fires at most once per
+//! budget period: crossings are recorded in `budget_alerts`, and a check
+//! crossing several thresholds at once notifies only for the highest.
+
+use anyhow::Result;
+use shared::analytics::{BudgetAnalytics, BudgetStatus};
+use shared::{BudgetPeriod, BudgetRepository, NewNotification, NotificationRepository};
+use sqlx::SqlitePool;
+use tracing::info;
+
+/// Hook event budget notifications are filed under, for notification
+/// settings
+pub const BUDGET_HOOK_EVENT: &str = "Budget";
+
+/// Check all enabled budgets, returning the number of notifications sent
+pub async fn evaluate(pool: &SqlitePool) -> Result<usize> {
+    let mut sent = 0;
+    for budget in BudgetRepository::find_enabled(pool).await? {
+        let status = BudgetAnalytics::status(pool, &budget).await?;
+
+        // Record the crossings and notify together, so a failed insert
+        // does not mark a threshold as alerted
+        let mut tx = pool.begin().await?;
+        let mut highest = None;
+        for threshold in status.crossed_thresholds() {
+            if BudgetRepository::record_alert(&mut *tx, budget.id, status.period_start, threshold)
+                .await?
+            {
+                highest = Some(threshold);
+            }
+        }
+
+        if let Some(threshold) = highest {
+            info!("Budget '{}' reached {}%", budget.name, threshold);
+            NotificationRepository::insert(&mut *tx, &notification(&status, threshold)).await?;
+            sent += 1;
+        }
+        tx.commit().await?;
+    }
+    Ok(sent)
+}
+
+fn notification(status: &BudgetStatus, threshold: u32) -> NewNotification {
+    let budget = &status.budget;
+    let title = if threshold >= 100 {
+        "Budget exceeded".to_string()
+    } else {
+        format!("Budget {}% used", threshold)
+    };
+    let period = match budget.period {
+        BudgetPeriod::Daily => "today",
+        BudgetPeriod::Weekly => "this week",
+        BudgetPeriod::Monthly => "this month",
+    };
+
+    NewNotification {
+        // Budgets span sessions
+        session_id: String::new(),
+        hook_event: BUDGET_HOOK_EVENT.to_string(),
+        notification_type: Some(format!("threshold_{}", threshold)),
+        title: Some(title),
+        message: Some(format!(
+            "{}: ${:.2} of ${:.2} {}",
+            budget.name, status.spent, budget.limit_usd, period
+        )),
+        agent_type: None,
+        cwd: None,
+        transcript_path: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::{BudgetScope, NewBudget};
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_alerts_once_per_threshold() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        shared::run_migrations(&pool).await.unwrap();
+
+        BudgetRepository::insert(
+            &pool,
+            &NewBudget {
+                name: "Daily".to_string(),
+                period: BudgetPeriod::Daily,
+                scope: BudgetScope::Global,
+                scope_value: None,
+                limit_usd: 10.0,
+                enabled: true,
+            },
+        )
+        .await
+        .unwrap();
+        let spend = |cost: f64| {
+            sqlx::query(
+                "INSERT INTO events (session_id, name, timestamp, cost_usd) VALUES ('s1', 'claude_code.api_request', ?, ?)",
+            )
+            .bind(chrono::Utc::now().timestamp_millis())
+            .bind(cost)
+        };
+
+        spend(8.5).execute(&pool).await.unwrap();
+        assert_eq!(evaluate(&pool).await.unwrap(), 1);
+        assert_eq!(evaluate(&pool).await.unwrap(), 0);
+
+        spend(2.0).execute(&pool).await.unwrap();
+        assert_eq!(evaluate(&pool).await.unwrap(), 1);
+
+        let mut notifications = NotificationRepository::find_unnotified(&pool)
+            .await
+            .unwrap();
+        notifications.sort_by_key(|n| n.id);
+        let types: Vec<_> = notifications
+            .iter()
+            .map(|n| n.notification_type.as_deref().unwrap())
+            .collect();
+        assert_eq!(types, ["threshold_80", "threshold_100"]);
+    }
+}
@@ -0,0 +1,221 @@
+//! Raw payload capture
+//!
+//! When enabled, every ingest request body (OTLP metrics/logs, hook
+//! notifications) is appended as one JSON line to rotating files in
+//! `~/.lumo/capture/`. `lumo-daemon replay` feeds them back through the
+//! handlers, so parser bugs can be reproduced offline and captures can be
+//! turned into `otlp_parser` regression fixtures.
+//!
+//! Enable with `LUMO_CAPTURE=1` or in `~/.lumo/daemon.json`:
+//!
+//! ```json
+//! { "capture": { "enabled": true, "maxFileBytes": 10485760, "maxFiles": 20 } }
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const DEFAULT_MAX_FILE_BYTES: u64 = 10 * 1024 * 1024;
+const DEFAULT_MAX_FILES: usize = 20;
+
+/// Capture settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Capture directory, defaults to `~/.lumo/capture`
+    #[serde(default)]
+    pub dir: Option<PathBuf>,
+    /// Rotate to a new file once the current one exceeds this size
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
+    /// Oldest files beyond this count are deleted on rotation
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+}
+
+fn default_max_file_bytes() -> u64 {
+    DEFAULT_MAX_FILE_BYTES
+}
+
+fn default_max_files() -> usize {
+    DEFAULT_MAX_FILES
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: None,
+            max_file_bytes: DEFAULT_MAX_FILE_BYTES,
+            max_files: DEFAULT_MAX_FILES,
+        }
+    }
+}
+
+/// One captured request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedRequest {
+    /// Receive time in Unix milliseconds
+    pub received_at: i64,
+    pub method: String,
+    /// Request path, e.g. `/v1/logs`
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Request body. Ingest endpoints only accept JSON, so this is UTF-8.
+    pub body: String,
+}
+
+/// Appends captured requests to size-rotated JSONL files
+pub struct CaptureWriter {
+    dir: PathBuf,
+    max_file_bytes: u64,
+    max_files: usize,
+    current: Mutex<Option<CaptureFile>>,
+}
+
+struct CaptureFile {
+    file: File,
+    written: u64,
+}
+
+impl CaptureWriter {
+    /// Create a writer, creating the capture directory if needed
+    pub fn new(config: &CaptureConfig) -> Result<Self> {
+        let dir = match &config.dir {
+            Some(dir) => dir.clone(),
+            None => dirs::home_dir()
+                .context("Could not determine home directory")?
+                .join(".lumo")
+                .join("capture"),
+        };
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        Ok(Self {
+            dir,
+            max_file_bytes: config.max_file_bytes,
+            max_files: config.max_files.max(1),
+            current: Mutex::new(None),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Append a request, rotating first if the current file is full
+    pub fn record(&self, request: &CapturedRequest) -> Result<()> {
+        let mut line = serde_json::to_vec(request)?;
+        line.push(b'\n');
+
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        if current
+            .as_ref()
+            .is_none_or(|c| c.written >= self.max_file_bytes)
+        {
+            *current = Some(self.rotate()?);
+        }
+
+        let capture = current.as_mut().expect("capture file opened above");
+        capture.file.write_all(&line)?;
+        capture.written += line.len() as u64;
+        Ok(())
+    }
+
+    /// Open a fresh capture file and prune the oldest ones
+    fn rotate(&self) -> Result<CaptureFile> {
+        let name = format!(
+            "capture-{}.jsonl",
+            chrono::Local::now().format("%Y%m%d-%H%M%S-%3f")
+        );
+        let path = self.dir.join(name);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+
+        let files = capture_files(&self.dir)?;
+        let excess = files.len().saturating_sub(self.max_files);
+        for old in &files[..excess] {
+            let _ = fs::remove_file(old);
+        }
+
+        Ok(CaptureFile { file, written: 0 })
+    }
+}
+
+/// Capture files in a directory, oldest first
+pub fn capture_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect();
+    // Names embed the creation time, so lexical order is chronological
+    files.sort();
+    Ok(files)
+}
+
+/// Read all captured requests from a file, skipping unparsable lines
+pub fn read_capture_file(path: &Path) -> Result<Vec<CapturedRequest>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut requests = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(request) => requests.push(request),
+            Err(e) => tracing::warn!("Skipping bad capture line in {}: {}", path.display(), e),
+        }
+    }
+    Ok(requests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_keeps_max_files() {
+        let dir = std::env::temp_dir().join(format!("lumo-capture-{}", uuid::Uuid::new_v4()));
+        let writer = CaptureWriter::new(&CaptureConfig {
+            enabled: true,
+            dir: Some(dir.clone()),
+            max_file_bytes: 1,
+            max_files: 2,
+        })
+        .unwrap();
+
+        for i in 0..4 {
+            writer
+                .record(&CapturedRequest {
+                    received_at: i,
+                    method: "POST".to_string(),
+                    path: "/v1/logs".to_string(),
+                    content_type: None,
+                    body: "{}".to_string(),
+                })
+                .unwrap();
+            // Distinct file names need distinct milliseconds
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+
+        let files = capture_files(&dir).unwrap();
+        assert_eq!(files.len(), 2);
+        let last = read_capture_file(files.last().unwrap()).unwrap();
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].received_at, 3);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
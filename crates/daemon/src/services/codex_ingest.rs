@@ -0,0 +1,490 @@
+//! Codex CLI session log ingestion
+//!
+//! Codex does not export OTLP by default, but it writes every session as a
+//! JSONL "rollout" file under `~/.codex/sessions/YYYY/MM/DD/`. This module
+//! tails those files and converts the relevant lines into events using
+//! Lumo's canonical event names, tagged with `agent = 'codex'`:
+//!
+//! - `event_msg/token_count`      → `claude_code.api_request` (priced locally)
+//! - `event_msg/user_message`     → `claude_code.user_prompt`
+//! - `event_msg/error`            → `claude_code.api_error`
+//! - `response_item/*_call_output` → `claude_code.tool_result`
+//!
+//! Read progress is stored per file in `ingest_cursors`, so each scan only
+//! parses lines appended since the previous one.
+
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use shared::{EventRepository, IngestCursorRepository, NewEvent};
+use sqlx::SqlitePool;
+use tracing::{debug, info, warn};
+
+use super::ingest_stats::{IngestStats, Signal};
+use super::{codex_pricing, AlertEvaluator};
+
+const SCAN_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Start the periodic rollout scan loop.
+pub fn start(
+    pool: SqlitePool,
+    sessions_dir: PathBuf,
+    stats: Arc<IngestStats>,
+    alerts: AlertEvaluator,
+) {
+    info!("Codex ingestion: watching {}", sessions_dir.display());
+    tokio::spawn(async move {
+        loop {
+            match scan(&pool, &sessions_dir).await {
+                Ok(0) => {}
+                Ok(count) => {
+                    info!("Codex ingestion: stored {} events", count);
+                    stats.record_ingest(Signal::Codex, count, 0);
+                    alerts.request();
+                }
+                Err(e) => {
+                    warn!("Codex ingestion error: {}", e);
+                    stats.record_rejected(Signal::Codex);
+                }
+            }
+            tokio::time::sleep(SCAN_INTERVAL).await;
+        }
+    });
+}
+
+/// Scan all rollout files once, returning the number of events stored.
+pub async fn scan(pool: &SqlitePool, sessions_dir: &Path) -> Result<usize> {
+    if !sessions_dir.exists() {
+        return Ok(0);
+    }
+
+    let mut files = Vec::new();
+    collect_rollout_files(sessions_dir, &mut files)?;
+
+    // One unreadable or malformed file must not hold up the others
+    let mut stored = 0;
+    for path in files {
+        match ingest_file(pool, &path).await {
+            Ok(count) => stored += count,
+            Err(e) => warn!(
+                "Codex ingestion: failed to ingest {}: {:#}",
+                path.display(),
+                e
+            ),
+        }
+    }
+    Ok(stored)
+}
+
+/// Recursively collect `rollout-*.jsonl` files.
+fn collect_rollout_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_rollout_files(&path, files)?;
+        } else if path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with("rollout-") && n.ends_with(".jsonl"))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+async fn ingest_file(pool: &SqlitePool, path: &Path) -> Result<usize> {
+    let key = path.to_string_lossy().to_string();
+    let cursor = IngestCursorRepository::find_by_path(pool, &key).await?;
+    let mut offset = cursor.as_ref().map(|c| c.byte_offset).unwrap_or(0) as u64;
+    let mut context: Option<RolloutContext> = cursor
+        .and_then(|c| c.context)
+        .and_then(|json| serde_json::from_str(&json).ok());
+
+    let len = std::fs::metadata(path)?.len();
+    // A file shorter than the cursor was truncated or rewritten: read it
+    // again from the start, replacing the events stored from it
+    let mut replaced_session = None;
+    if len < offset {
+        debug!("Codex ingestion: {} shrank, reading it again", key);
+        replaced_session = context.take().and_then(|c| c.session_id);
+        offset = 0;
+    }
+    if len <= offset {
+        return Ok(0);
+    }
+
+    let mut file = std::fs::File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut chunk = Vec::with_capacity((len - offset) as usize);
+    file.read_to_end(&mut chunk)?;
+
+    let mut context = context.unwrap_or_else(|| RolloutContext::from_path(path));
+
+    let (events, consumed) = parse_rollout_chunk(&chunk, offset, &mut context);
+    if consumed == 0 {
+        return Ok(0);
+    }
+
+    let context_json = serde_json::to_string(&context)?;
+    let mut tx = pool.begin().await?;
+    if let Some(session_id) = &replaced_session {
+        EventRepository::delete_by_session(&mut *tx, session_id).await?;
+    }
+    for event in &events {
+        EventRepository::insert(&mut *tx, event).await?;
+    }
+    IngestCursorRepository::upsert(
+        &mut *tx,
+        &key,
+        shared::agent::CODEX,
+        (offset + consumed as u64) as i64,
+        Some(&context_json),
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(events.len())
+}
+
+/// State carried between scans of the same rollout file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RolloutContext {
+    pub session_id: Option<String>,
+    pub model: Option<String>,
+    pub cli_version: Option<String>,
+    /// Tool calls awaiting output: call_id → (tool name, call timestamp ms)
+    #[serde(default)]
+    pub pending_calls: HashMap<String, (String, i64)>,
+    /// Cumulative token total at the last `token_count`, to skip repeats
+    pub last_total_tokens: Option<i64>,
+}
+
+impl RolloutContext {
+    /// Seed a context from the rollout file name, which ends in the session UUID
+    /// (`rollout-2025-09-20T10-00-00-<uuid>.jsonl`).
+    fn from_path(path: &Path) -> Self {
+        let session_id = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .filter(|s| s.len() >= 36)
+            .map(|s| s[s.len() - 36..].to_string());
+        Self {
+            session_id,
+            ..Default::default()
+        }
+    }
+}
+
+/// Parse complete lines from a chunk of a rollout file.
+///
+/// `base_offset` is the chunk's position in the file and is used to build
+/// deterministic event IDs. Returns the events and the number of bytes
+/// consumed; a trailing partial line is left for the next scan.
+pub fn parse_rollout_chunk(
+    chunk: &[u8],
+    base_offset: u64,
+    context: &mut RolloutContext,
+) -> (Vec<NewEvent>, usize) {
+    let mut events = Vec::new();
+    let mut consumed = 0;
+
+    while let Some(newline) = chunk[consumed..].iter().position(|&b| b == b'\n') {
+        let line = &chunk[consumed..consumed + newline];
+        let line_offset = base_offset + consumed as u64;
+        consumed += newline + 1;
+
+        let Ok(line) = serde_json::from_slice::<RolloutLine>(line) else {
+            continue;
+        };
+        if let Some(event) = parse_line(&line, line_offset, context) {
+            events.push(event);
+        }
+    }
+
+    (events, consumed)
+}
+
+#[derive(Debug, Deserialize)]
+struct RolloutLine {
+    timestamp: Option<String>,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    payload: Value,
+}
+
+fn parse_line(line: &RolloutLine, offset: u64, ctx: &mut RolloutContext) -> Option<NewEvent> {
+    let payload = &line.payload;
+    let payload_type = payload.get("type").and_then(Value::as_str).unwrap_or("");
+
+    match (line.kind.as_str(), payload_type) {
+        ("session_meta", _) => {
+            if let Some(id) = payload.get("id").and_then(Value::as_str) {
+                ctx.session_id = Some(id.to_string());
+            }
+            ctx.cli_version = payload
+                .get("cli_version")
+                .and_then(Value::as_str)
+                .map(String::from);
+            None
+        }
+        ("turn_context", _) => {
+            if let Some(model) = payload.get("model").and_then(Value::as_str) {
+                ctx.model = Some(model.to_string());
+            }
+            None
+        }
+        ("response_item", "function_call" | "custom_tool_call" | "local_shell_call") => {
+            let call_id = payload.get("call_id").and_then(Value::as_str)?;
+            let name = payload
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or("shell");
+            let timestamp = parse_timestamp(line.timestamp.as_deref())?;
+            ctx.pending_calls
+                .insert(call_id.to_string(), (name.to_string(), timestamp));
+            None
+        }
+        ("response_item", "function_call_output" | "custom_tool_call_output") => {
+            let timestamp = parse_timestamp(line.timestamp.as_deref())?;
+            let call_id = payload.get("call_id").and_then(Value::as_str)?;
+            let (tool_name, started) = ctx
+                .pending_calls
+                .remove(call_id)
+                .unwrap_or_else(|| ("unknown".to_string(), timestamp));
+            let output = payload.get("output");
+            let output_text = output.and_then(Value::as_str);
+
+            let mut event = new_event(ctx, offset, "claude_code.tool_result", timestamp)?;
+            event.tool_name = Some(tool_name);
+            event.duration_ms = Some(timestamp - started);
+            event.success = output_text.and_then(tool_exit_success);
+            event.tool_result_size_bytes = output_text.map(|s| s.len() as i64);
+            Some(event)
+        }
+        ("event_msg", "user_message") => {
+            let timestamp = parse_timestamp(line.timestamp.as_deref())?;
+            let message = payload.get("message").and_then(Value::as_str).unwrap_or("");
+            let mut event = new_event(ctx, offset, "claude_code.user_prompt", timestamp)?;
+            event.prompt_length = Some(message.chars().count() as i64);
+            Some(event)
+        }
+        ("event_msg", "error" | "stream_error") => {
+            let timestamp = parse_timestamp(line.timestamp.as_deref())?;
+            let mut event = new_event(ctx, offset, "claude_code.api_error", timestamp)?;
+            event.model = ctx.model.clone();
+            event.error = payload
+                .get("message")
+                .and_then(Value::as_str)
+                .map(String::from);
+            Some(event)
+        }
+        ("event_msg", "token_count") => {
+            let timestamp = parse_timestamp(line.timestamp.as_deref())?;
+            let info = payload.get("info").filter(|v| !v.is_null())?;
+            let total = info
+                .get("total_token_usage")
+                .and_then(|u| u.get("total_tokens"))
+                .and_then(Value::as_i64);
+
+            // Codex re-emits the same usage snapshot on some turns.
+            if total.is_some() && total == ctx.last_total_tokens {
+                return None;
+            }
+            ctx.last_total_tokens = total;
+
+            let usage = info.get("last_token_usage")?;
+            let token = |key: &str| usage.get(key).and_then(Value::as_i64).unwrap_or(0);
+            let cached = token("cached_input_tokens");
+            // OpenAI input counts include cached tokens; store them disjoint
+            // like Claude Code does so cache hit rate means the same thing.
+            let input = (token("input_tokens") - cached).max(0);
+            let output = token("output_tokens");
+
+            let mut event = new_event(ctx, offset, "claude_code.api_request", timestamp)?;
+            event.model = ctx.model.clone();
+            event.input_tokens = Some(input);
+            event.output_tokens = Some(output);
+            event.cache_read_tokens = Some(cached);
+            event.cache_creation_tokens = Some(0);
+            event.cost_usd = ctx
+                .model
+                .as_deref()
+                .and_then(|m| codex_pricing::cost_usd(m, input, cached, output));
+            Some(event)
+        }
+        _ => None,
+    }
+}
+
+/// Tool outputs are often JSON with `metadata.exit_code`; use it when present.
+fn tool_exit_success(output: &str) -> Option<bool> {
+    let value: Value = serde_json::from_str(output).ok()?;
+    value
+        .get("metadata")
+        .and_then(|m| m.get("exit_code"))
+        .and_then(Value::as_i64)
+        .map(|code| code == 0)
+}
+
+fn parse_timestamp(ts: Option<&str>) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(ts?)
+        .ok()
+        .map(|dt| dt.timestamp_millis())
+}
+
+/// An event at `offset`, or None while the session id is not known yet
+fn new_event(ctx: &RolloutContext, offset: u64, name: &str, timestamp: i64) -> Option<NewEvent> {
+    let session_id = ctx.session_id.clone()?;
+    Some(NewEvent {
+        id: format!("codex-{}-{}", session_id, offset),
+        session_id,
+        name: name.to_string(),
+        timestamp,
+        duration_ms: None,
+        success: None,
+        error: None,
+        model: None,
+        cost_usd: None,
+        input_tokens: None,
+        output_tokens: None,
+        cache_read_tokens: None,
+        cache_creation_tokens: None,
+        status_code: None,
+        attempt: None,
+        tool_name: None,
+        tool_decision: None,
+        decision_source: None,
+        tool_parameters: None,
+        prompt_length: None,
+        prompt: None,
+        account_uuid: None,
+        organization_id: None,
+        terminal_type: None,
+        app_version: ctx.cli_version.clone(),
+        resource: None,
+        user_id: None,
+        user_email: None,
+        event_sequence: None,
+        tool_result_size_bytes: None,
+        agent: shared::agent::CODEX.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROLLOUT: &str = r#"{"timestamp":"2025-09-20T10:00:00.000Z","type":"session_meta","payload":{"id":"0199a1b2-c3d4-7e5f-8a9b-0c1d2e3f4a5b","cwd":"/work/app","cli_version":"0.39.0"}}
+{"timestamp":"2025-09-20T10:00:01.000Z","type":"turn_context","payload":{"cwd":"/work/app","model":"gpt-5-codex"}}
+{"timestamp":"2025-09-20T10:00:02.000Z","type":"event_msg","payload":{"type":"user_message","message":"fix the build"}}
+{"timestamp":"2025-09-20T10:00:03.000Z","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{}","call_id":"call_1"}}
+{"timestamp":"2025-09-20T10:00:04.500Z","type":"response_item","payload":{"type":"function_call_output","call_id":"call_1","output":"{\"output\":\"ok\",\"metadata\":{\"exit_code\":0}}"}}
+{"timestamp":"2025-09-20T10:00:05.000Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1000,"cached_input_tokens":400,"output_tokens":200,"total_tokens":1200},"last_token_usage":{"input_tokens":1000,"cached_input_tokens":400,"output_tokens":200,"total_tokens":1200}}}}
+{"timestamp":"2025-09-20T10:00:05.100Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1000,"cached_input_tokens":400,"output_tokens":200,"total_tokens":1200},"last_token_usage":{"input_tokens":1000,"cached_input_tokens":400,"output_tokens":200,"total_tokens":1200}}}}
+"#;
+
+    #[test]
+    fn test_parse_rollout_chunk_maps_lines_to_events() {
+        let mut ctx = RolloutContext::default();
+        let (events, consumed) = parse_rollout_chunk(ROLLOUT.as_bytes(), 0, &mut ctx);
+
+        assert_eq!(consumed, ROLLOUT.len());
+        let names: Vec<&str> = events.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "claude_code.user_prompt",
+                "claude_code.tool_result",
+                "claude_code.api_request"
+            ]
+        );
+        assert!(events.iter().all(|e| e.agent == "codex"));
+        assert!(events
+            .iter()
+            .all(|e| e.session_id == "0199a1b2-c3d4-7e5f-8a9b-0c1d2e3f4a5b"));
+
+        let tool = &events[1];
+        assert_eq!(tool.tool_name.as_deref(), Some("shell"));
+        assert_eq!(tool.duration_ms, Some(1500));
+        assert_eq!(tool.success, Some(true));
+
+        let api = &events[2];
+        assert_eq!(api.model.as_deref(), Some("gpt-5-codex"));
+        assert_eq!(api.input_tokens, Some(600));
+        assert_eq!(api.cache_read_tokens, Some(400));
+        assert_eq!(api.output_tokens, Some(200));
+        let expected = (600.0 * 1.25 + 400.0 * 0.125 + 200.0 * 10.0) / 1_000_000.0;
+        assert!((api.cost_usd.unwrap() - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_parse_rollout_chunk_leaves_partial_line() {
+        let partial = r#"{"timestamp":"2025-09-20T10:00:02.000Z","type":"event_msg","payload":{"type":"user_message","message":"hi"}}
+{"timestamp":"2025-09-20T10:00:03.000Z","type":"event_msg"#;
+        let mut ctx = RolloutContext {
+            session_id: Some("s1".to_string()),
+            ..Default::default()
+        };
+        let (events, consumed) = parse_rollout_chunk(partial.as_bytes(), 0, &mut ctx);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(consumed, partial.find('\n').unwrap() + 1);
+    }
+
+    #[test]
+    fn test_parse_rollout_chunk_skips_lines_without_session() {
+        // The user message precedes any session id
+        let mut ctx = RolloutContext::default();
+        let lines: Vec<&str> = ROLLOUT.lines().collect();
+        let chunk = format!("{}\n{}\n", lines[2], lines[0]);
+        let (events, _) = parse_rollout_chunk(chunk.as_bytes(), 0, &mut ctx);
+        assert!(events.is_empty());
+        assert!(ctx.session_id.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_scan_reads_shrunk_file_again() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        shared::run_migrations(&pool).await.unwrap();
+
+        let dir = std::env::temp_dir().join(format!("lumo-codex-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path =
+            dir.join("rollout-2025-09-20T10-00-00-0199a1b2-c3d4-7e5f-8a9b-0c1d2e3f4a5b.jsonl");
+        std::fs::write(&path, ROLLOUT).unwrap();
+        assert_eq!(scan(&pool, &dir).await.unwrap(), 3);
+        assert_eq!(scan(&pool, &dir).await.unwrap(), 0);
+
+        // Rewritten with only the session header and the prompt
+        let lines: Vec<&str> = ROLLOUT.lines().take(3).collect();
+        std::fs::write(&path, format!("{}\n", lines.join("\n"))).unwrap();
+        assert_eq!(scan(&pool, &dir).await.unwrap(), 1);
+
+        let events =
+            EventRepository::find_by_session(&pool, "0199a1b2-c3d4-7e5f-8a9b-0c1d2e3f4a5b")
+                .await
+                .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name, "claude_code.user_prompt");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_price_lookup_prefers_longest_prefix() {
+        let mini = codex_pricing::price_for("gpt-5-mini-2025-08-07").unwrap();
+        assert_eq!(mini.input, 0.25);
+        assert!(codex_pricing::price_for("claude-sonnet-4-5").is_none());
+    }
+}
@@ -0,0 +1,61 @@
+//! Codex price table
+//!
+//! Codex CLI session logs carry token counts but no cost, so we price them
+//! ourselves. Prices are USD per million tokens, matched by model prefix
+//! (longest prefix wins, so `gpt-5-mini` is not priced as `gpt-5`).
+
+/// Per-million-token prices for a model family
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub cached_input: f64,
+    pub output: f64,
+}
+
+const fn price(input: f64, cached_input: f64, output: f64) -> ModelPrice {
+    ModelPrice {
+        input,
+        cached_input,
+        output,
+    }
+}
+
+const PRICES: &[(&str, ModelPrice)] = &[
+    ("gpt-5-codex", price(1.25, 0.125, 10.0)),
+    ("gpt-5-mini", price(0.25, 0.025, 2.0)),
+    ("gpt-5-nano", price(0.05, 0.005, 0.4)),
+    ("gpt-5", price(1.25, 0.125, 10.0)),
+    ("gpt-4.1-mini", price(0.4, 0.1, 1.6)),
+    ("gpt-4.1-nano", price(0.1, 0.025, 0.4)),
+    ("gpt-4.1", price(2.0, 0.5, 8.0)),
+    ("codex-mini", price(1.5, 0.375, 6.0)),
+    ("o4-mini", price(1.1, 0.275, 4.4)),
+    ("o3", price(2.0, 0.5, 8.0)),
+];
+
+/// Look up the price for a model by longest matching prefix
+pub fn price_for(model: &str) -> Option<ModelPrice> {
+    let model = model.to_lowercase();
+    PRICES
+        .iter()
+        .filter(|(prefix, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, price)| *price)
+}
+
+/// Compute the USD cost of a request.
+///
+/// `input_tokens` excludes cached tokens (Codex reports them inclusive;
+/// the caller subtracts before storing). Returns None for unpriced models.
+pub fn cost_usd(
+    model: &str,
+    input_tokens: i64,
+    cached_input_tokens: i64,
+    output_tokens: i64,
+) -> Option<f64> {
+    let price = price_for(model)?;
+    let cost = input_tokens as f64 * price.input
+        + cached_input_tokens as f64 * price.cached_input
+        + output_tokens as f64 * price.output;
+    Some(cost / 1_000_000.0)
+}
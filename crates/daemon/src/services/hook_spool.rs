@@ -0,0 +1,96 @@
+//! Hook spool
+//!
+//! `lumo-daemon hook` appends payloads here when the daemon is unreachable,
+//! one JSON line per hook. The daemon drains the spool on startup and then
+//! periodically, storing each entry as a notification.
+
+use anyhow::{Context, Result};
+use shared::{NewNotification, NotificationRepository};
+use sqlx::SqlitePool;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use super::ingest_stats::{IngestStats, Signal};
+use crate::handlers::NotifyRequest;
+
+const DRAIN_INTERVAL: Duration = Duration::from_secs(60);
+
+/// `~/.lumo/spool/hooks.jsonl`
+pub fn spool_path() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Could not determine home directory")?;
+    Ok(home.join(".lumo").join("spool").join("hooks.jsonl"))
+}
+
+/// Append one hook payload to the spool
+pub fn append(payload: &str) -> Result<()> {
+    let path = spool_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    // Keep each entry on a single line
+    writeln!(file, "{}", payload.replace('\n', " "))?;
+    Ok(())
+}
+
+/// Start the periodic spool drain loop
+pub fn start(pool: SqlitePool, stats: Arc<IngestStats>) {
+    tokio::spawn(async move {
+        loop {
+            match drain(&pool).await {
+                Ok(0) => {}
+                Ok(count) => {
+                    info!("Stored {} spooled hook notifications", count);
+                    stats.record_ingest(Signal::Notify, count, 0);
+                }
+                Err(e) => warn!("Hook spool drain failed: {}", e),
+            }
+            tokio::time::sleep(DRAIN_INTERVAL).await;
+        }
+    });
+}
+
+/// Store all spooled payloads, returning how many were stored.
+///
+/// The spool is renamed before reading so hooks arriving meanwhile start a
+/// new file instead of racing with the drain. The file is stored in one
+/// transaction and removed only after it commits, so an interrupted drain
+/// is retried whole rather than stored twice.
+pub async fn drain(pool: &SqlitePool) -> Result<usize> {
+    let path = spool_path()?;
+    let draining = path.with_extension("jsonl.draining");
+    // A leftover file from an interrupted drain is picked up first
+    if !draining.exists() {
+        if !path.exists() {
+            return Ok(0);
+        }
+        fs::rename(&path, &draining)?;
+    }
+    let content = fs::read_to_string(&draining)?;
+
+    let mut tx = pool.begin().await?;
+    let mut stored = 0;
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let payload: NotifyRequest = match serde_json::from_str(line) {
+            Ok(payload) => payload,
+            Err(e) => {
+                warn!("Dropping unparsable spooled hook: {}", e);
+                continue;
+            }
+        };
+        NotificationRepository::insert(&mut *tx, &NewNotification::from(payload)).await?;
+        stored += 1;
+    }
+    tx.commit().await?;
+
+    fs::remove_file(&draining)?;
+    Ok(stored)
+}
@@ -0,0 +1,148 @@
+//! In-memory ingest counters
+//!
+//! Tracks what the daemon has received since it started, per signal, for
+//! the `/v1/status` endpoint.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Telemetry sources the daemon ingests
+#[derive(Debug, Clone, Copy)]
+pub enum Signal {
+    /// OTLP metrics (`/v1/metrics`)
+    Metrics,
+    /// OTLP logs/events (`/v1/logs`)
+    Logs,
+    /// Hook notifications (`/notify`)
+    Notify,
+    /// Codex CLI session files
+    Codex,
+}
+
+impl Signal {
+    pub const ALL: [Signal; 4] = [Signal::Metrics, Signal::Logs, Signal::Notify, Signal::Codex];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Signal::Metrics => "metrics",
+            Signal::Logs => "logs",
+            Signal::Notify => "notify",
+            Signal::Codex => "codex",
+        }
+    }
+
+    /// Signal served by a request path, if it is an ingest route
+    pub fn from_path(path: &str) -> Option<Self> {
+        match path {
+            "/v1/metrics" => Some(Signal::Metrics),
+            "/v1/logs" => Some(Signal::Logs),
+            "/notify" => Some(Signal::Notify),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct SignalCounters {
+    last_ingest_at: AtomicI64,
+    requests: AtomicU64,
+    rows: AtomicU64,
+    quarantined: AtomicU64,
+    rejected: AtomicU64,
+}
+
+/// Snapshot of one signal's counters
+#[derive(Debug, Clone, Serialize)]
+pub struct SignalStats {
+    pub signal: &'static str,
+    /// Unix ms of the last successful ingest, None if nothing arrived yet
+    pub last_ingest_at: Option<i64>,
+    pub requests: u64,
+    pub rows: u64,
+    pub quarantined: u64,
+    pub rejected: u64,
+}
+
+/// Counters shared between handlers, ingest tasks and the status endpoint
+#[derive(Debug)]
+pub struct IngestStats {
+    started_at: i64,
+    signals: [SignalCounters; 4],
+    listeners: Mutex<Vec<String>>,
+}
+
+impl IngestStats {
+    pub fn new() -> Self {
+        Self {
+            started_at: chrono::Utc::now().timestamp_millis(),
+            signals: Default::default(),
+            listeners: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn counters(&self, signal: Signal) -> &SignalCounters {
+        &self.signals[signal as usize]
+    }
+
+    /// Record a successful ingest of `rows` records (plus `quarantined` set aside)
+    pub fn record_ingest(&self, signal: Signal, rows: usize, quarantined: usize) {
+        let c = self.counters(signal);
+        c.requests.fetch_add(1, Ordering::Relaxed);
+        c.rows.fetch_add(rows as u64, Ordering::Relaxed);
+        c.quarantined
+            .fetch_add(quarantined as u64, Ordering::Relaxed);
+        c.last_ingest_at
+            .store(chrono::Utc::now().timestamp_millis(), Ordering::Relaxed);
+    }
+
+    /// Record a request that was rejected (malformed body, storage failure)
+    pub fn record_rejected(&self, signal: Signal) {
+        self.counters(signal)
+            .rejected
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record an address the daemon is listening on
+    pub fn add_listener(&self, address: String) {
+        self.listeners
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(address);
+    }
+
+    pub fn listeners(&self) -> Vec<String> {
+        self.listeners
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub fn started_at(&self) -> i64 {
+        self.started_at
+    }
+
+    pub fn snapshot(&self) -> Vec<SignalStats> {
+        Signal::ALL
+            .iter()
+            .map(|&signal| {
+                let c = self.counters(signal);
+                let last = c.last_ingest_at.load(Ordering::Relaxed);
+                SignalStats {
+                    signal: signal.as_str(),
+                    last_ingest_at: (last > 0).then_some(last),
+                    requests: c.requests.load(Ordering::Relaxed),
+                    rows: c.rows.load(Ordering::Relaxed),
+                    quarantined: c.quarantined.load(Ordering::Relaxed),
+                    rejected: c.rejected.load(Ordering::Relaxed),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for IngestStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,213 @@
+//! Attribute mapping profiles
+//!
+//! Translates telemetry from other OTEL-emitting AI tools into Lumo's schema.
+//! A profile is selected by the resource's `service.name` and renames foreign
+//! attribute keys, event names and metric names to the ones `otlp_parser`
+//! understands (`input_tokens`, `model`, `claude_code.api_request`, ...).
+//!
+//! User profiles come from `mappingProfiles` in `~/.lumo/daemon.json` and are
+//! tried before the built-in ones:
+//!
+//! ```json
+//! {
+//!   "mappingProfiles": [{
+//!     "name": "my-agent",
+//!     "serviceNames": ["my-agent*"],
+//!     "agent": "my_agent",
+//!     "attributes": { "llm.cost": "cost_usd" },
+//!     "events": { "llm.call": "api_request" }
+//!   }]
+//! }
+//! ```
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Declarative mapping from a foreign telemetry vocabulary to Lumo's
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingProfile {
+    /// Profile name, for logs
+    pub name: String,
+    /// `service.name` patterns this profile applies to. A trailing `*`
+    /// matches any suffix; `*` alone matches every service.
+    #[serde(default)]
+    pub service_names: Vec<String>,
+    /// Agent recorded for matching data. Derived from `service.name` if unset.
+    #[serde(default)]
+    pub agent: Option<String>,
+    /// Foreign attribute key -> Lumo attribute key
+    #[serde(default)]
+    pub attributes: HashMap<String, String>,
+    /// Foreign event name -> Lumo event name (`api_request` or `claude_code.api_request`)
+    #[serde(default)]
+    pub events: HashMap<String, String>,
+    /// Foreign metric name -> Lumo metric name
+    #[serde(default)]
+    pub metrics: HashMap<String, String>,
+}
+
+impl MappingProfile {
+    /// Whether this profile applies to the given `service.name`
+    fn matches(&self, service_name: &str) -> bool {
+        self.service_names
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => service_name.starts_with(prefix),
+                None => service_name == pattern,
+            })
+    }
+
+    /// Copy foreign attributes to their Lumo keys.
+    ///
+    /// Existing Lumo keys win, so a profile never overrides native data.
+    pub fn apply_attributes(&self, attrs: &mut HashMap<String, String>) {
+        for (from, to) in &self.attributes {
+            if attrs.contains_key(to) {
+                continue;
+            }
+            if let Some(value) = attrs.get(from).cloned() {
+                attrs.insert(to.clone(), value);
+            }
+        }
+    }
+
+    /// Translate an event name, returning it unchanged if unmapped
+    pub fn event_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.events.get(name).map(String::as_str).unwrap_or(name)
+    }
+
+    /// Translate a metric name, returning it unchanged if unmapped
+    pub fn metric_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.metrics.get(name).map(String::as_str).unwrap_or(name)
+    }
+}
+
+/// Ordered set of profiles: user profiles first, then built-ins
+#[derive(Debug, Clone)]
+pub struct MappingRegistry {
+    profiles: Vec<MappingProfile>,
+}
+
+impl MappingRegistry {
+    /// Build a registry from user profiles plus the built-in ones
+    pub fn new(user_profiles: Vec<MappingProfile>) -> Self {
+        let mut profiles = user_profiles;
+        profiles.extend(builtin_profiles());
+        Self { profiles }
+    }
+
+    /// Select the first profile matching `service.name`
+    pub fn select(&self, service_name: Option<&str>) -> Option<&MappingProfile> {
+        let service_name = service_name.unwrap_or_default();
+        self.profiles.iter().find(|p| p.matches(service_name))
+    }
+}
+
+impl Default for MappingRegistry {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+/// Built-in profiles
+///
+/// Claude Code already speaks Lumo's vocabulary and gets an empty profile so
+/// the GenAI fallback never touches it. Everything else is read through the
+/// OpenTelemetry GenAI semantic conventions.
+fn builtin_profiles() -> Vec<MappingProfile> {
+    vec![
+        MappingProfile {
+            name: "claude-code".to_string(),
+            service_names: vec!["claude-code".to_string()],
+            ..Default::default()
+        },
+        otel_genai_profile(),
+    ]
+}
+
+/// OpenTelemetry GenAI semantic conventions
+/// (<https://opentelemetry.io/docs/specs/semconv/gen-ai/>)
+fn otel_genai_profile() -> MappingProfile {
+    fn map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(from, to)| (from.to_string(), to.to_string()))
+            .collect()
+    }
+
+    MappingProfile {
+        name: "otel-genai".to_string(),
+        service_names: vec!["*".to_string()],
+        agent: None,
+        attributes: map(&[
+            ("gen_ai.conversation.id", "session.id"),
+            ("gen_ai.request.model", "model"),
+            ("gen_ai.usage.input_tokens", "input_tokens"),
+            ("gen_ai.usage.output_tokens", "output_tokens"),
+            ("gen_ai.usage.cache_read_input_tokens", "cache_read_tokens"),
+            (
+                "gen_ai.usage.cache_creation_input_tokens",
+                "cache_creation_tokens",
+            ),
+            ("gen_ai.tool.name", "tool_name"),
+            ("gen_ai.token.type", "type"),
+            ("error.type", "error"),
+            ("http.response.status_code", "status_code"),
+        ]),
+        events: map(&[
+            ("gen_ai.client.inference.operation.details", "api_request"),
+            ("gen_ai.user.message", "user_prompt"),
+            ("gen_ai.tool.message", "tool_result"),
+        ]),
+        metrics: map(&[("gen_ai.client.token.usage", "claude_code.token.usage")]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_prefers_user_profiles() {
+        let registry = MappingRegistry::new(vec![MappingProfile {
+            name: "custom".to_string(),
+            service_names: vec!["my-agent*".to_string()],
+            ..Default::default()
+        }]);
+
+        assert_eq!(
+            registry.select(Some("my-agent-cli")).unwrap().name,
+            "custom"
+        );
+        assert_eq!(
+            registry.select(Some("claude-code")).unwrap().name,
+            "claude-code"
+        );
+        assert_eq!(
+            registry.select(Some("opencode")).unwrap().name,
+            "otel-genai"
+        );
+        assert_eq!(registry.select(None).unwrap().name, "otel-genai");
+    }
+
+    #[test]
+    fn test_genai_attributes_do_not_override_native_keys() {
+        let profile = otel_genai_profile();
+        let mut attrs = HashMap::from([
+            ("gen_ai.request.model".to_string(), "gpt-5".to_string()),
+            ("gen_ai.usage.input_tokens".to_string(), "120".to_string()),
+            ("input_tokens".to_string(), "100".to_string()),
+        ]);
+
+        profile.apply_attributes(&mut attrs);
+
+        assert_eq!(attrs["model"], "gpt-5");
+        assert_eq!(attrs["input_tokens"], "100");
+        assert_eq!(
+            profile.event_name("gen_ai.client.inference.operation.details"),
+            "api_request"
+        );
+        assert_eq!(profile.event_name("custom.event"), "custom.event");
+    }
+}
@@ -1,5 +1,18 @@
 //! Business logic services
 
+mod alert_evaluator;
+pub mod anomaly_alerts;
+pub mod budget_alerts;
+pub mod capture;
+pub mod codex_ingest;
+mod codex_pricing;
+pub mod hook_spool;
+pub mod ingest_stats;
+mod mapping;
 mod otlp_parser;
 
+pub use alert_evaluator::AlertEvaluator;
+pub use capture::{CaptureConfig, CaptureWriter};
+pub use ingest_stats::{IngestStats, Signal};
+pub use mapping::{MappingProfile, MappingRegistry};
 pub use otlp_parser::{parse_logs_to_events, parse_metrics};
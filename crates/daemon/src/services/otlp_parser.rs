@@ -1,15 +1,74 @@
 //! OTLP data parser
 //!
 //! Converts OTLP metrics and logs into our database entities.
+//!
+//! Records that fail validation (no `session.id`, no timestamp, unparsable
+//! numeric attributes, a metric data point without a value) are routed to
+//! the quarantine table instead.
+
+use std::collections::BTreeMap;
 
 use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
 use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
 use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, KeyValue};
-use shared::{NewEvent, NewMetric};
+use shared::{NewEvent, NewMetric, NewQuarantine, Quarantine, QuarantinedRecord};
 use uuid::Uuid;
 
+use super::mapping::{MappingProfile, MappingRegistry};
+
+/// Records parsed from one OTLP export, split into valid and quarantined
+#[derive(Debug)]
+pub struct ParsedBatch<T> {
+    pub records: Vec<T>,
+    pub quarantined: Vec<NewQuarantine>,
+}
+
+impl<T> ParsedBatch<T> {
+    fn new() -> Self {
+        Self {
+            records: Vec::new(),
+            quarantined: Vec::new(),
+        }
+    }
+
+    fn quarantine(&mut self, reason: &str, detail: Option<String>, record: QuarantinedRecord) {
+        self.quarantined.push(NewQuarantine {
+            reason: reason.to_string(),
+            detail,
+            record,
+            raw_values: BTreeMap::new(),
+        });
+    }
+
+    /// Quarantine a record whose numeric attributes did not parse, keeping
+    /// the raw values
+    fn quarantine_invalid_numbers(
+        &mut self,
+        record: QuarantinedRecord,
+        raw_values: BTreeMap<String, String>,
+    ) {
+        let detail = raw_values
+            .iter()
+            .map(|(key, raw)| format!("{}={:?}", key, raw))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.quarantined.push(NewQuarantine {
+            reason: Quarantine::INVALID_NUMBER.to_string(),
+            detail: Some(detail),
+            record,
+            raw_values,
+        });
+    }
+}
+
 /// Parse OTLP metrics request into NewMetric entities
-pub fn parse_metrics(request: &ExportMetricsServiceRequest) -> Vec<NewMetric> {
+///
+/// Data points without a `session.id`, a timestamp or a value are
+/// quarantined.
+pub fn parse_metrics(
+    request: &ExportMetricsServiceRequest,
+    mappings: &MappingRegistry,
+) -> ParsedBatch<NewMetric> {
     let mut metrics = Vec::new();
 
     for resource_metrics in &request.resource_metrics {
@@ -19,13 +78,11 @@ pub fn parse_metrics(request: &ExportMetricsServiceRequest) -> Vec<NewMetric> {
             .as_ref()
             .map(|r| extract_attributes(&r.attributes));
 
-        let resource_json = resource_attrs
-            .as_ref()
-            .and_then(|attrs| serde_json::to_string(attrs).ok());
+        let resource = ResourceContext::new(resource_attrs.as_ref(), mappings);
 
         for scope_metrics in &resource_metrics.scope_metrics {
             for metric in &scope_metrics.metrics {
-                let metric_name = &metric.name;
+                let metric_name = resource.metric_name(&metric.name);
                 let metric_unit = if metric.unit.is_empty() {
                     None
                 } else {
@@ -42,7 +99,8 @@ pub fn parse_metrics(request: &ExportMetricsServiceRequest) -> Vec<NewMetric> {
                     match data {
                         opentelemetry_proto::tonic::metrics::v1::metric::Data::Sum(sum) => {
                             for data_point in &sum.data_points {
-                                let attrs = extract_attributes(&data_point.attributes);
+                                let attrs = resource
+                                    .map_attributes(extract_attributes(&data_point.attributes));
                                 let value = extract_number_value(data_point);
                                 let timestamp = data_point.time_unix_nano as i64 / 1_000_000; // ns to ms
 
@@ -51,7 +109,7 @@ pub fn parse_metrics(request: &ExportMetricsServiceRequest) -> Vec<NewMetric> {
                                     timestamp,
                                     value,
                                     &attrs,
-                                    resource_json.as_deref(),
+                                    &resource,
                                     metric_unit.as_deref(),
                                     metric_description.as_deref(),
                                 ));
@@ -59,7 +117,8 @@ pub fn parse_metrics(request: &ExportMetricsServiceRequest) -> Vec<NewMetric> {
                         }
                         opentelemetry_proto::tonic::metrics::v1::metric::Data::Gauge(gauge) => {
                             for data_point in &gauge.data_points {
-                                let attrs = extract_attributes(&data_point.attributes);
+                                let attrs = resource
+                                    .map_attributes(extract_attributes(&data_point.attributes));
                                 let value = extract_number_value(data_point);
                                 let timestamp = data_point.time_unix_nano as i64 / 1_000_000;
 
@@ -68,7 +127,7 @@ pub fn parse_metrics(request: &ExportMetricsServiceRequest) -> Vec<NewMetric> {
                                     timestamp,
                                     value,
                                     &attrs,
-                                    resource_json.as_deref(),
+                                    &resource,
                                     metric_unit.as_deref(),
                                     metric_description.as_deref(),
                                 ));
@@ -76,8 +135,9 @@ pub fn parse_metrics(request: &ExportMetricsServiceRequest) -> Vec<NewMetric> {
                         }
                         opentelemetry_proto::tonic::metrics::v1::metric::Data::Histogram(hist) => {
                             for data_point in &hist.data_points {
-                                let attrs = extract_attributes(&data_point.attributes);
-                                let value = data_point.sum.unwrap_or(0.0);
+                                let attrs = resource
+                                    .map_attributes(extract_attributes(&data_point.attributes));
+                                let value = data_point.sum;
                                 let timestamp = data_point.time_unix_nano as i64 / 1_000_000;
 
                                 metrics.push(create_metric(
@@ -85,7 +145,7 @@ pub fn parse_metrics(request: &ExportMetricsServiceRequest) -> Vec<NewMetric> {
                                     timestamp,
                                     value,
                                     &attrs,
-                                    resource_json.as_deref(),
+                                    &resource,
                                     metric_unit.as_deref(),
                                     metric_description.as_deref(),
                                 ));
@@ -98,12 +158,40 @@ pub fn parse_metrics(request: &ExportMetricsServiceRequest) -> Vec<NewMetric> {
         }
     }
 
-    metrics
+    let mut batch = ParsedBatch::new();
+    for (metric, has_value) in metrics {
+        if metric.session_id.is_empty() {
+            batch.quarantine(
+                Quarantine::MISSING_SESSION_ID,
+                None,
+                QuarantinedRecord::Metric(metric),
+            );
+        } else if metric.timestamp == 0 {
+            batch.quarantine(
+                Quarantine::MISSING_TIMESTAMP,
+                None,
+                QuarantinedRecord::Metric(metric),
+            );
+        } else if !has_value {
+            batch.quarantine(
+                Quarantine::MISSING_VALUE,
+                None,
+                QuarantinedRecord::Metric(metric),
+            );
+        } else {
+            batch.records.push(metric);
+        }
+    }
+
+    batch
 }
 
 /// Parse OTLP logs request into NewEvent entities
-pub fn parse_logs_to_events(request: &ExportLogsServiceRequest) -> Vec<NewEvent> {
-    let mut events = Vec::new();
+pub fn parse_logs_to_events(
+    request: &ExportLogsServiceRequest,
+    mappings: &MappingRegistry,
+) -> ParsedBatch<NewEvent> {
+    let mut batch = ParsedBatch::new();
 
     for resource_logs in &request.resource_logs {
         let resource_attrs = resource_logs
@@ -111,35 +199,123 @@ pub fn parse_logs_to_events(request: &ExportLogsServiceRequest) -> Vec<NewEvent>
             .as_ref()
             .map(|r| extract_attributes(&r.attributes));
 
-        let resource_json = resource_attrs
-            .as_ref()
-            .and_then(|attrs| serde_json::to_string(attrs).ok());
+        let resource = ResourceContext::new(resource_attrs.as_ref(), mappings);
 
         for scope_logs in &resource_logs.scope_logs {
             for log_record in &scope_logs.log_records {
-                let attrs = extract_attributes(&log_record.attributes);
-                let timestamp = log_record.time_unix_nano as i64 / 1_000_000; // ns to ms
+                let attrs = resource.map_attributes(extract_attributes(&log_record.attributes));
+                // ns to ms, falling back to when the collector observed the record
+                let timestamp = if log_record.time_unix_nano != 0 {
+                    log_record.time_unix_nano
+                } else {
+                    log_record.observed_time_unix_nano
+                } as i64
+                    / 1_000_000;
 
-                // Extract event name from attributes or body
+                // Extract event name from attributes, the record's event_name, or body
                 let event_name = attrs
                     .get("event.name")
                     .cloned()
+                    .or_else(|| Some(log_record.event_name.clone()).filter(|name| !name.is_empty()))
                     .or_else(|| extract_body_string(&log_record.body))
                     .unwrap_or_else(|| "unknown".to_string());
 
-                // Prefix with claude_code if not already
-                let event_name = if event_name.starts_with("claude_code.") {
-                    event_name
-                } else {
-                    format!("claude_code.{}", event_name)
-                };
+                // Normalize to Lumo's canonical `claude_code.*` event vocabulary.
+                // The producing agent is recorded separately in the `agent` column.
+                let event_name =
+                    canonical_event_name(resource.event_name(&event_name), &resource.agent);
+
+                let (event, invalid_numbers) =
+                    create_event(&event_name, timestamp, &attrs, &resource);
 
-                events.push(create_event(&event_name, timestamp, &attrs, resource_json.as_deref()));
+                if event.session_id.is_empty() {
+                    batch.quarantine(
+                        Quarantine::MISSING_SESSION_ID,
+                        None,
+                        QuarantinedRecord::Event(event),
+                    );
+                } else if event.timestamp == 0 {
+                    batch.quarantine(
+                        Quarantine::MISSING_TIMESTAMP,
+                        None,
+                        QuarantinedRecord::Event(event),
+                    );
+                } else if !invalid_numbers.is_empty() {
+                    batch.quarantine_invalid_numbers(
+                        QuarantinedRecord::Event(event),
+                        invalid_numbers,
+                    );
+                } else {
+                    batch.records.push(event);
+                }
             }
         }
     }
 
-    events
+    batch
+}
+
+/// Resource-level context shared by every record in a resource batch
+struct ResourceContext<'a> {
+    /// Resource attributes serialized as JSON
+    json: Option<String>,
+    /// Producing agent, from the mapping profile or the `service.name` resource attribute
+    agent: String,
+    /// Mapping profile selected by `service.name`
+    profile: Option<&'a MappingProfile>,
+}
+
+impl<'a> ResourceContext<'a> {
+    fn new(
+        attrs: Option<&std::collections::HashMap<String, String>>,
+        mappings: &'a MappingRegistry,
+    ) -> Self {
+        let service_name = attrs
+            .and_then(|a| a.get("service.name"))
+            .map(String::as_str);
+        let profile = mappings.select(service_name);
+        let agent = profile
+            .and_then(|p| p.agent.clone())
+            .unwrap_or_else(|| shared::agent::from_service_name(service_name));
+
+        Self {
+            json: attrs.and_then(|a| serde_json::to_string(a).ok()),
+            agent,
+            profile,
+        }
+    }
+
+    /// Translate record attributes through the selected profile
+    fn map_attributes(
+        &self,
+        mut attrs: std::collections::HashMap<String, String>,
+    ) -> std::collections::HashMap<String, String> {
+        if let Some(profile) = self.profile {
+            profile.apply_attributes(&mut attrs);
+        }
+        attrs
+    }
+
+    fn event_name<'n>(&'n self, name: &'n str) -> &'n str {
+        self.profile.map_or(name, |p| p.event_name(name))
+    }
+
+    fn metric_name<'n>(&'n self, name: &'n str) -> &'n str {
+        self.profile.map_or(name, |p| p.metric_name(name))
+    }
+}
+
+/// Map an emitted event name onto the canonical `claude_code.*` name,
+/// stripping the agent's own prefix (e.g. `codex.api_request`).
+fn canonical_event_name(name: &str, agent: &str) -> String {
+    if name.starts_with("claude_code.") {
+        return name.to_string();
+    }
+    let bare = name
+        .strip_prefix(agent)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .unwrap_or(name);
+    format!("claude_code.{}", bare)
 }
 
 /// Extract attributes from KeyValue list into a HashMap
@@ -174,35 +350,36 @@ fn extract_body_string(body: &Option<AnyValue>) -> Option<String> {
 /// Extract number value from a data point
 fn extract_number_value(
     data_point: &opentelemetry_proto::tonic::metrics::v1::NumberDataPoint,
-) -> f64 {
+) -> Option<f64> {
     match &data_point.value {
-        Some(opentelemetry_proto::tonic::metrics::v1::number_data_point::Value::AsDouble(d)) => *d,
+        Some(opentelemetry_proto::tonic::metrics::v1::number_data_point::Value::AsDouble(d)) => {
+            Some(*d)
+        }
         Some(opentelemetry_proto::tonic::metrics::v1::number_data_point::Value::AsInt(i)) => {
-            *i as f64
+            Some(*i as f64)
         }
-        None => 0.0,
+        None => None,
     }
 }
 
-/// Create a NewMetric from parsed data
+/// Create a NewMetric from parsed data, along with whether the data point
+/// had a value. A missing value is stored as 0 and a missing `session.id`
+/// leaves `session_id` empty.
 fn create_metric(
     name: &str,
     timestamp: i64,
-    value: f64,
+    value: Option<f64>,
     attrs: &std::collections::HashMap<String, String>,
-    resource: Option<&str>,
+    resource: &ResourceContext,
     unit: Option<&str>,
     description: Option<&str>,
-) -> NewMetric {
-    NewMetric {
+) -> (NewMetric, bool) {
+    let metric = NewMetric {
         id: Uuid::new_v4().to_string(),
-        session_id: attrs
-            .get("session.id")
-            .cloned()
-            .unwrap_or_else(|| "unknown".to_string()),
+        session_id: attrs.get("session.id").cloned().unwrap_or_default(),
         name: name.to_string(),
         timestamp,
-        value,
+        value: value.unwrap_or_default(),
         metric_type: attrs.get("type").cloned(),
         model: attrs.get("model").cloned(),
         tool: attrs.get("tool").cloned(),
@@ -212,54 +389,245 @@ fn create_metric(
         organization_id: attrs.get("organization.id").cloned(),
         terminal_type: attrs.get("terminal.type").cloned(),
         app_version: attrs.get("app.version").cloned(),
-        resource: resource.map(String::from),
+        resource: resource.json.clone(),
         user_id: attrs.get("user.id").cloned(),
         user_email: attrs.get("user.email").cloned(),
         unit: unit.map(String::from),
         description: description.map(String::from),
+        agent: resource.agent.clone(),
+    };
+    (metric, value.is_some())
+}
+
+/// Parses numeric attributes, remembering the ones that fail to parse
+struct NumberAttrs<'a> {
+    attrs: &'a std::collections::HashMap<String, String>,
+    /// Raw value of each unparsable attribute, by key
+    invalid: BTreeMap<String, String>,
+}
+
+impl<'a> NumberAttrs<'a> {
+    fn new(attrs: &'a std::collections::HashMap<String, String>) -> Self {
+        Self {
+            attrs,
+            invalid: BTreeMap::new(),
+        }
+    }
+
+    fn float(&mut self, key: &str) -> Option<f64> {
+        let raw = self.attrs.get(key)?;
+        match raw.parse::<f64>() {
+            Ok(value) if value.is_finite() => Some(value),
+            _ => {
+                self.invalid.insert(key.to_string(), raw.clone());
+                None
+            }
+        }
+    }
+
+    /// Integer attribute; float strings such as `"12.0"` are truncated
+    fn int(&mut self, key: &str) -> Option<i64> {
+        match self.attrs.get(key)?.parse::<i64>() {
+            Ok(value) => Some(value),
+            Err(_) => self.float(key).map(|value| value as i64),
+        }
     }
 }
 
-/// Create a NewEvent from parsed data
+/// Create a NewEvent from parsed data.
+///
+/// Returns the event along with any numeric attributes that failed to parse.
+/// A missing `session.id` leaves `session_id` empty.
 fn create_event(
     name: &str,
     timestamp: i64,
     attrs: &std::collections::HashMap<String, String>,
-    resource: Option<&str>,
-) -> NewEvent {
-    NewEvent {
+    resource: &ResourceContext,
+) -> (NewEvent, BTreeMap<String, String>) {
+    let mut numbers = NumberAttrs::new(attrs);
+
+    let event = NewEvent {
         id: Uuid::new_v4().to_string(),
-        session_id: attrs
-            .get("session.id")
-            .cloned()
-            .unwrap_or_else(|| "unknown".to_string()),
+        session_id: attrs.get("session.id").cloned().unwrap_or_default(),
         name: name.to_string(),
         timestamp,
-        duration_ms: attrs.get("duration_ms").and_then(|s| s.parse().ok()),
+        duration_ms: numbers.int("duration_ms"),
         success: attrs.get("success").map(|s| s == "true"),
         error: attrs.get("error").cloned(),
         model: attrs.get("model").cloned(),
-        cost_usd: attrs.get("cost_usd").and_then(|s| s.parse().ok()),
-        input_tokens: attrs.get("input_tokens").and_then(|s| s.parse().ok()),
-        output_tokens: attrs.get("output_tokens").and_then(|s| s.parse().ok()),
-        cache_read_tokens: attrs.get("cache_read_tokens").and_then(|s| s.parse().ok()),
-        cache_creation_tokens: attrs.get("cache_creation_tokens").and_then(|s| s.parse().ok()),
-        status_code: attrs.get("status_code").and_then(|s| s.parse().ok()),
-        attempt: attrs.get("attempt").and_then(|s| s.parse().ok()),
+        cost_usd: numbers.float("cost_usd"),
+        input_tokens: numbers.int("input_tokens"),
+        output_tokens: numbers.int("output_tokens"),
+        cache_read_tokens: numbers.int("cache_read_tokens"),
+        cache_creation_tokens: numbers.int("cache_creation_tokens"),
+        status_code: numbers.int("status_code").map(|v| v as i32),
+        attempt: numbers.int("attempt").map(|v| v as i32),
         tool_name: attrs.get("tool_name").cloned(),
         tool_decision: attrs.get("decision").cloned(),
         decision_source: attrs.get("source").cloned(),
         tool_parameters: attrs.get("tool_parameters").cloned(),
-        prompt_length: attrs.get("prompt_length").and_then(|s| s.parse().ok()),
+        prompt_length: numbers.int("prompt_length"),
         prompt: attrs.get("prompt").cloned(),
         account_uuid: attrs.get("user.account_uuid").cloned(),
         organization_id: attrs.get("organization.id").cloned(),
         terminal_type: attrs.get("terminal.type").cloned(),
         app_version: attrs.get("app.version").cloned(),
-        resource: resource.map(String::from),
+        resource: resource.json.clone(),
         user_id: attrs.get("user.id").cloned(),
         user_email: attrs.get("user.email").cloned(),
-        event_sequence: attrs.get("event.sequence").and_then(|s| s.parse().ok()),
-        tool_result_size_bytes: attrs.get("tool_result_size_bytes").and_then(|s| s.parse().ok()),
+        event_sequence: numbers.int("event.sequence"),
+        tool_result_size_bytes: numbers.int("tool_result_size_bytes"),
+        agent: resource.agent.clone(),
+    };
+
+    (event, numbers.invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry_proto::tonic::logs::v1::{LogRecord, ResourceLogs, ScopeLogs};
+    use opentelemetry_proto::tonic::metrics::v1::{
+        metric, number_data_point, Metric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum,
+    };
+
+    fn attr(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue(value.to_string())),
+            }),
+        }
+    }
+
+    fn logs_request(records: Vec<LogRecord>) -> ExportLogsServiceRequest {
+        ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                scope_logs: vec![ScopeLogs {
+                    log_records: records,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn test_invalid_logs_are_quarantined() {
+        let request = logs_request(vec![
+            LogRecord {
+                time_unix_nano: 1_700_000_000_000_000_000,
+                attributes: vec![attr("event.name", "api_request")],
+                ..Default::default()
+            },
+            LogRecord {
+                attributes: vec![attr("event.name", "api_request"), attr("session.id", "s1")],
+                ..Default::default()
+            },
+            LogRecord {
+                time_unix_nano: 1_700_000_000_000_000_000,
+                attributes: vec![
+                    attr("event.name", "api_request"),
+                    attr("session.id", "s1"),
+                    attr("cost_usd", "n/a"),
+                ],
+                ..Default::default()
+            },
+        ]);
+
+        let batch = parse_logs_to_events(&request, &MappingRegistry::default());
+
+        assert!(batch.records.is_empty());
+        let reasons: Vec<_> = batch
+            .quarantined
+            .iter()
+            .map(|q| q.reason.as_str())
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                Quarantine::MISSING_SESSION_ID,
+                Quarantine::MISSING_TIMESTAMP,
+                Quarantine::INVALID_NUMBER
+            ]
+        );
+        assert_eq!(
+            batch.quarantined[2].detail.as_deref(),
+            Some("cost_usd=\"n/a\"")
+        );
+        assert_eq!(
+            batch.quarantined[2]
+                .raw_values
+                .get("cost_usd")
+                .map(String::as_str),
+            Some("n/a")
+        );
+    }
+
+    #[test]
+    fn test_invalid_metrics_are_quarantined() {
+        let point = |attributes, value| NumberDataPoint {
+            time_unix_nano: 1_700_000_000_000_000_000,
+            attributes,
+            value,
+            ..Default::default()
+        };
+        let request = ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                scope_metrics: vec![ScopeMetrics {
+                    metrics: vec![Metric {
+                        name: "claude_code.cost.usage".to_string(),
+                        data: Some(metric::Data::Sum(Sum {
+                            data_points: vec![
+                                point(vec![], Some(number_data_point::Value::AsDouble(0.5))),
+                                point(vec![attr("session.id", "s1")], None),
+                                point(
+                                    vec![attr("session.id", "s1")],
+                                    Some(number_data_point::Value::AsInt(2)),
+                                ),
+                            ],
+                            ..Default::default()
+                        })),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+
+        let batch = parse_metrics(&request, &MappingRegistry::default());
+
+        assert_eq!(batch.records.len(), 1);
+        assert_eq!(batch.records[0].value, 2.0);
+        let reasons: Vec<_> = batch
+            .quarantined
+            .iter()
+            .map(|q| q.reason.as_str())
+            .collect();
+        assert_eq!(
+            reasons,
+            [Quarantine::MISSING_SESSION_ID, Quarantine::MISSING_VALUE]
+        );
+    }
+
+    #[test]
+    fn test_observed_time_fallback() {
+        let request = logs_request(vec![LogRecord {
+            observed_time_unix_nano: 1_700_000_000_000_000_000,
+            attributes: vec![
+                attr("event.name", "api_request"),
+                attr("session.id", "s1"),
+                attr("duration_ms", "1200.0"),
+            ],
+            ..Default::default()
+        }]);
+
+        let batch = parse_logs_to_events(&request, &MappingRegistry::default());
+
+        assert!(batch.quarantined.is_empty());
+        assert_eq!(batch.records[0].timestamp, 1_700_000_000_000);
+        assert_eq!(batch.records[0].duration_ms, Some(1200));
+        assert_eq!(batch.records[0].name, "claude_code.api_request");
     }
 }
@@ -1,25 +1,37 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use shared::claude_settings::ManagedSettingsRecord;
+use shared::daemon_address;
+use shared::daemon_process::DaemonProcess;
+use shared::settings_file::SettingsFile;
 use std::path::Path;
-use tracing::info;
+use std::time::Duration;
+use tracing::{info, warn};
 
 pub async fn run(delete_data: bool) -> Result<()> {
     let home_dir = dirs::home_dir().context("Could not determine home directory")?;
 
-    // 1. Stop the running daemon service
+    // 1. Revert Lumo's entries in Claude Code settings
+    if let Err(e) = revert_claude_settings(&home_dir) {
+        warn!("Failed to revert Claude Code settings: {}", e);
+    }
+
+    // 2. Stop the running daemon (service or detached process)
     stop_service(&home_dir).await;
 
-    // 2. Remove the service file
+    // 3. Remove the service file
     remove_service_file(&home_dir);
 
-    // 3. Remove the daemon binary
-    let binary_path = home_dir.join(".lumo/bin/lumo-daemon");
-    if binary_path.exists() {
-        std::fs::remove_file(&binary_path)
-            .with_context(|| format!("Failed to remove {}", binary_path.display()))?;
-        info!("Removed daemon binary: {}", binary_path.display());
+    // 4. Remove the daemon and CLI binaries
+    for binary in ["lumo-daemon", "lumo"] {
+        let binary_path = home_dir.join(".lumo/bin").join(binary);
+        if binary_path.exists() {
+            std::fs::remove_file(&binary_path)
+                .with_context(|| format!("Failed to remove {}", binary_path.display()))?;
+            info!("Removed binary: {}", binary_path.display());
+        }
     }
 
-    // 4. Optionally delete all user data
+    // 5. Optionally delete all user data
     if delete_data {
         let lumo_dir = home_dir.join(".lumo");
         if lumo_dir.exists() {
@@ -43,6 +55,51 @@ pub async fn run(delete_data: bool) -> Result<()> {
     Ok(())
 }
 
+/// Remove Lumo's OTEL env vars and hooks from `~/.claude/settings.json`,
+/// putting back any values the user had before Lumo changed them
+fn revert_claude_settings(home_dir: &Path) -> Result<()> {
+    let mut record = ManagedSettingsRecord::load()?;
+    let endpoint = daemon_address::otlp_endpoint(&daemon_address::server_address()?)?;
+
+    // Every Claude profile the app configured, plus the default one for
+    // settings written before files were recorded
+    let mut paths = record.settings_paths();
+    let default_path = home_dir.join(".claude").join("settings.json");
+    if !paths.contains(&default_path) {
+        paths.push(default_path);
+    }
+
+    // A file that fails keeps its entry in the record, so a later uninstall
+    // can still revert it; the others are reverted regardless
+    let mut failed = Vec::new();
+    for path in paths {
+        if !path.exists() {
+            continue;
+        }
+        let mut reverted = record.clone();
+        let result = SettingsFile::read(&path).and_then(|mut settings| {
+            if reverted.revert(&path, &mut settings.root, &endpoint) {
+                settings.write()?;
+                info!("Reverted Lumo settings in {}", path.display());
+            }
+            Ok(())
+        });
+        match result {
+            Ok(()) => record = reverted,
+            Err(e) => {
+                warn!("Failed to revert {}: {}", path.display(), e);
+                failed.push(path.display().to_string());
+            }
+        }
+    }
+    record.save()?;
+
+    if !failed.is_empty() {
+        bail!("Could not revert {}", failed.join(", "));
+    }
+    Ok(())
+}
+
 async fn stop_service(home_dir: &Path) {
     #[cfg(target_os = "macos")]
     {
@@ -91,6 +148,13 @@ async fn stop_service(home_dir: &Path) {
             Err(e) => info!("Failed to run systemctl: {}", e),
         }
     }
+
+    // Detached daemon started by the app where systemd is unavailable
+    match DaemonProcess::new().and_then(|process| process.stop(Duration::from_secs(5))) {
+        Ok(true) => info!("Stopped detached daemon"),
+        Ok(false) => {}
+        Err(e) => warn!("Failed to stop detached daemon: {}", e),
+    }
 }
 
 fn remove_service_file(home_dir: &Path) {
@@ -0,0 +1,60 @@
+-- Agent dimension: which coding agent produced a record.
+--
+-- agent values:
+--   'claude_code' — Claude Code OTLP export (default for existing rows)
+--   'codex'       — OpenAI Codex CLI session logs (~/.codex/sessions)
+--
+-- Event names keep Lumo's canonical `claude_code.*` vocabulary regardless of
+-- agent, so every existing query keeps working; filter on `agent` to split.
+ALTER TABLE events ADD COLUMN agent TEXT NOT NULL DEFAULT 'claude_code';
+ALTER TABLE metrics ADD COLUMN agent TEXT NOT NULL DEFAULT 'claude_code';
+
+CREATE INDEX IF NOT EXISTS idx_events_agent ON events(agent);
+CREATE INDEX IF NOT EXISTS idx_metrics_agent ON metrics(agent);
+
+-- Recreate the sessions view to expose the agent column.
+DROP VIEW IF EXISTS sessions;
+
+CREATE VIEW sessions AS
+SELECT
+    session_id AS id,
+
+    -- Time range
+    MIN(timestamp) AS start_time,
+    MAX(timestamp) AS end_time,
+    MAX(timestamp) - MIN(timestamp) AS duration_ms,
+
+    -- Counts
+    COUNT(*) AS event_count,
+    COUNT(CASE WHEN name = 'claude_code.api_request' THEN 1 END) AS api_request_count,
+    COUNT(CASE WHEN name = 'claude_code.api_error' THEN 1 END) AS error_count,
+    COUNT(CASE WHEN name = 'claude_code.tool_result' THEN 1 END) AS tool_use_count,
+    COUNT(CASE WHEN name = 'claude_code.user_prompt' THEN 1 END) AS prompt_count,
+
+    -- Totals from api_request events
+    COALESCE(SUM(CASE WHEN name = 'claude_code.api_request' THEN cost_usd ELSE 0 END), 0) AS total_cost_usd,
+    COALESCE(SUM(CASE WHEN name = 'claude_code.api_request' THEN input_tokens ELSE 0 END), 0) AS total_input_tokens,
+    COALESCE(SUM(CASE WHEN name = 'claude_code.api_request' THEN output_tokens ELSE 0 END), 0) AS total_output_tokens,
+    COALESCE(SUM(CASE WHEN name = 'claude_code.api_request' THEN cache_read_tokens ELSE 0 END), 0) AS total_cache_read_tokens,
+
+    -- Metadata (take the most recent non-null value)
+    MAX(account_uuid) AS account_uuid,
+    MAX(organization_id) AS organization_id,
+    MAX(terminal_type) AS terminal_type,
+    MAX(app_version) AS app_version,
+    MAX(agent) AS agent
+
+FROM events
+GROUP BY session_id;
+
+-- Ingest cursors for file-based sources (e.g. Codex rollout logs).
+-- Tracks how far each file has been read so re-scans only pick up new lines.
+-- `context` holds source-specific JSON carried between scans (session id,
+-- current model, in-flight tool calls).
+CREATE TABLE IF NOT EXISTS ingest_cursors (
+    path TEXT PRIMARY KEY,
+    agent TEXT NOT NULL,
+    byte_offset INTEGER NOT NULL DEFAULT 0,
+    context TEXT,
+    updated_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000)
+);
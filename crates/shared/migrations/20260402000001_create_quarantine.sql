@@ -0,0 +1,29 @@
+-- Quarantine for malformed or unattributable telemetry.
+--
+-- Records that fail validation in the OTLP parser land here instead of in
+-- events/metrics, so analytics never see them. They can be inspected,
+-- re-attributed to a session (moved into events/metrics) or purged with
+-- `lumo-daemon quarantine`.
+--
+-- reason values:
+--   'missing_session_id' — event has no session.id attribute
+--   'missing_timestamp'  — both time_unix_nano and observed_time_unix_nano are 0
+--   'invalid_number'     — a numeric attribute could not be parsed
+--
+-- signal values: 'event', 'metric'
+-- payload: the parsed record as JSON (NewEvent / NewMetric)
+CREATE TABLE IF NOT EXISTS quarantine (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    signal TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    detail TEXT,
+    name TEXT NOT NULL,
+    session_id TEXT,
+    timestamp INTEGER NOT NULL,
+    agent TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    received_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000)
+);
+
+CREATE INDEX IF NOT EXISTS idx_quarantine_reason ON quarantine(reason);
+CREATE INDEX IF NOT EXISTS idx_quarantine_received_at ON quarantine(received_at);
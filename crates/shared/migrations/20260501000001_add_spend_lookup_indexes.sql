@@ -0,0 +1,5 @@
+-- Composite indexes for live spend lookups (`lumo statusline`), which run on
+-- every Claude Code prompt update. With only single-column indexes SQLite
+-- picks idx_events_name and walks every api_request ever recorded.
+CREATE INDEX IF NOT EXISTS idx_events_name_timestamp ON events(name, timestamp);
+CREATE INDEX IF NOT EXISTS idx_events_session_name ON events(session_id, name);
@@ -0,0 +1,38 @@
+-- Spend budgets with threshold alerts.
+--
+-- A budget caps API cost per calendar day, week or month (in the configured
+-- timezone and week start), either overall or for one project or model.
+-- The daemon checks budgets after each ingested batch and inserts a
+-- notification when spend crosses 50%, 80% or 100% of the limit.
+--
+-- period values: 'daily', 'weekly', 'monthly'
+-- scope values:
+--   'global'  — all spend; scope_value is NULL
+--   'project' — sessions whose hooks reported scope_value as their cwd
+--   'model'   — requests to the model named scope_value
+CREATE TABLE IF NOT EXISTS budgets (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    period TEXT NOT NULL,
+    scope TEXT NOT NULL DEFAULT 'global',
+    scope_value TEXT,
+    limit_usd REAL NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000),
+    updated_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000)
+);
+
+-- Thresholds already alerted on, so each fires at most once per period
+CREATE TABLE IF NOT EXISTS budget_alerts (
+    budget_id INTEGER NOT NULL REFERENCES budgets(id) ON DELETE CASCADE,
+    -- Unix ms start of the budget period the alert belongs to
+    period_start INTEGER NOT NULL,
+    -- Percentage of the limit: 50, 80 or 100
+    threshold INTEGER NOT NULL,
+    created_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000),
+    PRIMARY KEY (budget_id, period_start, threshold)
+);
+
+-- Budget alerts are on by default, like the hook events
+INSERT OR IGNORE INTO notification_settings (hook_event, enabled, show_banner, play_sound)
+VALUES ('Budget', 1, 1, 1);
@@ -0,0 +1,22 @@
+-- Spending and error anomaly alerts.
+--
+-- The daemon checks rolling windows after each ingested batch for cost
+-- spikes, API error bursts, retry storms and unusually expensive sessions,
+-- and inserts a notification for each anomaly found. This table records
+-- the anomalies already notified, so each fires once per window.
+--
+-- kind values: 'cost_spike', 'error_burst', 'retry_storm', 'expensive_session'
+CREATE TABLE IF NOT EXISTS anomaly_alerts (
+    kind TEXT NOT NULL,
+    -- Offending session, '' when the anomaly has none
+    session_id TEXT NOT NULL,
+    -- Unix ms start of the window the anomaly was found in; 0 for anomalies
+    -- raised once per session
+    window_start INTEGER NOT NULL,
+    created_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000),
+    PRIMARY KEY (kind, session_id, window_start)
+);
+
+-- Anomaly alerts are on by default, like budget alerts
+INSERT OR IGNORE INTO notification_settings (hook_event, enabled, show_banner, play_sound)
+VALUES ('Anomaly', 1, 1, 1);
@@ -0,0 +1,3 @@
+-- The project dimension of breakdowns and budgets looks up each session's
+-- latest working directory in notifications, once per event row.
+CREATE INDEX IF NOT EXISTS idx_notifications_session_id ON notifications(session_id);
@@ -0,0 +1,29 @@
+//! Coding agent identifiers
+//!
+//! Every event and metric carries an `agent` column naming the tool that
+//! produced it. These are the values Lumo writes there.
+
+/// Claude Code (OTLP export). Default for rows without an explicit source.
+pub const CLAUDE_CODE: &str = "claude_code";
+
+/// OpenAI Codex CLI (ingested from `~/.codex/sessions` rollout logs).
+pub const CODEX: &str = "codex";
+
+/// Derive an agent identifier from an OTLP `service.name` resource attribute.
+///
+/// `claude-code` maps to [`CLAUDE_CODE`] and any `codex*` service (e.g.
+/// `codex_cli_rs`) to [`CODEX`]; anything else is lowercased with separators
+/// replaced by underscores. Missing or empty names fall back to
+/// [`CLAUDE_CODE`], which is what older Claude Code builds sent.
+pub fn from_service_name(service_name: Option<&str>) -> String {
+    let name = match service_name.map(str::trim) {
+        None | Some("") => return CLAUDE_CODE.to_string(),
+        Some(name) => name.to_lowercase().replace(['-', ' ', '.'], "_"),
+    };
+
+    if name.starts_with(CODEX) {
+        CODEX.to_string()
+    } else {
+        name
+    }
+}
@@ -0,0 +1,513 @@
+//! Spending and error anomalies
+//!
+//! Checks rolling windows of recent events for signs of a runaway agent:
+//!
+//! - cost spike: spend in the last hour far above the average active hour
+//!   of the trailing baseline, or above the minimum alone without one
+//! - error burst: many API errors from one session within a few minutes
+//! - retry storm: many retried API calls (`attempt` above 1) from one
+//!   session within a few minutes
+//! - expensive session: a session active in the last hour costing many
+//!   times the median session of the last 30 days
+//!
+//! Each anomaly names the offending session and its working directory,
+//! as reported by the session's hook notifications.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use crate::error::Result;
+
+const MINUTE_MS: i64 = 60 * 1000;
+const HOUR_MS: i64 = 60 * MINUTE_MS;
+const DAY_MS: i64 = 24 * HOUR_MS;
+
+/// Days of sessions the median session cost is taken over
+const SESSION_HISTORY_DAYS: i64 = 30;
+/// Fewer sessions than this give no meaningful median
+const MIN_MEDIAN_SESSIONS: usize = 5;
+/// How long a computed median session cost is reused
+const MEDIAN_MAX_AGE_MS: i64 = HOUR_MS;
+
+/// Kind of anomaly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    CostSpike,
+    ErrorBurst,
+    RetryStorm,
+    ExpensiveSession,
+}
+
+impl AnomalyKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AnomalyKind::CostSpike => "cost_spike",
+            AnomalyKind::ErrorBurst => "error_burst",
+            AnomalyKind::RetryStorm => "retry_storm",
+            AnomalyKind::ExpensiveSession => "expensive_session",
+        }
+    }
+}
+
+/// When recent activity counts as anomalous
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AnomalyThresholds {
+    /// Spend in the last hour above this multiple of the baseline hour is
+    /// a spike
+    pub cost_spike_multiplier: f64,
+    /// Smallest hourly spend counted as a spike
+    pub cost_spike_min_usd: f64,
+    /// Days before the last hour the baseline averages
+    pub baseline_days: u32,
+    /// Window API errors and retries are counted in
+    pub burst_window_minutes: u32,
+    /// API errors of one session within the window that make a burst
+    pub error_burst_count: u32,
+    /// Retried API calls of one session within the window that make a storm
+    pub retry_storm_count: u32,
+    /// Session cost above this multiple of the median session is anomalous
+    pub session_median_multiplier: f64,
+    /// Smallest session cost counted as anomalous
+    pub session_min_usd: f64,
+}
+
+impl Default for AnomalyThresholds {
+    fn default() -> Self {
+        Self {
+            cost_spike_multiplier: 3.0,
+            cost_spike_min_usd: 5.0,
+            baseline_days: 7,
+            burst_window_minutes: 10,
+            error_burst_count: 10,
+            retry_storm_count: 10,
+            session_median_multiplier: 5.0,
+            session_min_usd: 2.0,
+        }
+    }
+}
+
+/// An anomaly found in recent events
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Anomaly {
+    pub kind: AnomalyKind,
+    /// Offending session; for cost spikes, the one that spent most in the
+    /// last hour, though the spike is the whole hour's spend
+    pub session_id: Option<String>,
+    pub cwd: Option<String>,
+    /// Unix ms start of the hour the anomaly was found in, or 0 for
+    /// expensive sessions, which are flagged once
+    pub window_start: i64,
+    /// Spend in the last hour, error or retry count, or session cost
+    pub observed: f64,
+    /// What `observed` is measured against: the baseline hourly spend (0
+    /// without earlier spend), the count threshold, or the median session
+    /// cost
+    pub baseline: f64,
+}
+
+/// Median session cost over the last 30 days, kept between checks. It
+/// moves slowly, and computing it scans a month of events, so it is reused
+/// for up to an hour.
+#[derive(Debug, Clone, Default)]
+pub struct SessionCostMedian {
+    /// Median (None with too few sessions) and when it was computed
+    cached: Option<(Option<f64>, i64)>,
+}
+
+impl SessionCostMedian {
+    async fn get(&mut self, pool: &SqlitePool, now: i64) -> Result<Option<f64>> {
+        if let Some((median, computed_at)) = self.cached {
+            if now - computed_at < MEDIAN_MAX_AGE_MS {
+                return Ok(median);
+            }
+        }
+        let costs: Vec<f64> = sqlx::query_scalar(
+            r#"
+            SELECT CAST(COALESCE(SUM(cost_usd), 0.0) AS REAL) as cost
+            FROM events
+            WHERE name = 'claude_code.api_request'
+                AND timestamp > ?
+                AND timestamp <= ?
+            GROUP BY session_id
+            "#,
+        )
+        .bind(now - SESSION_HISTORY_DAYS * DAY_MS)
+        .bind(now)
+        .fetch_all(pool)
+        .await?;
+        let median = median(costs);
+        self.cached = Some((median, now));
+        Ok(median)
+    }
+}
+
+/// Anomaly detection queries
+pub struct AnomalyAnalytics;
+
+impl AnomalyAnalytics {
+    /// Anomalies in the events up to now
+    pub async fn detect(
+        pool: &SqlitePool,
+        thresholds: &AnomalyThresholds,
+        median: &mut SessionCostMedian,
+    ) -> Result<Vec<Anomaly>> {
+        Self::detect_at(pool, thresholds, median, Utc::now().timestamp_millis()).await
+    }
+
+    async fn detect_at(
+        pool: &SqlitePool,
+        thresholds: &AnomalyThresholds,
+        median: &mut SessionCostMedian,
+        now: i64,
+    ) -> Result<Vec<Anomaly>> {
+        let hour_start = now - now.rem_euclid(HOUR_MS);
+        let mut anomalies = Vec::new();
+
+        if let Some((session_id, observed, baseline)) = cost_spike(pool, thresholds, now).await? {
+            anomalies.push(Anomaly {
+                kind: AnomalyKind::CostSpike,
+                session_id,
+                cwd: None,
+                window_start: hour_start,
+                observed,
+                baseline,
+            });
+        }
+
+        let burst_start = now - i64::from(thresholds.burst_window_minutes) * MINUTE_MS;
+        let bursts = [
+            (
+                AnomalyKind::ErrorBurst,
+                "name = 'claude_code.api_error'",
+                thresholds.error_burst_count,
+            ),
+            (
+                AnomalyKind::RetryStorm,
+                "name IN ('claude_code.api_request', 'claude_code.api_error') AND attempt > 1",
+                thresholds.retry_storm_count,
+            ),
+        ];
+        for (kind, condition, threshold) in bursts {
+            let query = format!(
+                r#"
+                SELECT session_id, COUNT(*) as count
+                FROM events
+                WHERE timestamp >= ?
+                    AND timestamp <= ?
+                    AND {}
+                GROUP BY session_id
+                HAVING COUNT(*) >= ?
+                ORDER BY count DESC
+                "#,
+                condition
+            );
+            let rows: Vec<(String, i64)> = sqlx::query_as(&query)
+                .bind(burst_start)
+                .bind(now)
+                .bind(i64::from(threshold.max(1)))
+                .fetch_all(pool)
+                .await?;
+            anomalies.extend(rows.into_iter().map(|(session_id, count)| Anomaly {
+                kind,
+                session_id: Some(session_id),
+                cwd: None,
+                window_start: hour_start,
+                observed: count as f64,
+                baseline: f64::from(threshold),
+            }));
+        }
+
+        anomalies.extend(expensive_sessions(pool, thresholds, median, now).await?);
+
+        for anomaly in &mut anomalies {
+            if let Some(session_id) = &anomaly.session_id {
+                anomaly.cwd = session_cwd(pool, session_id).await?;
+            }
+        }
+        Ok(anomalies)
+    }
+}
+
+/// Top session, spend and baseline of the last hour, if it is a spike
+async fn cost_spike(
+    pool: &SqlitePool,
+    thresholds: &AnomalyThresholds,
+    now: i64,
+) -> Result<Option<(Option<String>, f64, f64)>> {
+    let window_start = now - HOUR_MS;
+    let sessions: Vec<(String, f64)> = sqlx::query_as(
+        r#"
+        SELECT session_id, CAST(COALESCE(SUM(cost_usd), 0.0) AS REAL) as cost
+        FROM events
+        WHERE name = 'claude_code.api_request'
+            AND timestamp > ?
+            AND timestamp <= ?
+        GROUP BY session_id
+        ORDER BY cost DESC
+        "#,
+    )
+    .bind(window_start)
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+    let spent: f64 = sessions.iter().map(|(_, cost)| cost).sum();
+    if spent < thresholds.cost_spike_min_usd {
+        return Ok(None);
+    }
+
+    // Average over hours with any spend, so idle nights do not drag it down
+    let baseline: Option<f64> = sqlx::query_scalar(
+        r#"
+        SELECT CAST(AVG(cost) AS REAL)
+        FROM (
+            SELECT SUM(cost_usd) as cost
+            FROM events
+            WHERE name = 'claude_code.api_request'
+                AND timestamp > ?
+                AND timestamp <= ?
+            GROUP BY timestamp / 3600000
+        )
+        "#,
+    )
+    .bind(window_start - i64::from(thresholds.baseline_days) * DAY_MS)
+    .bind(window_start)
+    .fetch_one(pool)
+    .await?;
+
+    // Without earlier spend, the minimum alone decides
+    let baseline = baseline.unwrap_or(0.0);
+    if spent <= baseline * thresholds.cost_spike_multiplier {
+        return Ok(None);
+    }
+    let top = sessions
+        .into_iter()
+        .next()
+        .map(|(session_id, _)| session_id);
+    Ok(Some((top, spent, baseline)))
+}
+
+/// Sessions active in the last hour costing far more than the median
+async fn expensive_sessions(
+    pool: &SqlitePool,
+    thresholds: &AnomalyThresholds,
+    median: &mut SessionCostMedian,
+    now: i64,
+) -> Result<Vec<Anomaly>> {
+    let Some(median) = median.get(pool, now).await? else {
+        return Ok(Vec::new());
+    };
+
+    let costs: Vec<(String, f64)> = sqlx::query_as(
+        r#"
+        SELECT session_id, CAST(COALESCE(SUM(cost_usd), 0.0) AS REAL) as cost
+        FROM events
+        WHERE name = 'claude_code.api_request'
+            AND timestamp > ?
+            AND timestamp <= ?
+            AND session_id IN (
+                SELECT session_id
+                FROM events
+                WHERE name = 'claude_code.api_request'
+                    AND timestamp > ?
+                    AND timestamp <= ?
+            )
+        GROUP BY session_id
+        "#,
+    )
+    .bind(now - SESSION_HISTORY_DAYS * DAY_MS)
+    .bind(now)
+    .bind(now - HOUR_MS)
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+
+    let mut anomalies: Vec<Anomaly> = costs
+        .into_iter()
+        .filter(|(_, cost)| {
+            *cost >= thresholds.session_min_usd
+                && *cost > median * thresholds.session_median_multiplier
+        })
+        .map(|(session_id, cost)| Anomaly {
+            kind: AnomalyKind::ExpensiveSession,
+            session_id: Some(session_id),
+            cwd: None,
+            window_start: 0,
+            observed: cost,
+            baseline: median,
+        })
+        .collect();
+    anomalies.sort_by(|a, b| b.observed.total_cmp(&a.observed));
+    Ok(anomalies)
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.len() < MIN_MEDIAN_SESSIONS {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    })
+}
+
+/// Working directory the session's hooks last reported
+async fn session_cwd(pool: &SqlitePool, session_id: &str) -> Result<Option<String>> {
+    let cwd: Option<String> = sqlx::query_scalar(
+        r#"
+        SELECT cwd
+        FROM notifications
+        WHERE session_id = ?
+            AND cwd IS NOT NULL
+        ORDER BY created_at DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(session_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(cwd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_migrations;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    /// 2026-03-02 10:30 UTC
+    const NOW: i64 = 1_772_447_400_000;
+
+    async fn pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        run_migrations(&pool).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_detects_runaway_session() {
+        let pool = pool().await;
+
+        let mut events = Vec::new();
+        // Five ordinary sessions of $1, each in its own earlier hour
+        for i in 0..5 {
+            events.push((
+                format!("s{}", i),
+                "claude_code.api_request",
+                NOW - (5 + i) * HOUR_MS,
+                Some(1.0),
+                None,
+            ));
+        }
+        // A loop spending $12 and failing with retries in the last minutes
+        for i in 0..4 {
+            events.push((
+                "loop".to_string(),
+                "claude_code.api_request",
+                NOW - (i + 1) * MINUTE_MS,
+                Some(3.0),
+                None,
+            ));
+        }
+        for i in 0..10 {
+            events.push((
+                "loop".to_string(),
+                "claude_code.api_error",
+                NOW - i * 30 * 1000,
+                None,
+                Some(2),
+            ));
+        }
+        for (session, name, timestamp, cost, attempt) in events {
+            sqlx::query(
+                "INSERT INTO events (session_id, name, timestamp, cost_usd, attempt) VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(session)
+            .bind(name)
+            .bind(timestamp)
+            .bind(cost)
+            .bind(attempt)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO notifications (session_id, hook_event, title, message, cwd) VALUES ('loop', 'Stop', 't', 'm', '/work/loop')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let thresholds = AnomalyThresholds::default();
+        let mut median = SessionCostMedian::default();
+        let anomalies = AnomalyAnalytics::detect_at(&pool, &thresholds, &mut median, NOW)
+            .await
+            .unwrap();
+        let kinds: Vec<_> = anomalies.iter().map(|a| a.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                AnomalyKind::CostSpike,
+                AnomalyKind::ErrorBurst,
+                AnomalyKind::RetryStorm,
+                AnomalyKind::ExpensiveSession
+            ]
+        );
+        for anomaly in &anomalies {
+            assert_eq!(anomaly.session_id.as_deref(), Some("loop"));
+            assert_eq!(anomaly.cwd.as_deref(), Some("/work/loop"));
+        }
+        assert_eq!((anomalies[0].observed, anomalies[0].baseline), (12.0, 1.0));
+        assert_eq!(anomalies[1].observed, 10.0);
+        assert_eq!(anomalies[3].baseline, 1.0);
+
+        // Nothing stands out under looser thresholds
+        let relaxed = AnomalyThresholds {
+            cost_spike_min_usd: 20.0,
+            error_burst_count: 11,
+            retry_storm_count: 11,
+            session_median_multiplier: 20.0,
+            ..thresholds
+        };
+        let anomalies = AnomalyAnalytics::detect_at(&pool, &relaxed, &mut median, NOW)
+            .await
+            .unwrap();
+        assert!(anomalies.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cost_spike_without_baseline() {
+        let pool = pool().await;
+        for (session, cost) in [("a", 4.0), ("b", 2.0)] {
+            sqlx::query(
+                "INSERT INTO events (session_id, name, timestamp, cost_usd) VALUES (?, 'claude_code.api_request', ?, ?)",
+            )
+            .bind(session)
+            .bind(NOW - MINUTE_MS)
+            .bind(cost)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let thresholds = AnomalyThresholds::default();
+        let mut median = SessionCostMedian::default();
+        let anomalies = AnomalyAnalytics::detect_at(&pool, &thresholds, &mut median, NOW)
+            .await
+            .unwrap();
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].kind, AnomalyKind::CostSpike);
+        assert_eq!(anomalies[0].session_id.as_deref(), Some("a"));
+        assert_eq!((anomalies[0].observed, anomalies[0].baseline), (6.0, 0.0));
+    }
+}
@@ -0,0 +1,594 @@
+//! Breakdown queries
+//!
+//! A pivot over events and metrics: any combination of measures, split by
+//! dimensions and optionally time buckets, narrowed by filters. Queries are
+//! assembled from fixed SQL fragments picked by enum and every filter value
+//! is bound, so no combination can inject SQL.
+//!
+//! The rows a query aggregates depend on its dimensions: code edit
+//! decisions when it involves the language, tool calls when it involves
+//! the tool, API requests and errors otherwise.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+
+use super::time_range::{bucket_sql_in, time_range_bounds, Granularity, TimeRange};
+use crate::calendar::Calendar;
+use crate::error::{Error, Result};
+
+/// Value aggregated per breakdown row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Measure {
+    /// API cost in USD
+    Cost,
+    InputTokens,
+    OutputTokens,
+    CacheReadTokens,
+    CacheCreationTokens,
+    /// API requests, tool calls or code edit decisions
+    Requests,
+    /// API errors per API call (requests and errors), or failed tool calls
+    /// per tool call
+    ErrorRate,
+    /// Duration percentiles in milliseconds, nearest rank, of successful
+    /// API requests or of tool calls
+    DurationP50,
+    DurationP90,
+    DurationP99,
+}
+
+/// Attribute breakdown rows are split by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Dimension {
+    Model,
+    Tool,
+    /// Working directory of the session, as reported by its hook
+    /// notifications. Sessions without any are `unknown`.
+    Project,
+    Terminal,
+    AppVersion,
+    /// User email, or the account UUID without one
+    Account,
+    /// Language of code edit decisions
+    Language,
+    /// Local hour of day, `00` to `23`
+    Hour,
+    /// Local day of week, `0` (Sunday) to `6`
+    Weekday,
+    Agent,
+}
+
+/// Keep rows whose `dimension` is one of `values`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Filter {
+    pub dimension: Dimension,
+    pub values: Vec<String>,
+}
+
+/// A breakdown over a time range
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreakdownQuery {
+    pub measures: Vec<Measure>,
+    pub dimensions: Vec<Dimension>,
+    pub filters: Vec<Filter>,
+    pub time_range: TimeRange,
+    /// Also split rows into time buckets of this size, labelled like
+    /// [`super::time_range::generate_date_labels`]
+    pub bucket: Option<Granularity>,
+    /// Keep only the first rows
+    pub limit: Option<u32>,
+}
+
+/// One combination of dimension values (and bucket)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakdownRow {
+    /// Value of each dimension in query order, `unknown` where unset
+    pub dimensions: Vec<String>,
+    pub bucket: Option<String>,
+    /// Value of each measure in query order, None where undefined
+    pub values: Vec<Option<f64>>,
+}
+
+/// Label for rows without a value for a dimension
+const UNKNOWN: &str = "unknown";
+
+impl Measure {
+    fn percentile(self) -> Option<f64> {
+        match self {
+            Measure::DurationP50 => Some(0.5),
+            Measure::DurationP90 => Some(0.9),
+            Measure::DurationP99 => Some(0.99),
+            _ => None,
+        }
+    }
+}
+
+/// Rows a breakdown aggregates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    ApiCalls,
+    ToolCalls,
+    CodeEdits,
+}
+
+impl Source {
+    fn of(query: &BreakdownQuery) -> Self {
+        let involves = |dimension: Dimension| {
+            query.dimensions.contains(&dimension)
+                || query.filters.iter().any(|f| f.dimension == dimension)
+        };
+        if involves(Dimension::Language) {
+            Source::CodeEdits
+        } else if involves(Dimension::Tool) {
+            Source::ToolCalls
+        } else {
+            Source::ApiCalls
+        }
+    }
+
+    fn table(self) -> &'static str {
+        match self {
+            Source::ApiCalls | Source::ToolCalls => "events",
+            Source::CodeEdits => "metrics",
+        }
+    }
+
+    fn condition(self) -> &'static str {
+        match self {
+            Source::ApiCalls => "name IN ('claude_code.api_request', 'claude_code.api_error')",
+            Source::ToolCalls => "name = 'claude_code.tool_result'",
+            Source::CodeEdits => "name = 'claude_code.code_edit_tool.decision'",
+        }
+    }
+
+    /// Aggregate over the keyed rows, None where the measure does not
+    /// apply to these rows
+    fn measure_sql(self, measure: Measure) -> Option<String> {
+        const API_REQUEST: &str = "CASE WHEN name = 'claude_code.api_request' THEN 1 ELSE 0 END";
+        let sum = |column: &str| Some(format!("COALESCE(SUM({}), 0)", column));
+        let aggregate = match (self, measure) {
+            (Source::ApiCalls, Measure::Cost) => sum("cost_usd"),
+            (Source::ApiCalls, Measure::InputTokens) => sum("input_tokens"),
+            (Source::ApiCalls, Measure::OutputTokens) => sum("output_tokens"),
+            (Source::ApiCalls, Measure::CacheReadTokens) => sum("cache_read_tokens"),
+            (Source::ApiCalls, Measure::CacheCreationTokens) => sum("cache_creation_tokens"),
+            (Source::ApiCalls, Measure::Requests) => sum(API_REQUEST),
+            (Source::ApiCalls, Measure::ErrorRate) => Some(
+                "1.0 * SUM(CASE WHEN name = 'claude_code.api_error' THEN 1 ELSE 0 END) / COUNT(*)"
+                    .to_string(),
+            ),
+            (Source::ToolCalls, Measure::Requests) => Some("COUNT(*)".to_string()),
+            (Source::ToolCalls, Measure::ErrorRate) => {
+                Some("1.0 * SUM(CASE WHEN success = 0 THEN 1 ELSE 0 END) / COUNT(*)".to_string())
+            }
+            (Source::CodeEdits, Measure::Requests) => sum("value"),
+            (Source::ApiCalls | Source::ToolCalls, measure) => measure.percentile().map(|p| {
+                format!(
+                    "MIN(CASE WHEN duration_rank >= {} * duration_count THEN ranked_duration END)",
+                    p
+                )
+            }),
+            _ => None,
+        }?;
+        Some(format!("CAST({} AS REAL)", aggregate))
+    }
+
+    /// Duration the percentiles rank. API errors are left out: their
+    /// duration is that of the failed attempt, not of a response.
+    fn duration_sql(self) -> &'static str {
+        match self {
+            Source::ApiCalls => "CASE WHEN name = 'claude_code.api_request' THEN duration_ms END",
+            Source::ToolCalls | Source::CodeEdits => "duration_ms",
+        }
+    }
+
+    /// Expression for a dimension's raw value; `local_seconds` is the
+    /// timestamp as wall-clock Unix seconds
+    fn dimension_sql(self, dimension: Dimension, local_seconds: &str) -> Option<String> {
+        let expr = match (self, dimension) {
+            (_, Dimension::Model) => "model".to_string(),
+            (Source::CodeEdits, Dimension::Tool) => "tool".to_string(),
+            (_, Dimension::Tool) => "tool_name".to_string(),
+            // Latest working directory a hook reported for the session
+            (_, Dimension::Project) => format!(
+                r#"(
+                    SELECT cwd FROM notifications
+                    WHERE notifications.session_id = {}.session_id AND cwd IS NOT NULL
+                    ORDER BY created_at DESC
+                    LIMIT 1
+                )"#,
+                self.table()
+            ),
+            (_, Dimension::Terminal) => "terminal_type".to_string(),
+            (_, Dimension::AppVersion) => "app_version".to_string(),
+            (_, Dimension::Account) => "COALESCE(user_email, account_uuid)".to_string(),
+            (Source::CodeEdits, Dimension::Language) => "language".to_string(),
+            (_, Dimension::Language) => return None,
+            (_, Dimension::Hour) => format!("strftime('%H', {}, 'unixepoch')", local_seconds),
+            (_, Dimension::Weekday) => format!("strftime('%w', {}, 'unixepoch')", local_seconds),
+            (_, Dimension::Agent) => "agent".to_string(),
+        };
+        Some(format!("COALESCE(CAST({} AS TEXT), '{}')", expr, UNKNOWN))
+    }
+}
+
+/// A query ready to run: SQL and its bound values, in order
+struct CompiledQuery {
+    sql: String,
+    start_time: i64,
+    end_time: i64,
+    filter_values: Vec<String>,
+    limit: i64,
+}
+
+impl BreakdownQuery {
+    /// Run the breakdown. Rows come ordered by bucket, then by the first
+    /// measure, largest first.
+    pub async fn run(&self, pool: &SqlitePool) -> Result<Vec<BreakdownRow>> {
+        let resolved = BreakdownQuery {
+            time_range: self.time_range.resolve(pool).await?,
+            ..self.clone()
+        };
+        resolved
+            .run_in(pool, &Calendar::current(), Utc::now())
+            .await
+    }
+
+    pub(super) async fn run_in(
+        &self,
+        pool: &SqlitePool,
+        calendar: &Calendar,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<BreakdownRow>> {
+        let compiled = self.compile(calendar, now)?;
+        let mut query = sqlx::query(&compiled.sql)
+            .bind(compiled.start_time)
+            .bind(compiled.end_time);
+        for value in &compiled.filter_values {
+            query = query.bind(value);
+        }
+        let rows = query.bind(compiled.limit).fetch_all(pool).await?;
+
+        let dimensions = self.dimensions.len();
+        rows.iter()
+            .map(|row| {
+                Ok(BreakdownRow {
+                    dimensions: (0..dimensions)
+                        .map(|i| row.try_get(i))
+                        .collect::<std::result::Result<_, _>>()?,
+                    bucket: row.try_get("bucket")?,
+                    values: (0..self.measures.len())
+                        .map(|i| row.try_get(dimensions + 1 + i))
+                        .collect::<std::result::Result<_, _>>()?,
+                })
+            })
+            .collect()
+    }
+
+    fn compile(&self, calendar: &Calendar, now: DateTime<Utc>) -> Result<CompiledQuery> {
+        if self.measures.is_empty() {
+            return Err(Error::InvalidData(
+                "A breakdown needs at least one measure".to_string(),
+            ));
+        }
+        let source = Source::of(self);
+        let (start_time, end_time) = time_range_bounds(self.time_range, calendar, now);
+        let local_seconds = calendar.local_seconds_sql("timestamp", start_time, end_time);
+
+        let dimension_sql = |dimension: Dimension| {
+            source
+                .dimension_sql(dimension, &local_seconds)
+                .ok_or_else(|| Error::InvalidData(format!("Cannot break down by {:?}", dimension)))
+        };
+        let mut keys = Vec::new();
+        for (i, dimension) in self.dimensions.iter().enumerate() {
+            keys.push(format!("{} as d{}", dimension_sql(*dimension)?, i));
+        }
+        let bucket = match self.bucket {
+            Some(granularity) => {
+                bucket_sql_in(self.time_range, granularity, "timestamp", calendar, now).0
+            }
+            None => "NULL".to_string(),
+        };
+        keys.push(format!("{} as bucket", bucket));
+
+        let mut conditions = String::new();
+        let mut filter_values = Vec::new();
+        for filter in &self.filters {
+            if filter.values.is_empty() {
+                return Err(Error::InvalidData(format!(
+                    "Filter on {:?} has no values",
+                    filter.dimension
+                )));
+            }
+            let placeholders = vec!["?"; filter.values.len()].join(", ");
+            conditions.push_str(&format!(
+                "\n                    AND {} IN ({})",
+                dimension_sql(filter.dimension)?,
+                placeholders
+            ));
+            filter_values.extend(filter.values.iter().cloned());
+        }
+
+        let mut measures = Vec::new();
+        for (i, measure) in self.measures.iter().enumerate() {
+            let aggregate = source.measure_sql(*measure).ok_or_else(|| {
+                Error::InvalidData(format!("{:?} is not available for this breakdown", measure))
+            })?;
+            measures.push(format!("{} as m{}", aggregate, i));
+        }
+
+        let group: Vec<String> = (0..self.dimensions.len())
+            .map(|i| format!("d{}", i))
+            .chain(["bucket".to_string()])
+            .collect();
+        let group = group.join(", ");
+        // Percentiles rank each row's duration within its group
+        let ranks = if self.measures.iter().any(|m| m.percentile().is_some()) {
+            format!(
+                r#",
+                    {1} as ranked_duration,
+                    ROW_NUMBER() OVER (PARTITION BY {0} ORDER BY {1} NULLS LAST) as duration_rank,
+                    COUNT({1}) OVER (PARTITION BY {0}) as duration_count"#,
+                group,
+                source.duration_sql()
+            )
+        } else {
+            String::new()
+        };
+        let order = if self.bucket.is_some() {
+            "bucket ASC, m0 DESC"
+        } else {
+            "m0 DESC"
+        };
+
+        let sql = format!(
+            r#"
+            WITH keyed AS (
+                SELECT *, {keys}
+                FROM {table}
+                WHERE {condition}
+                    AND timestamp >= ? AND timestamp <= ?{conditions}
+            ),
+            ranked AS (
+                SELECT *{ranks}
+                FROM keyed
+            )
+            SELECT {group}, {measures}
+            FROM ranked
+            GROUP BY {group}
+            ORDER BY {order}
+            LIMIT ?
+            "#,
+            keys = keys.join(", "),
+            table = source.table(),
+            condition = source.condition(),
+            conditions = conditions,
+            ranks = ranks,
+            group = group,
+            measures = measures.join(", "),
+            order = order,
+        );
+
+        Ok(CompiledQuery {
+            sql,
+            start_time,
+            end_time,
+            filter_values,
+            limit: self.limit.map_or(-1, i64::from),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_migrations;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    const HOUR_MS: i64 = 60 * 60 * 1000;
+
+    fn utc() -> Calendar {
+        Calendar::parse(Some("UTC"), None).unwrap()
+    }
+
+    /// 2026-03-02, a Monday, 10:00 UTC
+    fn now() -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(1_772_445_600_000).unwrap()
+    }
+
+    async fn pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        run_migrations(&pool).await.unwrap();
+
+        let at = |hours_ago: i64| now().timestamp_millis() - hours_ago * HOUR_MS;
+        let events = [
+            (
+                "s1",
+                "claude_code.api_request",
+                at(3),
+                Some("opus"),
+                Some(2.0),
+                None,
+                Some(100.0),
+            ),
+            (
+                "s1",
+                "claude_code.api_request",
+                at(2),
+                Some("opus"),
+                Some(1.0),
+                None,
+                Some(300.0),
+            ),
+            (
+                "s1",
+                "claude_code.api_error",
+                at(2),
+                Some("opus"),
+                None,
+                None,
+                Some(500.0),
+            ),
+            (
+                "s2",
+                "claude_code.api_request",
+                at(1),
+                Some("haiku"),
+                Some(0.5),
+                None,
+                Some(200.0),
+            ),
+            (
+                "s1",
+                "claude_code.tool_result",
+                at(2),
+                None,
+                None,
+                Some("Bash"),
+                Some(10.0),
+            ),
+            (
+                "s2",
+                "claude_code.tool_result",
+                at(1),
+                None,
+                None,
+                Some("Bash"),
+                Some(30.0),
+            ),
+            (
+                "s2",
+                "claude_code.tool_result",
+                at(1),
+                None,
+                None,
+                Some("Read"),
+                Some(20.0),
+            ),
+            // Outside the range
+            (
+                "s2",
+                "claude_code.api_request",
+                at(30),
+                Some("haiku"),
+                Some(9.0),
+                None,
+                None,
+            ),
+        ];
+        for (session, name, timestamp, model, cost, tool, duration) in events {
+            sqlx::query(
+                r#"
+                INSERT INTO events (session_id, name, timestamp, model, cost_usd, tool_name, duration_ms, success)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(session)
+            .bind(name)
+            .bind(timestamp)
+            .bind(model)
+            .bind(cost)
+            .bind(tool)
+            .bind(duration)
+            .bind(tool != Some("Read"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO notifications (session_id, hook_event, title, message, cwd) VALUES ('s1', 'Stop', 't', 'm', '/work/lumo')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    fn breakdown(measures: &[Measure], dimensions: &[Dimension]) -> BreakdownQuery {
+        BreakdownQuery {
+            measures: measures.to_vec(),
+            dimensions: dimensions.to_vec(),
+            filters: Vec::new(),
+            time_range: TimeRange::Today,
+            bucket: None,
+            limit: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_api_breakdown_by_model() {
+        let pool = pool().await;
+        let query = breakdown(
+            &[
+                Measure::Cost,
+                Measure::Requests,
+                Measure::ErrorRate,
+                Measure::DurationP50,
+            ],
+            &[Dimension::Model],
+        );
+        let rows = query.run_in(&pool, &utc(), now()).await.unwrap();
+        assert_eq!(
+            rows,
+            [
+                BreakdownRow {
+                    dimensions: vec!["opus".to_string()],
+                    bucket: None,
+                    values: vec![Some(3.0), Some(2.0), Some(1.0 / 3.0), Some(100.0)],
+                },
+                BreakdownRow {
+                    dimensions: vec!["haiku".to_string()],
+                    bucket: None,
+                    values: vec![Some(0.5), Some(1.0), Some(0.0), Some(200.0)],
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tool_breakdown_with_filters_and_buckets() {
+        let pool = pool().await;
+        let mut query = breakdown(
+            &[Measure::Requests, Measure::ErrorRate],
+            &[Dimension::Project, Dimension::Hour],
+        );
+        query.filters = vec![Filter {
+            dimension: Dimension::Tool,
+            values: vec!["Bash".to_string(), "Read".to_string()],
+        }];
+        query.bucket = Some(Granularity::Day);
+        let rows = query.run_in(&pool, &utc(), now()).await.unwrap();
+        let summary: Vec<_> = rows
+            .iter()
+            .map(|row| (row.dimensions.join(" "), row.values.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("unknown 09".to_string(), vec![Some(2.0), Some(0.5)]),
+                ("/work/lumo 08".to_string(), vec![Some(1.0), Some(0.0)]),
+            ]
+        );
+        assert!(rows
+            .iter()
+            .all(|row| row.bucket.as_deref() == Some("2026-03-02")));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_measures_without_source() {
+        let pool = pool().await;
+        let query = breakdown(&[Measure::Cost], &[Dimension::Language]);
+        assert!(query.run_in(&pool, &utc(), now()).await.is_err());
+        let query = breakdown(&[], &[Dimension::Model]);
+        assert!(query.run_in(&pool, &utc(), now()).await.is_err());
+    }
+}
@@ -0,0 +1,339 @@
+//! Budget status
+//!
+//! Spend against each budget's limit in its current period, and the
+//! cumulative burn-down across that period. Spend is the API cost the
+//! breakdown engine reports for the budget's scope.
+
+use chrono::{DateTime, Datelike, Duration, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use super::breakdown::{BreakdownQuery, Dimension, Filter, Measure};
+use super::time_range::{date_labels, Granularity, TimeRange};
+use crate::calendar::Calendar;
+use crate::database::entities::{Budget, BudgetPeriod, BudgetScope};
+use crate::error::Result;
+
+/// Percentages of a budget's limit that raise an alert
+pub const BUDGET_THRESHOLDS: [u32; 3] = [50, 80, 100];
+
+/// Spend against a budget in its current period
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetStatus {
+    pub budget: Budget,
+    /// Unix ms start of the current period
+    pub period_start: i64,
+    /// Unix ms start of the next period
+    pub period_end: i64,
+    pub spent: f64,
+    /// Spend as a percentage of the limit, above 100 once exceeded
+    pub percent: f64,
+    /// Limit minus spend, negative once exceeded
+    pub remaining: f64,
+}
+
+impl BudgetStatus {
+    /// The thresholds spend has reached, lowest first
+    pub fn crossed_thresholds(&self) -> impl Iterator<Item = u32> + '_ {
+        BUDGET_THRESHOLDS
+            .into_iter()
+            .filter(|threshold| self.percent >= f64::from(*threshold))
+    }
+}
+
+/// Cumulative spend at the end of one bucket of a budget period
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BurnDownPoint {
+    /// Hour ("YYYY-MM-DD HH:00") for daily budgets, day otherwise
+    pub date: String,
+    /// None for buckets that have not started yet
+    pub spent: Option<f64>,
+    pub remaining: Option<f64>,
+    /// What would remain spending the limit evenly across the period
+    pub ideal_remaining: f64,
+}
+
+/// Budget queries
+pub struct BudgetAnalytics;
+
+impl BudgetAnalytics {
+    /// Spend against `budget` in its current period
+    pub async fn status(pool: &SqlitePool, budget: &Budget) -> Result<BudgetStatus> {
+        Self::status_in(pool, budget, &Calendar::current(), Utc::now()).await
+    }
+
+    async fn status_in(
+        pool: &SqlitePool,
+        budget: &Budget,
+        calendar: &Calendar,
+        now: DateTime<Utc>,
+    ) -> Result<BudgetStatus> {
+        let (period_start, period_end) = period_bounds(budget.period, calendar, now);
+        let query = spend_query(
+            budget,
+            TimeRange::Custom {
+                start: period_start,
+                end: now.timestamp_millis(),
+            },
+            None,
+        );
+        let spent: f64 = query
+            .run_in(pool, calendar, now)
+            .await?
+            .iter()
+            .filter_map(|row| row.values[0])
+            .sum();
+
+        Ok(BudgetStatus {
+            budget: budget.clone(),
+            period_start,
+            period_end,
+            spent,
+            percent: spent / budget.limit_usd * 100.0,
+            remaining: budget.limit_usd - spent,
+        })
+    }
+
+    /// Cumulative spend per hour (daily budgets) or day (weekly and monthly
+    /// budgets) across the current period, future buckets included
+    pub async fn burn_down(pool: &SqlitePool, budget: &Budget) -> Result<Vec<BurnDownPoint>> {
+        Self::burn_down_in(pool, budget, &Calendar::current(), Utc::now()).await
+    }
+
+    async fn burn_down_in(
+        pool: &SqlitePool,
+        budget: &Budget,
+        calendar: &Calendar,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<BurnDownPoint>> {
+        let (period_start, period_end) = period_bounds(budget.period, calendar, now);
+        let range = TimeRange::Custom {
+            start: period_start,
+            end: period_end - 1,
+        };
+        let (granularity, format) = match budget.period {
+            BudgetPeriod::Daily => (Granularity::Hour, "%Y-%m-%d %H:00"),
+            BudgetPeriod::Weekly | BudgetPeriod::Monthly => (Granularity::Day, "%Y-%m-%d"),
+        };
+        let rows = spend_query(budget, range, Some(granularity))
+            .run_in(pool, calendar, now)
+            .await?;
+        let current = calendar.local(now).format(format).to_string();
+
+        let labels = date_labels(range, calendar, now);
+        let buckets = labels.len() as f64;
+        let mut spent = 0.0;
+        Ok(labels
+            .into_iter()
+            .enumerate()
+            .map(|(i, date)| {
+                let started = date <= current;
+                if started {
+                    spent += rows
+                        .iter()
+                        .filter(|row| row.bucket.as_deref() == Some(date.as_str()))
+                        .filter_map(|row| row.values[0])
+                        .sum::<f64>();
+                }
+                BurnDownPoint {
+                    spent: started.then_some(spent),
+                    remaining: started.then_some(budget.limit_usd - spent),
+                    ideal_remaining: budget.limit_usd * (1.0 - (i + 1) as f64 / buckets),
+                    date,
+                }
+            })
+            .collect())
+    }
+}
+
+/// Unix ms start of the budget period containing `now`, and of the next
+pub(super) fn period_bounds(
+    period: BudgetPeriod,
+    calendar: &Calendar,
+    now: DateTime<Utc>,
+) -> (i64, i64) {
+    let today = calendar.local(now).date_naive();
+    let (start, end) = match period {
+        BudgetPeriod::Daily => (today, today + Duration::days(1)),
+        BudgetPeriod::Weekly => {
+            let start = calendar.start_of_week(today);
+            (start, start + Duration::days(7))
+        }
+        BudgetPeriod::Monthly => {
+            let start = today.with_day(1).unwrap();
+            (
+                start,
+                start.checked_add_months(chrono::Months::new(1)).unwrap(),
+            )
+        }
+    };
+    (
+        calendar.start_of_day(start).timestamp_millis(),
+        calendar.start_of_day(end).timestamp_millis(),
+    )
+}
+
+/// API cost within `budget`'s scope over `time_range`
+fn spend_query(
+    budget: &Budget,
+    time_range: TimeRange,
+    bucket: Option<Granularity>,
+) -> BreakdownQuery {
+    let dimension = match budget.scope {
+        BudgetScope::Global => None,
+        BudgetScope::Project => Some(Dimension::Project),
+        BudgetScope::Model => Some(Dimension::Model),
+    };
+    let filters = dimension
+        .zip(budget.scope_value.clone())
+        .map(|(dimension, value)| Filter {
+            dimension,
+            values: vec![value],
+        })
+        .into_iter()
+        .collect();
+
+    BreakdownQuery {
+        measures: vec![Measure::Cost],
+        dimensions: Vec::new(),
+        filters,
+        time_range,
+        bucket,
+        limit: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_migrations;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    const HOUR_MS: i64 = 60 * 60 * 1000;
+
+    fn utc() -> Calendar {
+        Calendar::parse(Some("UTC"), None).unwrap()
+    }
+
+    /// 2026-03-04, a Wednesday, 10:00 UTC
+    fn now() -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(1_772_618_400_000).unwrap()
+    }
+
+    fn budget(period: BudgetPeriod, scope: BudgetScope, scope_value: Option<&str>) -> Budget {
+        Budget {
+            id: 1,
+            name: "Test".to_string(),
+            period,
+            scope,
+            scope_value: scope_value.map(str::to_string),
+            limit_usd: 10.0,
+            enabled: true,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_period_bounds() {
+        let day = |d: u32| {
+            chrono::NaiveDate::from_ymd_opt(2026, 3, d)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc()
+                .timestamp_millis()
+        };
+        let calendar = utc();
+        assert_eq!(
+            period_bounds(BudgetPeriod::Daily, &calendar, now()),
+            (day(4), day(5))
+        );
+        assert_eq!(
+            period_bounds(BudgetPeriod::Weekly, &calendar, now()),
+            (day(2), day(9))
+        );
+        let (start, end) = period_bounds(BudgetPeriod::Monthly, &calendar, now());
+        assert_eq!(start, day(1));
+        assert_eq!(end - start, 31 * 24 * HOUR_MS);
+    }
+
+    #[tokio::test]
+    async fn test_status_and_burn_down_by_scope() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        run_migrations(&pool).await.unwrap();
+
+        let at = |hours_ago: i64| now().timestamp_millis() - hours_ago * HOUR_MS;
+        // Today, yesterday (same week) and last week
+        for (session, timestamp, model, cost) in [
+            ("s1", at(1), "opus", 4.0),
+            ("s2", at(2), "haiku", 1.0),
+            ("s1", at(24), "opus", 3.0),
+            ("s1", at(24 * 7), "opus", 50.0),
+        ] {
+            sqlx::query(
+                "INSERT INTO events (session_id, name, timestamp, model, cost_usd) VALUES (?, 'claude_code.api_request', ?, ?, ?)",
+            )
+            .bind(session)
+            .bind(timestamp)
+            .bind(model)
+            .bind(cost)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO notifications (session_id, hook_event, title, message, cwd) VALUES ('s1', 'Stop', 't', 'm', '/work/lumo')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let status = |budget: Budget| {
+            let pool = pool.clone();
+            async move {
+                BudgetAnalytics::status_in(&pool, &budget, &utc(), now())
+                    .await
+                    .unwrap()
+            }
+        };
+        let daily = status(budget(BudgetPeriod::Daily, BudgetScope::Global, None)).await;
+        assert_eq!(daily.spent, 5.0);
+        assert_eq!(daily.percent, 50.0);
+        assert_eq!(daily.crossed_thresholds().collect::<Vec<_>>(), [50]);
+
+        let weekly = budget(
+            BudgetPeriod::Weekly,
+            BudgetScope::Project,
+            Some("/work/lumo"),
+        );
+        let weekly_status = status(weekly.clone()).await;
+        assert_eq!(weekly_status.spent, 7.0);
+        assert_eq!(weekly_status.remaining, 3.0);
+
+        let model = status(budget(
+            BudgetPeriod::Weekly,
+            BudgetScope::Model,
+            Some("haiku"),
+        ))
+        .await;
+        assert_eq!(model.spent, 1.0);
+
+        let points = BudgetAnalytics::burn_down_in(&pool, &weekly, &utc(), now())
+            .await
+            .unwrap();
+        let spent: Vec<_> = points.iter().map(|p| p.spent).collect();
+        assert_eq!(
+            spent,
+            [Some(0.0), Some(3.0), Some(7.0), None, None, None, None]
+        );
+        assert_eq!(points[0].date, "2026-03-02");
+        assert_eq!(points[6].ideal_remaining, 0.0);
+    }
+}
@@ -0,0 +1,305 @@
+//! Period-over-period comparison
+//!
+//! Summary, model, tool and trend statistics for a time range next to the
+//! same statistics for its [`TimeRange::previous_period`]. `All` has no
+//! previous period, so it compares against zero.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use super::display::format_model_display_name;
+use super::stats::{ModelStats, StatsAnalytics};
+use super::time_range::TimeRange;
+use super::tools::{ToolUsageStats, ToolsAnalytics};
+use super::trends::{TrendsAnalytics, UsageTrend};
+use crate::error::Result;
+
+/// A measure in the current and previous period
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comparison {
+    pub current: f64,
+    pub previous: f64,
+    /// `current - previous`
+    pub delta: f64,
+    /// Change relative to the previous value, None when that is zero
+    pub delta_percent: Option<f64>,
+}
+
+impl Comparison {
+    pub fn new(current: f64, previous: f64) -> Self {
+        let delta = current - previous;
+        Self {
+            current,
+            previous,
+            delta,
+            delta_percent: (previous != 0.0).then(|| delta / previous.abs() * 100.0),
+        }
+    }
+}
+
+/// Summary statistics against the previous period
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SummaryComparison {
+    pub total_cost: Comparison,
+    pub total_tokens: Comparison,
+    pub cache_tokens: Comparison,
+    pub cache_percentage: Comparison,
+    pub active_time_seconds: Comparison,
+    pub total_sessions: Comparison,
+    pub lines_of_code_added: Comparison,
+    pub lines_of_code_removed: Comparison,
+    pub pull_requests: Comparison,
+    pub commits: Comparison,
+    pub code_edit_accepts: Comparison,
+    pub code_edit_rejects: Comparison,
+}
+
+/// Model usage against the previous period. Models used in either period
+/// are listed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelComparison {
+    pub model: String,
+    pub display_name: String,
+    pub cost: Comparison,
+    pub requests: Comparison,
+    pub tokens: Comparison,
+}
+
+/// Tool usage against the previous period. Tools used in either period
+/// are listed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolComparison {
+    pub tool_name: String,
+    pub count: Comparison,
+    pub successes: Comparison,
+    pub failures: Comparison,
+    /// Zero in a period without calls
+    pub avg_duration_ms: Comparison,
+}
+
+/// Usage trend bucket against the bucket at the same position of the
+/// previous period, for overlay charts
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageTrendComparison {
+    pub date: String,
+    /// Label of the aligned previous bucket, None past the previous
+    /// period's last bucket
+    pub previous_date: Option<String>,
+    pub cost: Comparison,
+    pub input_tokens: Comparison,
+    pub output_tokens: Comparison,
+    pub cache_read_tokens: Comparison,
+    pub cache_creation_tokens: Comparison,
+}
+
+/// Comparison queries
+pub struct ComparisonAnalytics;
+
+impl ComparisonAnalytics {
+    /// Summary statistics against the previous period
+    pub async fn get_summary_comparison(
+        pool: &SqlitePool,
+        time_range: TimeRange,
+        agent: Option<&str>,
+    ) -> Result<SummaryComparison> {
+        let current = StatsAnalytics::get_summary(pool, time_range, agent).await?;
+        let previous = match time_range.previous_period() {
+            Some(previous) => StatsAnalytics::get_summary(pool, previous, agent).await?,
+            None => Default::default(),
+        };
+
+        let compare =
+            |current: i64, previous: i64| Comparison::new(current as f64, previous as f64);
+        Ok(SummaryComparison {
+            total_cost: Comparison::new(current.total_cost, previous.total_cost),
+            total_tokens: compare(current.total_tokens, previous.total_tokens),
+            cache_tokens: compare(current.cache_tokens, previous.cache_tokens),
+            cache_percentage: Comparison::new(current.cache_percentage, previous.cache_percentage),
+            active_time_seconds: compare(current.active_time_seconds, previous.active_time_seconds),
+            total_sessions: compare(current.total_sessions, previous.total_sessions),
+            lines_of_code_added: compare(current.lines_of_code_added, previous.lines_of_code_added),
+            lines_of_code_removed: compare(
+                current.lines_of_code_removed,
+                previous.lines_of_code_removed,
+            ),
+            pull_requests: compare(current.pull_requests, previous.pull_requests),
+            commits: compare(current.commits, previous.commits),
+            code_edit_accepts: compare(current.code_edit_accepts, previous.code_edit_accepts),
+            code_edit_rejects: compare(current.code_edit_rejects, previous.code_edit_rejects),
+        })
+    }
+
+    /// Model usage against the previous period, by current cost
+    pub async fn get_model_comparison(
+        pool: &SqlitePool,
+        time_range: TimeRange,
+        agent: Option<&str>,
+    ) -> Result<Vec<ModelComparison>> {
+        let current = StatsAnalytics::get_model_stats(pool, time_range, agent).await?;
+        let previous = match time_range.previous_period() {
+            Some(previous) => StatsAnalytics::get_model_stats(pool, previous, agent).await?,
+            None => Vec::new(),
+        };
+
+        let measures = |s: &ModelStats| [s.cost, s.requests as f64, s.tokens as f64];
+        let rows = pair_by_key(
+            current.iter().map(|s| (s.model.clone(), measures(s))),
+            previous.iter().map(|s| (s.model.clone(), measures(s))),
+        );
+        Ok(rows
+            .into_iter()
+            .map(|(model, [cost, requests, tokens])| ModelComparison {
+                display_name: format_model_display_name(&model),
+                model,
+                cost,
+                requests,
+                tokens,
+            })
+            .collect())
+    }
+
+    /// Tool usage against the previous period, by current call count
+    pub async fn get_tool_comparison(
+        pool: &SqlitePool,
+        time_range: TimeRange,
+        agent: Option<&str>,
+    ) -> Result<Vec<ToolComparison>> {
+        let current = ToolsAnalytics::get_tool_usage_stats(pool, time_range, agent).await?;
+        let previous = match time_range.previous_period() {
+            Some(previous) => ToolsAnalytics::get_tool_usage_stats(pool, previous, agent).await?,
+            None => Vec::new(),
+        };
+
+        let measures = |s: &ToolUsageStats| {
+            [
+                s.count as f64,
+                s.successes as f64,
+                s.failures as f64,
+                s.avg_duration_ms.unwrap_or(0.0),
+            ]
+        };
+        let rows = pair_by_key(
+            current.iter().map(|s| (s.tool_name.clone(), measures(s))),
+            previous.iter().map(|s| (s.tool_name.clone(), measures(s))),
+        );
+        Ok(rows
+            .into_iter()
+            .map(
+                |(tool_name, [count, successes, failures, avg_duration_ms])| ToolComparison {
+                    tool_name,
+                    count,
+                    successes,
+                    failures,
+                    avg_duration_ms,
+                },
+            )
+            .collect())
+    }
+
+    /// Usage trends with the previous period's buckets aligned by position:
+    /// the first bucket against the previous period's first, and so on
+    pub async fn get_usage_trend_comparison(
+        pool: &SqlitePool,
+        time_range: TimeRange,
+        agent: Option<&str>,
+    ) -> Result<Vec<UsageTrendComparison>> {
+        let time_range = time_range.resolve(pool).await?;
+        let current = TrendsAnalytics::get_usage_trends(pool, time_range, agent).await?;
+        let previous = match time_range.previous_period() {
+            Some(previous) => TrendsAnalytics::get_usage_trends(pool, previous, agent).await?,
+            None => Vec::new(),
+        };
+
+        let mut previous = previous.into_iter();
+        Ok(current
+            .into_iter()
+            .map(|current| {
+                let previous = previous.next();
+                let value = |f: fn(&UsageTrend) -> f64| {
+                    Comparison::new(f(&current), previous.as_ref().map_or(0.0, f))
+                };
+                UsageTrendComparison {
+                    cost: value(|t| t.cost),
+                    input_tokens: value(|t| t.input_tokens as f64),
+                    output_tokens: value(|t| t.output_tokens as f64),
+                    cache_read_tokens: value(|t| t.cache_read_tokens as f64),
+                    cache_creation_tokens: value(|t| t.cache_creation_tokens as f64),
+                    previous_date: previous.map(|t| t.date),
+                    date: current.date,
+                }
+            })
+            .collect())
+    }
+}
+
+/// Pair per-key measures of both periods, zero where a key is missing from
+/// one. Keys keep the current period's order, followed by keys only seen
+/// in the previous period.
+fn pair_by_key<const N: usize>(
+    current: impl Iterator<Item = (String, [f64; N])>,
+    previous: impl Iterator<Item = (String, [f64; N])>,
+) -> Vec<(String, [Comparison; N])> {
+    let mut previous: Vec<(String, [f64; N])> = previous.collect();
+    let mut previous_index: HashMap<String, usize> = previous
+        .iter()
+        .enumerate()
+        .map(|(i, (key, _))| (key.clone(), i))
+        .collect();
+
+    let mut rows: Vec<(String, [Comparison; N])> = current
+        .map(|(key, values)| {
+            let before = previous_index
+                .remove(&key)
+                .map_or([0.0; N], |i| previous[i].1);
+            let comparisons = std::array::from_fn(|i| Comparison::new(values[i], before[i]));
+            (key, comparisons)
+        })
+        .collect();
+
+    previous.retain(|(key, _)| previous_index.contains_key(key));
+    rows.extend(previous.into_iter().map(|(key, before)| {
+        let comparisons = std::array::from_fn(|i| Comparison::new(0.0, before[i]));
+        (key, comparisons)
+    }));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comparison_deltas() {
+        let up = Comparison::new(15.0, 10.0);
+        assert_eq!(up.delta, 5.0);
+        assert_eq!(up.delta_percent, Some(50.0));
+        assert_eq!(Comparison::new(0.0, 4.0).delta_percent, Some(-100.0));
+        assert_eq!(Comparison::new(3.0, 0.0).delta_percent, None);
+    }
+
+    #[test]
+    fn test_pair_by_key_keeps_keys_of_both_periods() {
+        let current = [("opus".to_string(), [3.0]), ("haiku".to_string(), [1.0])];
+        let previous = [("sonnet".to_string(), [2.0]), ("opus".to_string(), [1.0])];
+        let rows = pair_by_key(current.into_iter(), previous.into_iter());
+        let summary: Vec<_> = rows
+            .iter()
+            .map(|(key, [c])| (key.as_str(), c.current, c.previous))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("opus", 3.0, 1.0),
+                ("haiku", 1.0, 0.0),
+                ("sonnet", 0.0, 2.0)
+            ]
+        );
+    }
+}
@@ -0,0 +1,45 @@
+//! Display names for agents and models
+
+/// Format agent identifier to display name
+pub fn format_agent_display_name(agent: &str) -> String {
+    match agent {
+        crate::agent::CLAUDE_CODE => "Claude Code".to_string(),
+        crate::agent::CODEX => "Codex".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Format model identifier to display name
+pub fn format_model_display_name(model: &str) -> String {
+    let model_lower = model.to_lowercase();
+
+    if model_lower.contains("opus") {
+        if model_lower.contains("4-5") || model_lower.contains("4.5") {
+            "Opus 4.5".to_string()
+        } else if model_lower.contains("4") {
+            "Opus 4".to_string()
+        } else {
+            "Opus".to_string()
+        }
+    } else if model_lower.contains("sonnet") {
+        if model_lower.contains("4-5") || model_lower.contains("4.5") {
+            "Sonnet 4.5".to_string()
+        } else if model_lower.contains("-4-") || model_lower.ends_with("-4") {
+            "Sonnet 4".to_string()
+        } else if model_lower.contains("3-5") || model_lower.contains("3.5") {
+            "Sonnet 3.5".to_string()
+        } else {
+            "Sonnet".to_string()
+        }
+    } else if model_lower.contains("haiku") {
+        if model_lower.contains("3-5") || model_lower.contains("3.5") {
+            "Haiku 3.5".to_string()
+        } else if model_lower.contains("3") {
+            "Haiku 3".to_string()
+        } else {
+            "Haiku".to_string()
+        }
+    } else {
+        model.to_string()
+    }
+}
@@ -0,0 +1,374 @@
+//! Spend forecasting
+//!
+//! Projects spend to the end of the current day, week or month from the
+//! daily spend of the last four weeks, or of the days since the first
+//! spend when that is more recent. Weekdays and weekends are averaged
+//! separately, since most usage follows the working week. Today is still
+//! in progress, so it counts towards spend to date but not towards the
+//! averages.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use super::budgets::period_bounds;
+use super::spend::SpendAnalytics;
+use crate::calendar::Calendar;
+use crate::database::entities::BudgetPeriod;
+use crate::error::Result;
+
+/// Completed days the averages are taken over
+const HISTORY_DAYS: u32 = 28;
+
+/// Standard deviations either side of the projection in the confidence
+/// band: 95% under a normal approximation
+const BAND_Z: f64 = 1.96;
+
+/// Two-sided 95% Student's t quantiles for 1 to 30 degrees of freedom.
+/// Averages of few days are uncertain themselves, so their band is wider
+/// than [`BAND_Z`] standard deviations.
+const T_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+/// Projected spend for the current period
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendForecast {
+    /// First day of the period, `YYYY-MM-DD`
+    pub period_start: String,
+    /// Last day of the period, `YYYY-MM-DD`
+    pub period_end: String,
+    /// Spend so far this period, today included
+    pub spent: f64,
+    /// Projected spend at the end of the period
+    pub projected: f64,
+    /// Lower edge of the 95% confidence band, never below spend so far
+    pub lower: f64,
+    /// Upper edge of the 95% confidence band
+    pub upper: f64,
+    /// Average spend on completed weekdays and weekend days
+    pub weekday_average: f64,
+    pub weekend_average: f64,
+    pub limit: Option<f64>,
+    /// Day projected spend first goes over `limit`, if it does within the
+    /// period. A day in the past when it already has.
+    pub exceeds_on: Option<String>,
+    /// Cumulative spend per day of the period
+    pub days: Vec<ForecastDay>,
+}
+
+/// Cumulative spend at the end of one day of a forecast period
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastDay {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    /// Recorded spend, None after today
+    pub actual: Option<f64>,
+    pub projected: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Spend forecasting queries
+pub struct ForecastAnalytics;
+
+impl ForecastAnalytics {
+    /// Forecast spend to the end of the current `period`, and the day it
+    /// goes over `limit`
+    pub async fn forecast(
+        pool: &SqlitePool,
+        period: BudgetPeriod,
+        limit: Option<f64>,
+        agent: Option<&str>,
+    ) -> Result<SpendForecast> {
+        Self::forecast_in(pool, period, limit, agent, &Calendar::current(), Utc::now()).await
+    }
+
+    async fn forecast_in(
+        pool: &SqlitePool,
+        period: BudgetPeriod,
+        limit: Option<f64>,
+        agent: Option<&str>,
+        calendar: &Calendar,
+        now: DateTime<Utc>,
+    ) -> Result<SpendForecast> {
+        let today = calendar.local(now).date_naive();
+        let (start, end) = period_bounds(period, calendar, now);
+        let first = local_date(calendar, start).unwrap_or(today);
+        let last = local_date(calendar, end).unwrap_or(today) - Duration::days(1);
+
+        let days_in_period = (today - first).num_days() as u32 + 1;
+        let days = days_in_period.max(HISTORY_DAYS + 1);
+        let spend = SpendAnalytics::daily_spend_in(pool, days, agent, calendar, now).await?;
+        let spend: HashMap<NaiveDate, f64> = spend
+            .into_iter()
+            .filter_map(|day| {
+                let date = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d").ok()?;
+                Some((date, day.cost))
+            })
+            .collect();
+
+        Ok(project(&spend, today, first, last, limit))
+    }
+}
+
+fn local_date(calendar: &Calendar, timestamp_ms: i64) -> Option<NaiveDate> {
+    calendar
+        .local_millis(timestamp_ms)
+        .map(|time| time.date_naive())
+}
+
+/// Mean of daily spend, and the variance of a day's spend around it
+#[derive(Debug, Clone, Copy, Default)]
+struct DayProfile {
+    mean: f64,
+    /// Prediction variance, scaled so that [`BAND_Z`] standard deviations
+    /// span the t-distribution's 95% interval for this many samples
+    variance: f64,
+}
+
+impl DayProfile {
+    fn of(costs: &[f64]) -> Option<Self> {
+        if costs.is_empty() {
+            return None;
+        }
+        let n = costs.len() as f64;
+        let mean = costs.iter().sum::<f64>() / n;
+        let variance = if costs.len() > 1 {
+            let sample = costs.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            let t = T_95.get(costs.len() - 2).copied().unwrap_or(BAND_Z);
+            // A new day varies around the mean, which is itself uncertain
+            sample * (1.0 + 1.0 / n) * (t / BAND_Z).powi(2)
+        } else {
+            // A single day says nothing about the spread: assume it is as
+            // large as the spend itself
+            mean.powi(2)
+        };
+        Some(Self { mean, variance })
+    }
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// Forecast the period `first..=last` from per-day spend, which covers the
+/// history before `today` and the period up to and including `today`
+fn project(
+    spend: &HashMap<NaiveDate, f64>,
+    today: NaiveDate,
+    first: NaiveDate,
+    last: NaiveDate,
+    limit: Option<f64>,
+) -> SpendForecast {
+    let cost = |date: NaiveDate| spend.get(&date).copied().unwrap_or(0.0);
+
+    // Days before the first recorded spend predate tracking, not idle days
+    let history: Vec<NaiveDate> = (1..=i64::from(HISTORY_DAYS))
+        .rev()
+        .map(|days| today - Duration::days(days))
+        .skip_while(|date| cost(*date) <= 0.0)
+        .collect();
+    let costs = |weekend: bool| -> Vec<f64> {
+        history
+            .iter()
+            .filter(|date| is_weekend(**date) == weekend)
+            .map(|date| cost(*date))
+            .collect()
+    };
+    let all: Vec<f64> = history.iter().map(|date| cost(*date)).collect();
+    let overall = DayProfile::of(&all).unwrap_or_default();
+    let weekday = DayProfile::of(&costs(false)).unwrap_or(overall);
+    let weekend = DayProfile::of(&costs(true)).unwrap_or(overall);
+    let profile = |date: NaiveDate| if is_weekend(date) { weekend } else { weekday };
+
+    let mut actual = 0.0;
+    let mut projected = 0.0;
+    let mut variance = 0.0;
+    let mut exceeds_on = None;
+    let mut days = Vec::new();
+    for date in first.iter_days().take_while(|date| *date <= last) {
+        if date <= today {
+            actual += cost(date);
+        }
+        if date < today {
+            projected += cost(date);
+        } else {
+            // Today counts as at least its expected spend until it is over
+            let expected = profile(date);
+            let recorded = if date == today { cost(date) } else { 0.0 };
+            if recorded < expected.mean {
+                projected += expected.mean;
+                variance += expected.variance;
+            } else {
+                projected += recorded;
+            }
+        }
+
+        let half_band = BAND_Z * variance.sqrt();
+        let label = date.format("%Y-%m-%d").to_string();
+        if exceeds_on.is_none() && limit.is_some_and(|limit| projected > limit) {
+            exceeds_on = Some(label.clone());
+        }
+        days.push(ForecastDay {
+            date: label,
+            actual: (date <= today).then_some(actual),
+            projected,
+            lower: (projected - half_band).max(actual),
+            upper: projected + half_band,
+        });
+    }
+
+    let (lower, upper) = days
+        .last()
+        .map_or((actual, actual), |day| (day.lower, day.upper));
+    SpendForecast {
+        period_start: first.format("%Y-%m-%d").to_string(),
+        period_end: last.format("%Y-%m-%d").to_string(),
+        spent: actual,
+        projected,
+        lower,
+        upper,
+        weekday_average: weekday.mean,
+        weekend_average: weekend.mean,
+        limit,
+        exceeds_on,
+        days,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    /// Four weeks of $10 weekdays and $2 weekends before Wednesday
+    /// 2026-03-18, which has $4 so far
+    fn history() -> HashMap<NaiveDate, f64> {
+        let today = date(18);
+        let mut spend: HashMap<NaiveDate, f64> = (1..=28)
+            .map(|days| {
+                let day = today - Duration::days(days);
+                (day, if is_weekend(day) { 2.0 } else { 10.0 })
+            })
+            .collect();
+        spend.insert(today, 4.0);
+        spend
+    }
+
+    #[test]
+    fn test_projects_weekdays_and_weekends_separately() {
+        let forecast = project(&history(), date(18), date(1), date(31), Some(250.0));
+        assert_eq!(forecast.weekday_average, 10.0);
+        assert_eq!(forecast.weekend_average, 2.0);
+
+        // March 1-17: 12 weekdays and 5 weekend days, then today
+        assert_eq!(forecast.spent, 12.0 * 10.0 + 5.0 * 2.0 + 4.0);
+        // Today at its expected $10, then 9 weekdays and 4 weekend days
+        assert_eq!(forecast.projected, 130.0 + 10.0 + 9.0 * 10.0 + 4.0 * 2.0);
+        // No variation in the history, so no band
+        assert_eq!(forecast.lower, forecast.projected);
+        assert_eq!(forecast.upper, forecast.projected);
+        assert_eq!(forecast.exceeds_on, None);
+        assert_eq!(forecast.days.len(), 31);
+        assert_eq!(forecast.days[18].actual, None);
+
+        let forecast = project(&history(), date(18), date(1), date(31), Some(200.0));
+        assert_eq!(forecast.exceeds_on.as_deref(), Some("2026-03-26"));
+    }
+
+    #[test]
+    fn test_band_widens_with_variation() {
+        let mut spend = history();
+        spend.insert(date(17), 30.0);
+        let forecast = project(&spend, date(18), date(16), date(22), None);
+        assert!(forecast.lower < forecast.projected);
+        assert!(forecast.upper > forecast.projected);
+        assert!(forecast.lower >= forecast.spent);
+        assert_eq!(forecast.exceeds_on, None);
+    }
+
+    #[test]
+    fn test_averages_only_since_first_spend() {
+        // Tracking started on Friday the 13th
+        let mut spend: HashMap<NaiveDate, f64> =
+            [(date(13), 8.0), (date(16), 10.0), (date(17), 12.0)].into();
+        let recent = project(&spend, date(18), date(16), date(22), None);
+        assert_eq!(recent.weekday_average, 10.0);
+        assert_eq!(recent.weekend_average, 0.0);
+
+        // The same spread over four weeks gives a narrower band
+        for days in 4..=28 {
+            let day = date(18) - Duration::days(days);
+            if !is_weekend(day) {
+                spend.insert(day, [8.0, 10.0, 12.0][days as usize % 3]);
+            }
+        }
+        let settled = project(&spend, date(18), date(16), date(22), None);
+        assert!(recent.upper - recent.projected > 2.0 * (settled.upper - settled.projected));
+    }
+
+    #[tokio::test]
+    async fn test_forecast_from_database() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::run_migrations(&pool).await.unwrap();
+
+        let calendar = Calendar::parse(Some("UTC"), None).unwrap();
+        // Wednesday 2026-03-18, 12:00 UTC
+        let now = DateTime::from_timestamp_millis(1_773_835_200_000).unwrap();
+        let mut events: Vec<(NaiveDate, f64, &str)> = history()
+            .into_iter()
+            .map(|(day, cost)| (day, cost, "claude_code"))
+            .collect();
+        events.push((date(18), 100.0, "codex"));
+        for (day, cost, agent) in events {
+            let timestamp = day.and_hms_opt(9, 0, 0).unwrap().and_utc();
+            sqlx::query(
+                "INSERT INTO events (session_id, name, timestamp, cost_usd, agent) VALUES ('s1', 'claude_code.api_request', ?, ?, ?)",
+            )
+            .bind(timestamp.timestamp_millis())
+            .bind(cost)
+            .bind(agent)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let forecast = ForecastAnalytics::forecast_in(
+            &pool,
+            BudgetPeriod::Monthly,
+            Some(200.0),
+            Some("claude_code"),
+            &calendar,
+            now,
+        )
+        .await
+        .unwrap();
+        assert_eq!(forecast.period_start, "2026-03-01");
+        assert_eq!(forecast.period_end, "2026-03-31");
+        assert_eq!(forecast.spent, 134.0);
+        assert_eq!(forecast.projected, 238.0);
+        assert_eq!(forecast.exceeds_on.as_deref(), Some("2026-03-26"));
+
+        let forecast =
+            ForecastAnalytics::forecast_in(&pool, BudgetPeriod::Weekly, None, None, &calendar, now)
+                .await
+                .unwrap();
+        // Monday to today, with Codex's $100 today
+        assert_eq!(forecast.period_start, "2026-03-16");
+        assert_eq!(forecast.spent, 10.0 + 10.0 + 104.0);
+    }
+}
@@ -0,0 +1,59 @@
+//! Usage analytics
+//!
+//! Aggregation queries behind the app's dashboard and the `lumo` CLI:
+//! summary, model, token and agent statistics, trends, tool usage,
+//! comparisons with the previous period, breakdowns by any dimension,
+//! budget status and spend forecasts, spending and error anomalies,
+//! fixed-interval series for charting tools, and the live spend the Claude
+//! Code status line shows.
+
+mod anomalies;
+mod breakdown;
+mod budgets;
+mod comparison;
+mod display;
+mod forecast;
+mod series;
+mod spend;
+mod stats;
+pub mod time_range;
+mod tools;
+mod trends;
+
+use sqlx::SqlitePool;
+
+use crate::error::Result;
+
+pub use anomalies::{Anomaly, AnomalyAnalytics, AnomalyKind, AnomalyThresholds, SessionCostMedian};
+pub use breakdown::{BreakdownQuery, BreakdownRow, Dimension, Filter, Measure};
+pub use budgets::{BudgetAnalytics, BudgetStatus, BurnDownPoint, BUDGET_THRESHOLDS};
+pub use comparison::{
+    Comparison, ComparisonAnalytics, ModelComparison, SummaryComparison, ToolComparison,
+    UsageTrendComparison,
+};
+pub use display::{format_agent_display_name, format_model_display_name};
+pub use forecast::{ForecastAnalytics, ForecastDay, SpendForecast};
+pub use series::{Series, SeriesAnalytics, SeriesTarget};
+pub use spend::{DailySpend, SpendAnalytics, SpendSnapshot};
+pub use stats::{AgentStats, ModelStats, StatsAnalytics, SummaryStats, TokenStats};
+pub use time_range::{Granularity, TimeRange};
+pub use tools::{CodeEditLanguageStats, ToolTrend, ToolUsageStats, ToolsAnalytics};
+pub use trends::{CostByModelTrend, CostEfficiencyTrend, TrendsAnalytics, UsageTrend};
+
+/// Check whether a table or view exists, so queries against a database
+/// that predates it return empty results instead of failing
+pub(crate) async fn source_exists(pool: &SqlitePool, name: &str) -> Result<bool> {
+    let row: Option<i64> = sqlx::query_scalar(
+        r#"
+        SELECT 1
+        FROM sqlite_master
+        WHERE name = ?
+          AND type IN ('table', 'view')
+        LIMIT 1
+        "#,
+    )
+    .bind(name)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.is_some())
+}
@@ -0,0 +1,273 @@
+//! Fixed-interval time series
+//!
+//! Named series over events and metrics, bucketed by an arbitrary interval
+//! rather than the dashboard's hour/day labels, for charting tools such as
+//! Grafana that choose their own resolution.
+
+use std::collections::BTreeMap;
+
+use sqlx::{Row, SqlitePool};
+
+use super::display::{format_agent_display_name, format_model_display_name};
+use crate::error::Result;
+
+/// Smallest bucket, so a zoomed-in chart cannot ask for one row per event
+const MIN_INTERVAL_MS: i64 = 1000;
+
+/// A chartable series
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeriesTarget {
+    /// API cost in USD
+    Cost,
+    /// API cost in USD, one series per model
+    CostByModel,
+    /// API cost in USD, one series per agent
+    CostByAgent,
+    /// Input, output, cache read and cache creation tokens
+    TokensByType,
+    /// API request count
+    Requests,
+    /// API error count
+    ApiErrors,
+    /// Tool call count, one series per tool
+    ToolCallsByTool,
+    /// Lines added and removed
+    LinesOfCode,
+    Commits,
+    PullRequests,
+    /// Active time in seconds
+    ActiveTime,
+}
+
+impl SeriesTarget {
+    pub const ALL: [SeriesTarget; 11] = [
+        SeriesTarget::Cost,
+        SeriesTarget::CostByModel,
+        SeriesTarget::CostByAgent,
+        SeriesTarget::TokensByType,
+        SeriesTarget::Requests,
+        SeriesTarget::ApiErrors,
+        SeriesTarget::ToolCallsByTool,
+        SeriesTarget::LinesOfCode,
+        SeriesTarget::Commits,
+        SeriesTarget::PullRequests,
+        SeriesTarget::ActiveTime,
+    ];
+
+    /// Stable name used by API clients
+    pub fn name(self) -> &'static str {
+        match self {
+            SeriesTarget::Cost => "cost",
+            SeriesTarget::CostByModel => "cost_by_model",
+            SeriesTarget::CostByAgent => "cost_by_agent",
+            SeriesTarget::TokensByType => "tokens_by_type",
+            SeriesTarget::Requests => "requests",
+            SeriesTarget::ApiErrors => "api_errors",
+            SeriesTarget::ToolCallsByTool => "tool_calls_by_tool",
+            SeriesTarget::LinesOfCode => "lines_of_code",
+            SeriesTarget::Commits => "commits",
+            SeriesTarget::PullRequests => "pull_requests",
+            SeriesTarget::ActiveTime => "active_time",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|target| target.name() == name)
+    }
+
+    fn spec(self) -> SeriesSpec {
+        const API_REQUEST: &str = "claude_code.api_request";
+        let cost = &[("cost", "SUM(cost_usd)")];
+        let (table, name, split, values): (_, _, _, &'static [_]) = match self {
+            SeriesTarget::Cost => ("events", API_REQUEST, None, cost),
+            SeriesTarget::CostByModel => (
+                "events",
+                API_REQUEST,
+                Some((
+                    "COALESCE(model, 'unknown')",
+                    format_model_display_name as SeriesLabel,
+                )),
+                cost,
+            ),
+            SeriesTarget::CostByAgent => (
+                "events",
+                API_REQUEST,
+                Some(("agent", format_agent_display_name as SeriesLabel)),
+                cost,
+            ),
+            SeriesTarget::TokensByType => (
+                "events",
+                API_REQUEST,
+                None,
+                &[
+                    ("input", "SUM(input_tokens)"),
+                    ("output", "SUM(output_tokens)"),
+                    ("cache_read", "SUM(cache_read_tokens)"),
+                    ("cache_creation", "SUM(cache_creation_tokens)"),
+                ],
+            ),
+            SeriesTarget::Requests => ("events", API_REQUEST, None, &[("requests", "COUNT(*)")]),
+            SeriesTarget::ApiErrors => (
+                "events",
+                "claude_code.api_error",
+                None,
+                &[("api_errors", "COUNT(*)")],
+            ),
+            SeriesTarget::ToolCallsByTool => (
+                "events",
+                "claude_code.tool_result",
+                Some((
+                    "COALESCE(tool_name, 'unknown')",
+                    str::to_string as SeriesLabel,
+                )),
+                &[("tool_calls", "COUNT(*)")],
+            ),
+            SeriesTarget::LinesOfCode => (
+                "metrics",
+                "claude_code.lines_of_code.count",
+                None,
+                &[
+                    (
+                        "added",
+                        "SUM(CASE WHEN metric_type = 'added' THEN value END)",
+                    ),
+                    (
+                        "removed",
+                        "SUM(CASE WHEN metric_type = 'removed' THEN value END)",
+                    ),
+                ],
+            ),
+            SeriesTarget::Commits => (
+                "metrics",
+                "claude_code.commit.count",
+                None,
+                &[("commits", "SUM(value)")],
+            ),
+            SeriesTarget::PullRequests => (
+                "metrics",
+                "claude_code.pull_request.count",
+                None,
+                &[("pull_requests", "SUM(value)")],
+            ),
+            SeriesTarget::ActiveTime => (
+                "metrics",
+                "claude_code.active_time.total",
+                None,
+                &[("active_time", "SUM(value)")],
+            ),
+        };
+        SeriesSpec {
+            table,
+            name,
+            split,
+            values,
+        }
+    }
+}
+
+/// Turns a split value into a series name
+type SeriesLabel = fn(&str) -> String;
+
+/// How a target maps onto SQL
+struct SeriesSpec {
+    table: &'static str,
+    /// Event or metric name
+    name: &'static str,
+    /// Expression splitting rows into one series per value, and how to
+    /// label each value
+    split: Option<(&'static str, SeriesLabel)>,
+    /// (series label, aggregate) pairs, one series each
+    values: &'static [(&'static str, &'static str)],
+}
+
+/// A named series of `(bucket start in Unix ms, value)` points, oldest
+/// first. Buckets without data are omitted.
+#[derive(Debug, Clone)]
+pub struct Series {
+    pub name: String,
+    pub points: Vec<(i64, f64)>,
+}
+
+/// Fixed-interval series queries
+pub struct SeriesAnalytics;
+
+impl SeriesAnalytics {
+    /// Series of `target` between `start_time` and `end_time` (Unix ms),
+    /// in buckets of `interval_ms` aligned to the Unix epoch
+    pub async fn query(
+        pool: &SqlitePool,
+        target: SeriesTarget,
+        start_time: i64,
+        end_time: i64,
+        interval_ms: i64,
+        agent: Option<&str>,
+    ) -> Result<Vec<Series>> {
+        let spec = target.spec();
+        let interval_ms = interval_ms.max(MIN_INTERVAL_MS);
+
+        let values: Vec<String> = spec
+            .values
+            .iter()
+            .map(|(_, aggregate)| format!("CAST(COALESCE({}, 0) AS REAL)", aggregate))
+            .collect();
+        let query = format!(
+            r#"
+            SELECT
+                {} as series,
+                (timestamp / ?) * ? as bucket,
+                {}
+            FROM {}
+            WHERE name = ?
+                AND timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
+            GROUP BY series, bucket
+            ORDER BY bucket ASC
+            "#,
+            spec.split.map_or("''", |(expr, _)| expr),
+            values.join(", "),
+            spec.table,
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(interval_ms)
+            .bind(interval_ms)
+            .bind(spec.name)
+            .bind(start_time)
+            .bind(end_time)
+            .bind(agent)
+            .fetch_all(pool)
+            .await?;
+
+        // Ordered by series name, so the response is stable
+        let mut series: BTreeMap<String, Vec<(i64, f64)>> = BTreeMap::new();
+        for row in rows {
+            let key: String = row.try_get("series")?;
+            let bucket: i64 = row.try_get("bucket")?;
+            for (i, (label, _)) in spec.values.iter().enumerate() {
+                let value: f64 = row.try_get(i + 2)?;
+                let name = match spec.split {
+                    None => label.to_string(),
+                    Some((_, display)) if spec.values.len() == 1 => display(&key),
+                    Some((_, display)) => format!("{} {}", display(&key), label),
+                };
+                series.entry(name).or_default().push((bucket, value));
+            }
+        }
+
+        Ok(series
+            .into_iter()
+            .map(|(name, mut points)| {
+                // Models sharing a display name land in the same series
+                points.sort_by_key(|(bucket, _)| *bucket);
+                points.dedup_by(|(bucket, value), (prev_bucket, prev_value)| {
+                    let same = bucket == prev_bucket;
+                    if same {
+                        *prev_value += *value;
+                    }
+                    same
+                });
+                Series { name, points }
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,170 @@
+//! Live spend lookups
+//!
+//! Small, index-backed queries for callers that run on every prompt, like
+//! the Claude Code status line: they must answer in a few milliseconds.
+//! Also the per-day spend the MCP server reports.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use super::time_range::{get_time_range_bounds, TimeRange};
+use crate::calendar::Calendar;
+use crate::error::Result;
+
+/// Spend for the current session and today
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendSnapshot {
+    /// None when no API request of the session has been recorded yet
+    pub session_cost: Option<f64>,
+    pub today_cost: f64,
+    /// Cache reads as a percentage of cache reads plus input tokens, for the
+    /// session if given, else for today. None without any input.
+    pub cache_hit_rate: Option<f64>,
+}
+
+/// API spend on one local calendar day
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailySpend {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub cost: f64,
+    pub requests: i64,
+}
+
+/// Live spend queries
+pub struct SpendAnalytics;
+
+impl SpendAnalytics {
+    /// Spend for `session_id` (if any) and for today across all sessions
+    pub async fn snapshot(pool: &SqlitePool, session_id: Option<&str>) -> Result<SpendSnapshot> {
+        let (today_start, _) = get_time_range_bounds(TimeRange::Today);
+
+        // Uses idx_events_name_timestamp: only today's requests are visited
+        let today: UsageRow = sqlx::query_as(
+            r#"
+            SELECT
+                COUNT(*) as requests,
+                CAST(COALESCE(SUM(cost_usd), 0.0) AS REAL) as cost,
+                COALESCE(SUM(input_tokens), 0) as input_tokens,
+                COALESCE(SUM(cache_read_tokens), 0) as cache_read_tokens
+            FROM events
+            WHERE timestamp >= ?
+                AND name = 'claude_code.api_request'
+            "#,
+        )
+        .bind(today_start)
+        .fetch_one(pool)
+        .await?;
+
+        let session = match session_id {
+            // Uses idx_events_session_name
+            Some(session_id) => Some(
+                sqlx::query_as::<_, UsageRow>(
+                    r#"
+                    SELECT
+                        COUNT(*) as requests,
+                        CAST(COALESCE(SUM(cost_usd), 0.0) AS REAL) as cost,
+                        COALESCE(SUM(input_tokens), 0) as input_tokens,
+                        COALESCE(SUM(cache_read_tokens), 0) as cache_read_tokens
+                    FROM events
+                    WHERE session_id = ?
+                        AND name = 'claude_code.api_request'
+                    "#,
+                )
+                .bind(session_id)
+                .fetch_one(pool)
+                .await?,
+            ),
+            None => None,
+        }
+        .filter(|row| row.requests > 0);
+
+        let cache_source = session.as_ref().unwrap_or(&today);
+        Ok(SpendSnapshot {
+            session_cost: session.as_ref().map(|row| row.cost),
+            today_cost: today.cost,
+            cache_hit_rate: cache_source.cache_hit_rate(),
+        })
+    }
+
+    /// Spend per local day for the last `days` days including today, oldest
+    /// first. Days without requests are included with zero cost.
+    pub async fn daily_spend(
+        pool: &SqlitePool,
+        days: u32,
+        agent: Option<&str>,
+    ) -> Result<Vec<DailySpend>> {
+        Self::daily_spend_in(pool, days, agent, &Calendar::current(), Utc::now()).await
+    }
+
+    pub(super) async fn daily_spend_in(
+        pool: &SqlitePool,
+        days: u32,
+        agent: Option<&str>,
+        calendar: &Calendar,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<DailySpend>> {
+        let today = calendar.local(now).date_naive();
+        let first_day = today - Duration::days(i64::from(days.max(1)) - 1);
+        let start_time = calendar.start_of_day(first_day).timestamp_millis();
+
+        let query = format!(
+            r#"
+            SELECT
+                strftime('%Y-%m-%d', {}, 'unixepoch') as date,
+                CAST(COALESCE(SUM(cost_usd), 0.0) AS REAL) as cost,
+                COUNT(*) as requests
+            FROM events
+            WHERE timestamp >= ?
+                AND agent = COALESCE(?, agent)
+                AND name = 'claude_code.api_request'
+            GROUP BY date
+            "#,
+            calendar.local_seconds_sql("timestamp", start_time, now.timestamp_millis())
+        );
+        let rows: Vec<DailySpendRow> = sqlx::query_as(&query)
+            .bind(start_time)
+            .bind(agent)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(first_day
+            .iter_days()
+            .take_while(|day| *day <= today)
+            .map(|day| {
+                let date = day.format("%Y-%m-%d").to_string();
+                let row = rows.iter().find(|r| r.date == date);
+                DailySpend {
+                    cost: row.map_or(0.0, |r| r.cost),
+                    requests: row.map_or(0, |r| r.requests),
+                    date,
+                }
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DailySpendRow {
+    date: String,
+    cost: f64,
+    requests: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct UsageRow {
+    requests: i64,
+    cost: f64,
+    input_tokens: i64,
+    cache_read_tokens: i64,
+}
+
+impl UsageRow {
+    fn cache_hit_rate(&self) -> Option<f64> {
+        let denominator = self.cache_read_tokens + self.input_tokens;
+        (denominator > 0).then(|| self.cache_read_tokens as f64 / denominator as f64 * 100.0)
+    }
+}
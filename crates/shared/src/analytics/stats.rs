@@ -0,0 +1,545 @@
+//! Summary, model, token and agent statistics
+
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use super::display::{format_agent_display_name, format_model_display_name};
+use super::source_exists;
+use super::time_range::{get_time_range_bounds, TimeRange};
+use crate::error::Result;
+
+/// Summary statistics for a time period
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SummaryStats {
+    pub total_cost: f64,
+    pub total_tokens: i64,
+    pub cache_tokens: i64,
+    /// Cache reads as a percentage of cache reads plus input tokens
+    pub cache_percentage: f64,
+    pub active_time_seconds: i64,
+    pub total_sessions: i64,
+    pub today_sessions: i64,
+    /// Cost change against the same stretch of the previous period, see
+    /// [`TimeRange::previous_period`]
+    pub cost_change_percent: f64,
+    // Metric counters from metrics table
+    pub lines_of_code_added: i64,
+    pub lines_of_code_removed: i64,
+    pub pull_requests: i64,
+    pub commits: i64,
+    pub code_edit_accepts: i64,
+    pub code_edit_rejects: i64,
+}
+
+/// Model usage statistics
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelStats {
+    pub model: String,
+    pub display_name: String,
+    pub cost: f64,
+    pub requests: i64,
+    pub tokens: i64,
+}
+
+/// Token breakdown by model
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenStats {
+    pub model: String,
+    pub display_name: String,
+    pub input: i64,
+    pub output: i64,
+    pub cache_read: i64,
+    pub cache_creation: i64,
+}
+
+/// Usage statistics per coding agent (Claude Code, Codex, ...)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentStats {
+    pub agent: String,
+    pub display_name: String,
+    pub cost: f64,
+    pub requests: i64,
+    pub tokens: i64,
+    pub sessions: i64,
+}
+
+/// Statistics queries
+pub struct StatsAnalytics;
+
+impl StatsAnalytics {
+    /// Get summary statistics for a time range
+    pub async fn get_summary(
+        pool: &SqlitePool,
+        time_range: TimeRange,
+        agent: Option<&str>,
+    ) -> Result<SummaryStats> {
+        let (start_time, end_time) = get_time_range_bounds(time_range);
+        let (today_start, _) = get_time_range_bounds(TimeRange::Today);
+        let has_events = source_exists(pool, "events").await?;
+
+        // Query cost and token totals from events table (not sessions)
+        // to ensure consistency with Cost Trends chart
+        let totals: EventTotalsRow = if has_events {
+            sqlx::query_as(
+                r#"
+            SELECT
+                CAST(COALESCE(SUM(cost_usd), 0.0) AS REAL) as total_cost,
+                COALESCE(SUM(input_tokens + output_tokens), 0) as total_tokens,
+                COALESCE(SUM(input_tokens), 0) as total_input_tokens,
+                COALESCE(SUM(cache_read_tokens), 0) as cache_tokens
+            FROM events
+            WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
+                AND name = 'claude_code.api_request'
+            "#,
+            )
+            .bind(start_time)
+            .bind(end_time)
+            .bind(agent)
+            .fetch_one(pool)
+            .await?
+        } else {
+            EventTotalsRow::default()
+        };
+
+        // Session counts: use lightweight COUNT queries directly on the events table
+        // instead of materializing the expensive sessions VIEW with SELECT *
+        let total_sessions = if has_events {
+            Self::count_sessions(pool, start_time, end_time, agent).await?
+        } else {
+            0
+        };
+        let today_sessions = if has_events {
+            Self::count_sessions(pool, today_start, end_time, agent).await?
+        } else {
+            0
+        };
+
+        // Cache hit rate = cache_read / (cache_read + input)
+        let cache_denominator = totals.cache_tokens + totals.total_input_tokens;
+        let cache_percentage = if cache_denominator > 0 {
+            (totals.cache_tokens as f64 / cache_denominator as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        // Calculate cost change vs previous period
+        let cost_change_percent = if has_events {
+            Self::calculate_cost_change(pool, time_range, agent, totals.total_cost).await?
+        } else {
+            0.0
+        };
+
+        // Get metric counters
+        let metric_counters = Self::get_metric_counters(pool, start_time, end_time, agent).await?;
+
+        // Active time from OTEL metric (claude_code.active_time.total)
+        let active_time_seconds =
+            Self::get_active_time_seconds(pool, start_time, end_time, agent).await?;
+
+        Ok(SummaryStats {
+            total_cost: totals.total_cost,
+            total_tokens: totals.total_tokens,
+            cache_tokens: totals.cache_tokens,
+            cache_percentage,
+            active_time_seconds,
+            total_sessions,
+            today_sessions,
+            cost_change_percent,
+            lines_of_code_added: metric_counters.lines_added,
+            lines_of_code_removed: metric_counters.lines_removed,
+            pull_requests: metric_counters.pull_requests,
+            commits: metric_counters.commits,
+            code_edit_accepts: metric_counters.code_edit_accepts,
+            code_edit_rejects: metric_counters.code_edit_rejects,
+        })
+    }
+
+    /// Get model usage statistics for a time range
+    pub async fn get_model_stats(
+        pool: &SqlitePool,
+        time_range: TimeRange,
+        agent: Option<&str>,
+    ) -> Result<Vec<ModelStats>> {
+        if !source_exists(pool, "events").await? {
+            return Ok(vec![]);
+        }
+
+        let (start_time, end_time) = get_time_range_bounds(time_range);
+
+        // Query model stats from events
+        let rows: Vec<ModelStatsRow> = sqlx::query_as(
+            r#"
+            SELECT
+                COALESCE(model, 'unknown') as model,
+                CAST(COALESCE(SUM(cost_usd), 0.0) AS REAL) as cost,
+                COUNT(*) as requests,
+                COALESCE(SUM(input_tokens + output_tokens), 0) as tokens
+            FROM events
+            WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
+                AND name = 'claude_code.api_request'
+                AND model IS NOT NULL
+            GROUP BY model
+            ORDER BY cost DESC
+            "#,
+        )
+        .bind(start_time)
+        .bind(end_time)
+        .bind(agent)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| ModelStats {
+                display_name: format_model_display_name(&r.model),
+                model: r.model,
+                cost: r.cost,
+                requests: r.requests,
+                tokens: r.tokens,
+            })
+            .collect())
+    }
+
+    /// Get token statistics by model for a time range
+    pub async fn get_token_stats(
+        pool: &SqlitePool,
+        time_range: TimeRange,
+        agent: Option<&str>,
+    ) -> Result<Vec<TokenStats>> {
+        if !source_exists(pool, "events").await? {
+            return Ok(vec![]);
+        }
+
+        let (start_time, end_time) = get_time_range_bounds(time_range);
+
+        let rows: Vec<TokenStatsRow> = sqlx::query_as(
+            r#"
+            SELECT
+                COALESCE(model, 'unknown') as model,
+                COALESCE(SUM(input_tokens), 0) as input,
+                COALESCE(SUM(output_tokens), 0) as output,
+                COALESCE(SUM(cache_read_tokens), 0) as cache_read,
+                COALESCE(SUM(cache_creation_tokens), 0) as cache_creation
+            FROM events
+            WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
+                AND name = 'claude_code.api_request'
+                AND model IS NOT NULL
+            GROUP BY model
+            ORDER BY (input + output) DESC
+            "#,
+        )
+        .bind(start_time)
+        .bind(end_time)
+        .bind(agent)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| TokenStats {
+                display_name: format_model_display_name(&r.model),
+                model: r.model,
+                input: r.input,
+                output: r.output,
+                cache_read: r.cache_read,
+                cache_creation: r.cache_creation,
+            })
+            .collect())
+    }
+
+    /// Get per-agent usage statistics for a time range, for comparing agents
+    pub async fn get_agent_stats(
+        pool: &SqlitePool,
+        time_range: TimeRange,
+    ) -> Result<Vec<AgentStats>> {
+        if !source_exists(pool, "events").await? {
+            return Ok(vec![]);
+        }
+
+        let (start_time, end_time) = get_time_range_bounds(time_range);
+
+        let rows: Vec<AgentStatsRow> = sqlx::query_as(
+            r#"
+            SELECT
+                agent,
+                CAST(COALESCE(SUM(CASE WHEN name = 'claude_code.api_request' THEN cost_usd END), 0.0) AS REAL) as cost,
+                COUNT(CASE WHEN name = 'claude_code.api_request' THEN 1 END) as requests,
+                COALESCE(SUM(CASE WHEN name = 'claude_code.api_request' THEN input_tokens + output_tokens END), 0) as tokens,
+                COUNT(DISTINCT session_id) as sessions
+            FROM events
+            WHERE timestamp >= ? AND timestamp <= ?
+                AND session_id != 'unknown'
+            GROUP BY agent
+            ORDER BY cost DESC
+            "#,
+        )
+        .bind(start_time)
+        .bind(end_time)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| AgentStats {
+                display_name: format_agent_display_name(&r.agent),
+                agent: r.agent,
+                cost: r.cost,
+                requests: r.requests,
+                tokens: r.tokens,
+                sessions: r.sessions,
+            })
+            .collect())
+    }
+
+    /// Get metric counters from the metrics table
+    async fn get_metric_counters(
+        pool: &SqlitePool,
+        start_time: i64,
+        end_time: i64,
+        agent: Option<&str>,
+    ) -> Result<MetricCounters> {
+        if !source_exists(pool, "metrics").await? {
+            return Ok(MetricCounters::default());
+        }
+
+        // Query lines of code
+        let lines_row: Option<LinesOfCodeRow> = sqlx::query_as(
+            r#"
+            SELECT
+                COALESCE(SUM(CASE WHEN metric_type = 'added' THEN value ELSE 0.0 END), 0.0) as added,
+                COALESCE(SUM(CASE WHEN metric_type = 'removed' THEN value ELSE 0.0 END), 0.0) as removed
+            FROM metrics
+            WHERE name = 'claude_code.lines_of_code.count'
+                AND timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
+            "#,
+        )
+        .bind(start_time)
+        .bind(end_time)
+        .bind(agent)
+        .fetch_optional(pool)
+        .await?;
+
+        // Query pull requests
+        let pr_count: Option<CountRow> = sqlx::query_as(
+            r#"
+            SELECT COALESCE(SUM(value), 0.0) as count
+            FROM metrics
+            WHERE name = 'claude_code.pull_request.count'
+                AND timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
+            "#,
+        )
+        .bind(start_time)
+        .bind(end_time)
+        .bind(agent)
+        .fetch_optional(pool)
+        .await?;
+
+        // Query commits
+        let commit_count: Option<CountRow> = sqlx::query_as(
+            r#"
+            SELECT COALESCE(SUM(value), 0.0) as count
+            FROM metrics
+            WHERE name = 'claude_code.commit.count'
+                AND timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
+            "#,
+        )
+        .bind(start_time)
+        .bind(end_time)
+        .bind(agent)
+        .fetch_optional(pool)
+        .await?;
+
+        // Query code edit tool decisions
+        let code_edit_row: Option<DecisionRow> = sqlx::query_as(
+            r#"
+            SELECT
+                COALESCE(SUM(CASE WHEN decision = 'accept' THEN value ELSE 0.0 END), 0.0) as accepts,
+                COALESCE(SUM(CASE WHEN decision = 'reject' THEN value ELSE 0.0 END), 0.0) as rejects
+            FROM metrics
+            WHERE name = 'claude_code.code_edit_tool.decision'
+                AND timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
+            "#,
+        )
+        .bind(start_time)
+        .bind(end_time)
+        .bind(agent)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(MetricCounters {
+            lines_added: lines_row.as_ref().map(|r| r.added as i64).unwrap_or(0),
+            lines_removed: lines_row.as_ref().map(|r| r.removed as i64).unwrap_or(0),
+            pull_requests: pr_count.as_ref().map(|r| r.count as i64).unwrap_or(0),
+            commits: commit_count.as_ref().map(|r| r.count as i64).unwrap_or(0),
+            code_edit_accepts: code_edit_row
+                .as_ref()
+                .map(|r| r.accepts as i64)
+                .unwrap_or(0),
+            code_edit_rejects: code_edit_row
+                .as_ref()
+                .map(|r| r.rejects as i64)
+                .unwrap_or(0),
+        })
+    }
+
+    /// Get active time in seconds from the `claude_code.active_time.total` OTEL metric.
+    async fn get_active_time_seconds(
+        pool: &SqlitePool,
+        start_time: i64,
+        end_time: i64,
+        agent: Option<&str>,
+    ) -> Result<i64> {
+        let (seconds,): (f64,) = sqlx::query_as(
+            r#"
+            SELECT COALESCE(SUM(value), 0.0)
+            FROM metrics
+            WHERE name = 'claude_code.active_time.total'
+              AND timestamp >= ? AND timestamp <= ?
+              AND agent = COALESCE(?, agent)
+            "#,
+        )
+        .bind(start_time)
+        .bind(end_time)
+        .bind(agent)
+        .fetch_one(pool)
+        .await?;
+        Ok(seconds as i64)
+    }
+
+    /// Count distinct sessions within a time range by querying events directly.
+    /// This avoids materializing the expensive sessions VIEW.
+    async fn count_sessions(
+        pool: &SqlitePool,
+        start_time: i64,
+        end_time: i64,
+        agent: Option<&str>,
+    ) -> Result<i64> {
+        let (count,): (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(DISTINCT session_id) as count
+            FROM events
+            WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
+                AND session_id != 'unknown'
+            "#,
+        )
+        .bind(start_time)
+        .bind(end_time)
+        .bind(agent)
+        .fetch_one(pool)
+        .await?;
+        Ok(count)
+    }
+
+    /// Calculate cost change percentage vs the previous period
+    async fn calculate_cost_change(
+        pool: &SqlitePool,
+        time_range: TimeRange,
+        agent: Option<&str>,
+        current_cost: f64,
+    ) -> Result<f64> {
+        // Nothing precedes all-time
+        let Some(previous) = time_range.previous_period() else {
+            return Ok(0.0);
+        };
+        let (prev_start, prev_end) = get_time_range_bounds(previous);
+
+        let row: CostRow = sqlx::query_as(
+            r#"
+            SELECT CAST(COALESCE(SUM(cost_usd), 0.0) AS REAL) as cost
+            FROM events
+            WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
+                AND name = 'claude_code.api_request'
+            "#,
+        )
+        .bind(prev_start)
+        .bind(prev_end)
+        .bind(agent)
+        .fetch_one(pool)
+        .await?;
+
+        if row.cost > 0.0 {
+            Ok(((current_cost - row.cost) / row.cost) * 100.0)
+        } else {
+            Ok(0.0)
+        }
+    }
+}
+
+#[derive(Debug, Default, sqlx::FromRow)]
+struct EventTotalsRow {
+    total_cost: f64,
+    total_tokens: i64,
+    total_input_tokens: i64,
+    cache_tokens: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct CostRow {
+    cost: f64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ModelStatsRow {
+    model: String,
+    cost: f64,
+    requests: i64,
+    tokens: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct AgentStatsRow {
+    agent: String,
+    cost: f64,
+    requests: i64,
+    tokens: i64,
+    sessions: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct TokenStatsRow {
+    model: String,
+    input: i64,
+    output: i64,
+    cache_read: i64,
+    cache_creation: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct LinesOfCodeRow {
+    added: f64,
+    removed: f64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct CountRow {
+    count: f64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DecisionRow {
+    accepts: f64,
+    rejects: f64,
+}
+
+#[derive(Debug, Default)]
+struct MetricCounters {
+    lines_added: i64,
+    lines_removed: i64,
+    pull_requests: i64,
+    commits: i64,
+    code_edit_accepts: i64,
+    code_edit_rejects: i64,
+}
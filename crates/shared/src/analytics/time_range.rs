@@ -0,0 +1,452 @@
+//! Time range utilities
+//!
+//! Time ranges queries cover, their bounds, and the date labels charts
+//! bucket them into. Days, weeks and hours follow the configured
+//! [`Calendar`].
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use crate::calendar::Calendar;
+use crate::error::Result;
+
+/// Time range filter for queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeRange {
+    /// Since local midnight
+    Today,
+    /// Since the start of the week
+    Week,
+    /// Since the 1st of the month
+    Month,
+    /// Rolling last 24 hours
+    #[serde(rename = "last24h")]
+    Last24Hours,
+    /// Rolling last 7 days
+    #[serde(rename = "last7d")]
+    Last7Days,
+    /// Rolling last 30 days
+    #[serde(rename = "last30d")]
+    Last30Days,
+    /// Rolling last 90 days
+    #[serde(rename = "last90d")]
+    Last90Days,
+    /// Since January 1st
+    Year,
+    /// Everything recorded
+    All,
+    /// Between two Unix millisecond timestamps
+    Custom { start: i64, end: i64 },
+}
+
+/// Size of the buckets a time range is charted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    Hour,
+    Day,
+    /// Weeks starting on the calendar's configured first day
+    Week,
+    Month,
+}
+
+const HOUR_MS: i64 = 60 * 60 * 1000;
+const DAY_MS: i64 = 24 * HOUR_MS;
+
+/// Longest span bucketed by hour
+const HOURLY_MAX_SPAN_MS: i64 = 48 * HOUR_MS;
+/// Longest span bucketed by day, so a 90-day window is still daily
+const DAILY_MAX_SPAN_MS: i64 = 92 * DAY_MS;
+/// Longest span bucketed by week; anything longer is monthly
+const WEEKLY_MAX_SPAN_MS: i64 = 2 * 366 * DAY_MS;
+
+impl TimeRange {
+    /// Bucket size for trends over this range, picked from its span
+    pub fn granularity(self) -> Granularity {
+        self.granularity_in(&Calendar::current(), Utc::now())
+    }
+
+    fn granularity_in(self, calendar: &Calendar, now: DateTime<Utc>) -> Granularity {
+        if self == TimeRange::Today {
+            return Granularity::Hour;
+        }
+        let (start, end) = time_range_bounds(self, calendar, now);
+        match end - start {
+            span if span <= HOURLY_MAX_SPAN_MS => Granularity::Hour,
+            span if span <= DAILY_MAX_SPAN_MS => Granularity::Day,
+            span if span <= WEEKLY_MAX_SPAN_MS => Granularity::Week,
+            _ => Granularity::Month,
+        }
+    }
+
+    /// Pin `All` to the first recorded event, so its labels and buckets
+    /// start where the data does. Other ranges are returned unchanged.
+    pub async fn resolve(self, pool: &SqlitePool) -> Result<TimeRange> {
+        if self != TimeRange::All {
+            return Ok(self);
+        }
+        let end = Utc::now().timestamp_millis();
+        let first: Option<i64> = sqlx::query_scalar("SELECT MIN(timestamp) FROM events")
+            .fetch_one(pool)
+            .await?;
+        Ok(TimeRange::Custom {
+            start: first.unwrap_or(end).min(end),
+            end,
+        })
+    }
+
+    /// The period before this one, as long as this one has run so far:
+    /// yesterday until the current time of day for `Today`, the previous
+    /// week, month or year up to the same point for `Week`, `Month` and
+    /// `Year`, and the preceding window of equal length otherwise. `All`
+    /// has no previous period.
+    pub fn previous_period(self) -> Option<TimeRange> {
+        self.previous_period_in(&Calendar::current(), Utc::now())
+    }
+
+    fn previous_period_in(self, calendar: &Calendar, now: DateTime<Utc>) -> Option<TimeRange> {
+        let (start, end) = time_range_bounds(self, calendar, now);
+        let today = calendar.local(now).date_naive();
+        let previous_start = match self {
+            TimeRange::All => return None,
+            TimeRange::Today => today - Duration::days(1),
+            TimeRange::Week => calendar.start_of_week(today) - Duration::days(7),
+            TimeRange::Month => today
+                .with_day(1)
+                .and_then(|first| first.checked_sub_months(chrono::Months::new(1)))?,
+            TimeRange::Year => NaiveDate::from_ymd_opt(today.year() - 1, 1, 1)?,
+            _ => {
+                return Some(TimeRange::Custom {
+                    start: start - (end - start) - 1,
+                    end: start - 1,
+                })
+            }
+        };
+        let previous_start = calendar.start_of_day(previous_start).timestamp_millis();
+        Some(TimeRange::Custom {
+            start: previous_start,
+            end: (previous_start + end - start).min(start - 1),
+        })
+    }
+}
+
+/// Get time range start and end timestamps (in milliseconds)
+pub fn get_time_range_bounds(time_range: TimeRange) -> (i64, i64) {
+    time_range_bounds(time_range, &Calendar::current(), Utc::now())
+}
+
+pub(super) fn time_range_bounds(
+    time_range: TimeRange,
+    calendar: &Calendar,
+    now: DateTime<Utc>,
+) -> (i64, i64) {
+    let end_time = now.timestamp_millis();
+    let today = calendar.local(now).date_naive();
+
+    let start = match time_range {
+        TimeRange::Custom { start, end } => return (start, end),
+        TimeRange::All => return (0, end_time),
+        TimeRange::Last24Hours => return (end_time - DAY_MS, end_time),
+        TimeRange::Last7Days => return (end_time - 7 * DAY_MS, end_time),
+        TimeRange::Last30Days => return (end_time - 30 * DAY_MS, end_time),
+        TimeRange::Last90Days => return (end_time - 90 * DAY_MS, end_time),
+        TimeRange::Today => today,
+        TimeRange::Week => calendar.start_of_week(today),
+        TimeRange::Month => today.with_day(1).unwrap(),
+        TimeRange::Year => today.with_ordinal(1).unwrap(),
+    };
+
+    (calendar.start_of_day(start).timestamp_millis(), end_time)
+}
+
+/// Generate all date/hour labels for the given time range, bucketed by its
+/// [`TimeRange::granularity`]:
+///
+/// - `Hour`: "HH:00" for `Today`, "YYYY-MM-DD HH:00" otherwise. The hour
+///   repeated when clocks fall back has a single label.
+/// - `Day`: "YYYY-MM-DD"
+/// - `Week`: "YYYY-MM-DD" of each week's first day
+/// - `Month`: "YYYY-MM"
+///
+/// `All` should be [`TimeRange::resolve`]d first; unresolved it starts at
+/// the Unix epoch.
+pub fn generate_date_labels(time_range: TimeRange) -> Vec<String> {
+    date_labels(time_range, &Calendar::current(), Utc::now())
+}
+
+pub(super) fn date_labels(
+    time_range: TimeRange,
+    calendar: &Calendar,
+    now: DateTime<Utc>,
+) -> Vec<String> {
+    let (start, end) = time_range_bounds(time_range, calendar, now);
+    let (Some(start), Some(end)) = (calendar.local_millis(start), calendar.local_millis(end))
+    else {
+        return Vec::new();
+    };
+    let (start_date, end_date) = (start.date_naive(), end.date_naive());
+
+    match time_range.granularity_in(calendar, now) {
+        Granularity::Hour => {
+            let format = if time_range == TimeRange::Today {
+                "%H:00"
+            } else {
+                "%Y-%m-%d %H:00"
+            };
+            let mut labels: Vec<String> = Vec::new();
+            let mut current = start
+                - Duration::minutes(i64::from(start.minute()))
+                - Duration::seconds(i64::from(start.second()))
+                - Duration::nanoseconds(i64::from(start.nanosecond()));
+            while current <= end {
+                let label = current.format(format).to_string();
+                if labels.last() != Some(&label) {
+                    labels.push(label);
+                }
+                current += Duration::hours(1);
+            }
+            labels
+        }
+        Granularity::Day => generate_daily_labels(start_date, end_date, 1),
+        Granularity::Week => generate_daily_labels(calendar.start_of_week(start_date), end_date, 7),
+        Granularity::Month => {
+            let mut labels = Vec::new();
+            let mut current = start_date.with_day(1).unwrap();
+            while current <= end_date {
+                labels.push(current.format("%Y-%m").to_string());
+                current = current.checked_add_months(chrono::Months::new(1)).unwrap();
+            }
+            labels
+        }
+    }
+}
+
+/// SQL expressions bucketing `column` (Unix milliseconds) for a time range:
+/// the label format matching [`generate_date_labels`], and the grouping key
+pub fn bucket_sql(time_range: TimeRange, column: &str) -> (String, String) {
+    let calendar = Calendar::current();
+    let now = Utc::now();
+    let granularity = time_range.granularity_in(&calendar, now);
+    bucket_sql_in(time_range, granularity, column, &calendar, now)
+}
+
+/// [`bucket_sql`] with buckets of `granularity` instead of the range's own
+pub(super) fn bucket_sql_in(
+    time_range: TimeRange,
+    granularity: Granularity,
+    column: &str,
+    calendar: &Calendar,
+    now: DateTime<Utc>,
+) -> (String, String) {
+    let (start, end) = time_range_bounds(time_range, calendar, now);
+    let local = calendar.local_seconds_sql(column, start, end);
+    let (label, group, modifiers) = match granularity {
+        Granularity::Hour if time_range == TimeRange::Today => {
+            ("%H:00", "%Y-%m-%d %H", String::new())
+        }
+        Granularity::Hour => ("%Y-%m-%d %H:00", "%Y-%m-%d %H", String::new()),
+        Granularity::Day => ("%Y-%m-%d", "%Y-%m-%d", String::new()),
+        // Back to the first day of the day's week
+        Granularity::Week => (
+            "%Y-%m-%d",
+            "%Y-%m-%d",
+            format!(", {}, '-6 days'", calendar.week_end_modifier()),
+        ),
+        Granularity::Month => ("%Y-%m", "%Y-%m", String::new()),
+    };
+    let expr = |format: &str| {
+        format!(
+            "strftime('{}', {}, 'unixepoch'{})",
+            format, local, modifiers
+        )
+    };
+    (expr(label), expr(group))
+}
+
+/// Labels every `step_days` days from `start` through `end`
+fn generate_daily_labels(start: NaiveDate, end: NaiveDate, step_days: i64) -> Vec<String> {
+    let mut dates = Vec::new();
+    let mut current = start;
+    while current <= end {
+        dates.push(current.format("%Y-%m-%d").to_string());
+        current += Duration::days(step_days);
+    }
+    dates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Weekday};
+    use sqlx::SqlitePool;
+
+    fn new_york() -> Calendar {
+        Calendar::parse(Some("America/New_York"), Some("monday")).unwrap()
+    }
+
+    fn at(calendar: &Calendar, (y, m, d): (i32, u32, u32), hour: u32) -> i64 {
+        calendar
+            .timezone
+            .with_ymd_and_hms(y, m, d, hour, 0, 0)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    fn custom(calendar: &Calendar, start: (i32, u32, u32), end: (i32, u32, u32)) -> TimeRange {
+        TimeRange::Custom {
+            start: at(calendar, start, 12),
+            end: at(calendar, end, 12),
+        }
+    }
+
+    #[test]
+    fn test_granularity_follows_span() {
+        let calendar = new_york();
+        let now = Utc::now();
+        let granularity = |range: TimeRange| range.granularity_in(&calendar, now);
+        assert_eq!(granularity(TimeRange::Today), Granularity::Hour);
+        assert_eq!(granularity(TimeRange::Last24Hours), Granularity::Hour);
+        assert_eq!(granularity(TimeRange::Last90Days), Granularity::Day);
+        assert_eq!(
+            granularity(custom(&calendar, (2026, 1, 1), (2026, 1, 2))),
+            Granularity::Hour
+        );
+        assert_eq!(
+            granularity(custom(&calendar, (2026, 1, 1), (2026, 6, 1))),
+            Granularity::Week
+        );
+        assert_eq!(
+            granularity(custom(&calendar, (2023, 11, 15), (2026, 2, 1))),
+            Granularity::Month
+        );
+    }
+
+    #[test]
+    fn test_weekly_and_monthly_labels() {
+        let mut calendar = new_york();
+        let now = Utc::now();
+        // Wednesday 2026-01-07: the first bucket is that week's Monday
+        let range = custom(&calendar, (2026, 1, 7), (2026, 6, 1));
+        let weeks = date_labels(range, &calendar, now);
+        assert_eq!(weeks.first().map(String::as_str), Some("2026-01-05"));
+        assert_eq!(weeks.last().map(String::as_str), Some("2026-06-01"));
+        assert_eq!(weeks.len(), 22);
+
+        calendar.week_start = Weekday::Sun;
+        let weeks = date_labels(range, &calendar, now);
+        assert_eq!(weeks.first().map(String::as_str), Some("2026-01-04"));
+        assert_eq!(weeks.last().map(String::as_str), Some("2026-05-31"));
+
+        let range = custom(&calendar, (2023, 11, 15), (2026, 2, 1));
+        let months = date_labels(range, &calendar, now);
+        assert_eq!(months.first().map(String::as_str), Some("2023-11"));
+        assert_eq!(months.last().map(String::as_str), Some("2026-02"));
+        assert_eq!(months.len(), 28);
+    }
+
+    #[test]
+    fn test_bounds_in_calendar_timezone() {
+        let calendar = new_york();
+        // 03:00 UTC on Monday 2026-03-09 is still Sunday evening in New York
+        let now = Utc.with_ymd_and_hms(2026, 3, 9, 3, 0, 0).unwrap();
+        let (start, _) = time_range_bounds(TimeRange::Today, &calendar, now);
+        assert_eq!(start, at(&calendar, (2026, 3, 8), 0));
+        let (start, _) = time_range_bounds(TimeRange::Week, &calendar, now);
+        assert_eq!(start, at(&calendar, (2026, 3, 2), 0));
+    }
+
+    #[test]
+    fn test_today_labels_across_dst() {
+        let calendar = new_york();
+        // Spring forward: 02:00 does not exist
+        let now = Utc.with_ymd_and_hms(2026, 3, 8, 9, 30, 0).unwrap();
+        let labels = date_labels(TimeRange::Today, &calendar, now);
+        assert_eq!(labels, ["00:00", "01:00", "03:00", "04:00", "05:00"]);
+
+        // Fall back: 01:00 happens twice but is one bucket
+        let now = Utc.with_ymd_and_hms(2026, 11, 1, 9, 30, 0).unwrap();
+        let labels = date_labels(TimeRange::Today, &calendar, now);
+        assert_eq!(labels, ["00:00", "01:00", "02:00", "03:00", "04:00"]);
+    }
+
+    #[tokio::test]
+    async fn test_bucket_sql_matches_labels_across_dst() {
+        let calendar = new_york();
+        let range = TimeRange::Custom {
+            start: at(&calendar, (2026, 10, 31), 12),
+            end: at(&calendar, (2026, 11, 1), 12),
+        };
+        let now = Utc::now();
+        let labels = date_labels(range, &calendar, now);
+        assert_eq!(labels.len(), 25);
+
+        // One row per absolute hour of the range, 26 instants across 25 labels
+        let granularity = range.granularity_in(&calendar, now);
+        let (label_expr, _) = bucket_sql_in(range, granularity, "ts", &calendar, now);
+        let sql = format!(
+            r#"
+            WITH RECURSIVE hours(ts) AS (
+                SELECT ? UNION ALL SELECT ts + 3600000 FROM hours WHERE ts + 3600000 <= ?
+            )
+            SELECT DISTINCT {} FROM hours
+            "#,
+            label_expr
+        );
+        let (start, end) = time_range_bounds(range, &calendar, now);
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        let buckets: Vec<String> = sqlx::query_scalar(&sql)
+            .bind(start)
+            .bind(end)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(buckets, labels);
+    }
+
+    #[test]
+    fn test_previous_period() {
+        let calendar = new_york();
+        // Tuesday 2026-03-31, 10:00 local
+        let now = Utc.with_ymd_and_hms(2026, 3, 31, 14, 0, 0).unwrap();
+        let previous = |range: TimeRange| range.previous_period_in(&calendar, now);
+        let custom = |start: i64, end: i64| Some(TimeRange::Custom { start, end });
+
+        assert_eq!(
+            previous(TimeRange::Today),
+            custom(
+                at(&calendar, (2026, 3, 30), 0),
+                at(&calendar, (2026, 3, 30), 10)
+            )
+        );
+        assert_eq!(
+            previous(TimeRange::Week),
+            custom(
+                at(&calendar, (2026, 3, 23), 0),
+                at(&calendar, (2026, 3, 24), 10)
+            )
+        );
+        // February is shorter: the previous month ends at its end
+        assert_eq!(
+            previous(TimeRange::Month),
+            custom(
+                at(&calendar, (2026, 2, 1), 0),
+                at(&calendar, (2026, 3, 1), 0) - 1
+            )
+        );
+        let end = now.timestamp_millis();
+        assert_eq!(
+            previous(TimeRange::Last24Hours),
+            custom(end - 2 * DAY_MS - 1, end - DAY_MS - 1)
+        );
+        assert_eq!(previous(TimeRange::All), None);
+    }
+
+    #[test]
+    fn test_preset_names() {
+        let range: TimeRange = serde_json::from_str("\"last7d\"").unwrap();
+        assert_eq!(range, TimeRange::Last7Days);
+        assert_eq!(serde_json::to_string(&TimeRange::All).unwrap(), "\"all\"");
+    }
+}
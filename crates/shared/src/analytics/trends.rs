@@ -0,0 +1,261 @@
+//! Usage trends and time series
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use super::time_range::{bucket_sql, generate_date_labels, get_time_range_bounds, TimeRange};
+use crate::error::Result;
+
+/// Usage trend data point (for charts)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageTrend {
+    pub date: String,
+    pub cost: f64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_creation_tokens: i64,
+}
+
+/// Cost trend data point grouped by model
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostByModelTrend {
+    pub date: String,
+    pub model: String,
+    pub cost: f64,
+}
+
+/// Cost efficiency trend data point (cost per session over time)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostEfficiencyTrend {
+    pub date: String,
+    pub cost_per_session: f64,
+    pub session_count: i64,
+}
+
+/// Trend queries. Every label of the range is present in the result, with
+/// zeros where there was no usage.
+pub struct TrendsAnalytics;
+
+impl TrendsAnalytics {
+    /// Get usage trends for a time range
+    pub async fn get_usage_trends(
+        pool: &SqlitePool,
+        time_range: TimeRange,
+        agent: Option<&str>,
+    ) -> Result<Vec<UsageTrend>> {
+        let time_range = time_range.resolve(pool).await?;
+        let (start_time, end_time) = get_time_range_bounds(time_range);
+
+        // Format string and grouping depends on time range
+        let (label_expr, group_expr) = bucket_sql(time_range, "timestamp");
+
+        let query = format!(
+            r#"
+            SELECT
+                {} as date,
+                COALESCE(SUM(cost_usd), 0) as cost,
+                COALESCE(SUM(input_tokens), 0) as input_tokens,
+                COALESCE(SUM(output_tokens), 0) as output_tokens,
+                COALESCE(SUM(cache_read_tokens), 0) as cache_read_tokens,
+                COALESCE(SUM(cache_creation_tokens), 0) as cache_creation_tokens
+            FROM events
+            WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
+                AND name = 'claude_code.api_request'
+            GROUP BY {}
+            ORDER BY MIN(timestamp) ASC
+            "#,
+            label_expr, group_expr
+        );
+
+        let rows: Vec<UsageTrendRow> = sqlx::query_as(&query)
+            .bind(start_time)
+            .bind(end_time)
+            .bind(agent)
+            .fetch_all(pool)
+            .await?;
+
+        let mut trend_map: HashMap<String, UsageTrend> = HashMap::new();
+        for r in rows {
+            trend_map.insert(
+                r.date.clone(),
+                UsageTrend {
+                    date: r.date,
+                    cost: r.cost,
+                    input_tokens: r.input_tokens,
+                    output_tokens: r.output_tokens,
+                    cache_read_tokens: r.cache_read_tokens,
+                    cache_creation_tokens: r.cache_creation_tokens,
+                },
+            );
+        }
+
+        let all_labels = generate_date_labels(time_range);
+        Ok(all_labels
+            .into_iter()
+            .map(|label| {
+                trend_map.remove(&label).unwrap_or(UsageTrend {
+                    date: label,
+                    cost: 0.0,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    cache_read_tokens: 0,
+                    cache_creation_tokens: 0,
+                })
+            })
+            .collect())
+    }
+
+    /// Get cost trends grouped by date and model
+    pub async fn get_cost_by_model_trends(
+        pool: &SqlitePool,
+        time_range: TimeRange,
+        agent: Option<&str>,
+    ) -> Result<Vec<CostByModelTrend>> {
+        let time_range = time_range.resolve(pool).await?;
+        let (start_time, end_time) = get_time_range_bounds(time_range);
+        let (label_expr, group_expr) = bucket_sql(time_range, "timestamp");
+
+        let query = format!(
+            r#"
+            SELECT
+                {} as date,
+                COALESCE(model, 'unknown') as model,
+                COALESCE(SUM(cost_usd), 0) as cost
+            FROM events
+            WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
+                AND name = 'claude_code.api_request'
+            GROUP BY {}, model
+            ORDER BY MIN(timestamp) ASC, model ASC
+            "#,
+            label_expr, group_expr
+        );
+
+        let rows: Vec<CostByModelRow> = sqlx::query_as(&query)
+            .bind(start_time)
+            .bind(end_time)
+            .bind(agent)
+            .fetch_all(pool)
+            .await?;
+
+        let models: HashSet<String> = rows.iter().map(|r| r.model.clone()).collect();
+        let mut models: Vec<String> = models.into_iter().collect();
+        models.sort();
+        let mut cost_map: HashMap<(String, String), f64> = HashMap::new();
+        for r in rows {
+            cost_map.insert((r.date, r.model), r.cost);
+        }
+
+        let all_labels = generate_date_labels(time_range);
+        let mut result = Vec::new();
+        for label in &all_labels {
+            for model in &models {
+                let cost = cost_map
+                    .get(&(label.clone(), model.clone()))
+                    .copied()
+                    .unwrap_or(0.0);
+                result.push(CostByModelTrend {
+                    date: label.clone(),
+                    model: model.clone(),
+                    cost,
+                });
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Get cost efficiency trend (cost per session over time)
+    pub async fn get_cost_efficiency_trend(
+        pool: &SqlitePool,
+        time_range: TimeRange,
+        agent: Option<&str>,
+    ) -> Result<Vec<CostEfficiencyTrend>> {
+        let time_range = time_range.resolve(pool).await?;
+        let (start_time, end_time) = get_time_range_bounds(time_range);
+        let (label_expr, group_expr) = bucket_sql(time_range, "start_time");
+
+        let query = format!(
+            r#"
+            SELECT
+                {} as date,
+                COALESCE(SUM(total_cost_usd), 0) as total_cost,
+                COUNT(*) as session_count
+            FROM sessions
+            WHERE start_time >= ? AND start_time <= ?
+                AND agent = COALESCE(?, agent)
+                AND id != 'unknown'
+            GROUP BY {}
+            ORDER BY MIN(start_time) ASC
+            "#,
+            label_expr, group_expr
+        );
+
+        let rows: Vec<CostEfficiencyRow> = sqlx::query_as(&query)
+            .bind(start_time)
+            .bind(end_time)
+            .bind(agent)
+            .fetch_all(pool)
+            .await?;
+
+        let mut trend_map: HashMap<String, CostEfficiencyTrend> = HashMap::new();
+        for r in rows {
+            let cost_per_session = if r.session_count > 0 {
+                r.total_cost / r.session_count as f64
+            } else {
+                0.0
+            };
+            trend_map.insert(
+                r.date.clone(),
+                CostEfficiencyTrend {
+                    date: r.date,
+                    cost_per_session,
+                    session_count: r.session_count,
+                },
+            );
+        }
+
+        let all_labels = generate_date_labels(time_range);
+        Ok(all_labels
+            .into_iter()
+            .map(|label| {
+                trend_map.remove(&label).unwrap_or(CostEfficiencyTrend {
+                    date: label,
+                    cost_per_session: 0.0,
+                    session_count: 0,
+                })
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct CostByModelRow {
+    date: String,
+    model: String,
+    cost: f64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct UsageTrendRow {
+    date: String,
+    cost: f64,
+    input_tokens: i64,
+    output_tokens: i64,
+    cache_read_tokens: i64,
+    cache_creation_tokens: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct CostEfficiencyRow {
+    date: String,
+    total_cost: f64,
+    session_count: i64,
+}
@@ -0,0 +1,291 @@
+//! Calendar settings
+//!
+//! The timezone and first day of the week that days, weeks and hours are
+//! bucketed in: the `timezone` (IANA name) and `week_start` keys of
+//! `~/.lumo/config.json`, set from the app. Unset, the system timezone and
+//! Monday are used.
+//!
+//! Buckets are computed here rather than with SQLite's `'localtime'`, so
+//! they follow the setting instead of the machine's zone, and DST
+//! transitions inside a range are applied where they happen.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Offset, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use std::path::PathBuf;
+use tracing::warn;
+
+use crate::error::{Error, Result};
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Timezone and week start used for bucketing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calendar {
+    pub timezone: Tz,
+    pub week_start: Weekday,
+}
+
+/// The calendar keys of `~/.lumo/config.json`; the app owns the rest
+#[derive(Debug, Default, Deserialize)]
+struct CalendarConfig {
+    timezone: Option<String>,
+    week_start: Option<String>,
+}
+
+impl Default for Calendar {
+    /// System timezone, weeks starting on Monday
+    fn default() -> Self {
+        Self {
+            timezone: system_timezone(),
+            week_start: Weekday::Mon,
+        }
+    }
+}
+
+impl Calendar {
+    /// Calendar from setting values; unset values fall back to the defaults
+    pub fn parse(timezone: Option<&str>, week_start: Option<&str>) -> Result<Self> {
+        let default = Self::default();
+        let timezone = match timezone {
+            Some(name) => name
+                .parse::<Tz>()
+                .map_err(|_| Error::InvalidData(format!("Unknown timezone: {}", name)))?,
+            None => default.timezone,
+        };
+        let week_start = match week_start {
+            Some(day) => day
+                .parse::<Weekday>()
+                .map_err(|_| Error::InvalidData(format!("Unknown weekday: {}", day)))?,
+            None => default.week_start,
+        };
+        Ok(Self {
+            timezone,
+            week_start,
+        })
+    }
+
+    /// The configured calendar. An unreadable or invalid setting falls back
+    /// to the default rather than failing every query.
+    pub fn current() -> Self {
+        let config = match read_config() {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("Failed to read calendar settings: {}", e);
+                CalendarConfig::default()
+            }
+        };
+        Self::parse(config.timezone.as_deref(), config.week_start.as_deref()).unwrap_or_else(|e| {
+            warn!("Ignoring calendar settings: {}", e);
+            Self::default()
+        })
+    }
+
+    /// `instant` in this calendar's timezone
+    pub fn local(&self, instant: DateTime<Utc>) -> DateTime<Tz> {
+        instant.with_timezone(&self.timezone)
+    }
+
+    /// Unix milliseconds in this calendar's timezone
+    pub fn local_millis(&self, timestamp_ms: i64) -> Option<DateTime<Tz>> {
+        DateTime::from_timestamp_millis(timestamp_ms).map(|t| self.local(t))
+    }
+
+    /// First instant of `date`. Where midnight falls into a DST gap, the
+    /// day starts when the clocks jump forward.
+    pub fn start_of_day(&self, date: NaiveDate) -> DateTime<Tz> {
+        let midnight = date.and_time(NaiveTime::MIN);
+        (0..=8)
+            .find_map(|quarter| {
+                self.timezone
+                    .from_local_datetime(&(midnight + Duration::minutes(15 * quarter)))
+                    .earliest()
+            })
+            .unwrap_or_else(|| self.timezone.from_utc_datetime(&midnight))
+    }
+
+    /// First day of the week containing `date`
+    pub fn start_of_week(&self, date: NaiveDate) -> NaiveDate {
+        let days = (date.weekday().num_days_from_monday() + 7
+            - self.week_start.num_days_from_monday())
+            % 7;
+        date - Duration::days(i64::from(days))
+    }
+
+    /// SQL expression turning `column` (Unix milliseconds) into wall-clock
+    /// Unix seconds in this timezone, for `strftime(format, <expr>,
+    /// 'unixepoch')`. Offsets are exact for rows between `start_ms` and
+    /// `end_ms`; rows outside use the nearest edge's offset.
+    pub fn local_seconds_sql(&self, column: &str, start_ms: i64, end_ms: i64) -> String {
+        let spans = self.offset_spans(start_ms, end_ms);
+        let last = spans[spans.len() - 1].1;
+        if spans.len() == 1 {
+            return format!("({} / 1000 + {})", column, last);
+        }
+        let cases: String = spans
+            .windows(2)
+            .map(|pair| format!(" WHEN {} < {} THEN {}", column, pair[1].0, pair[0].1))
+            .collect();
+        format!("({} / 1000 + CASE{} ELSE {} END)", column, cases, last)
+    }
+
+    /// `'weekday N'` SQLite modifier moving a date to the last day of its
+    /// week; with `'-6 days'` after it, to the first
+    pub fn week_end_modifier(&self) -> String {
+        format!(
+            "'weekday {}'",
+            self.week_start.pred().num_days_from_sunday()
+        )
+    }
+
+    /// UTC offset in seconds at `timestamp_ms`
+    fn offset_at(&self, timestamp_ms: i64) -> i32 {
+        DateTime::from_timestamp_millis(timestamp_ms).map_or(0, |t| {
+            self.timezone
+                .offset_from_utc_datetime(&t.naive_utc())
+                .fix()
+                .local_minus_utc()
+        })
+    }
+
+    /// `(from_ms, offset)` pairs covering `start_ms..=end_ms`, each offset
+    /// applying until the next pair's `from_ms`
+    fn offset_spans(&self, start_ms: i64, end_ms: i64) -> Vec<(i64, i32)> {
+        let mut spans = vec![(start_ms, self.offset_at(start_ms))];
+        let mut probe = start_ms;
+        while probe < end_ms {
+            let next = (probe + DAY_MS).min(end_ms);
+            let current = spans[spans.len() - 1].1;
+            if self.offset_at(next) == current {
+                probe = next;
+                continue;
+            }
+            // Narrow the transition down to the millisecond
+            let (mut before, mut after) = (probe, next);
+            while after - before > 1 {
+                let mid = before + (after - before) / 2;
+                if self.offset_at(mid) == current {
+                    before = mid;
+                } else {
+                    after = mid;
+                }
+            }
+            spans.push((after, self.offset_at(after)));
+            probe = after;
+        }
+        spans
+    }
+}
+
+/// Path of the app config file holding the calendar settings
+fn config_path() -> Result<PathBuf> {
+    let home = dirs::home_dir()
+        .ok_or_else(|| Error::InvalidData("Could not determine home directory".to_string()))?;
+    Ok(home.join(".lumo").join("config.json"))
+}
+
+fn read_config() -> Result<CalendarConfig> {
+    let path = config_path()?;
+    if !path.exists() {
+        return Ok(CalendarConfig::default());
+    }
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+/// The system's IANA timezone, UTC if it cannot be determined
+fn system_timezone() -> Tz {
+    iana_time_zone::get_timezone()
+        .ok()
+        .and_then(|name| name.parse().ok())
+        .unwrap_or(Tz::UTC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::SqlitePool;
+
+    fn new_york() -> Calendar {
+        Calendar::parse(Some("America/New_York"), Some("monday")).unwrap()
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_day_length_across_dst() {
+        let calendar = new_york();
+        let hours = |d: NaiveDate| {
+            let start = calendar.start_of_day(d);
+            let end = calendar.start_of_day(d + Duration::days(1));
+            (end - start).num_hours()
+        };
+        // Clocks spring forward on 2026-03-08 and fall back on 2026-11-01
+        assert_eq!(hours(date(2026, 3, 8)), 23);
+        assert_eq!(hours(date(2026, 11, 1)), 25);
+        assert_eq!(hours(date(2026, 11, 2)), 24);
+    }
+
+    #[test]
+    fn test_midnight_in_dst_gap() {
+        // São Paulo skipped from 00:00 to 01:00 on 2018-11-04
+        let calendar = Calendar::parse(Some("America/Sao_Paulo"), None).unwrap();
+        let start = calendar.start_of_day(date(2018, 11, 4));
+        assert_eq!(start.format("%H:%M").to_string(), "01:00");
+        assert_eq!(start.timestamp(), 1_541_300_400);
+    }
+
+    #[test]
+    fn test_start_of_week() {
+        let mut calendar = new_york();
+        // Wednesday
+        assert_eq!(calendar.start_of_week(date(2026, 1, 7)), date(2026, 1, 5));
+        calendar.week_start = Weekday::Sun;
+        assert_eq!(calendar.start_of_week(date(2026, 1, 7)), date(2026, 1, 4));
+        assert_eq!(calendar.start_of_week(date(2026, 1, 4)), date(2026, 1, 4));
+        assert_eq!(calendar.week_end_modifier(), "'weekday 6'");
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_names() {
+        assert!(Calendar::parse(Some("Mars/Olympus"), None).is_err());
+        assert!(Calendar::parse(None, Some("someday")).is_err());
+        assert_eq!(
+            Calendar::parse(None, Some("Sunday")).unwrap().week_start,
+            Weekday::Sun
+        );
+    }
+
+    #[tokio::test]
+    async fn test_local_seconds_sql_follows_transitions() {
+        let calendar = new_york();
+        let start = calendar.start_of_day(date(2026, 11, 1)).timestamp_millis();
+        let end = start + DAY_MS;
+        let hour = |t: i64| {
+            let expr = calendar.local_seconds_sql("ts", start, end);
+            format!(
+                "SELECT strftime('%Y-%m-%d %H:%M', {}, 'unixepoch') FROM (SELECT {} AS ts)",
+                expr, t
+            )
+        };
+
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        let mut labels = Vec::new();
+        // 00:30 EDT, 01:30 EDT, 01:30 EST, 02:30 EST
+        for minutes in [30, 90, 150, 210] {
+            let sql = hour(start + minutes * 60 * 1000);
+            let label: String = sqlx::query_scalar(&sql).fetch_one(&pool).await.unwrap();
+            labels.push(label);
+        }
+        assert_eq!(
+            labels,
+            [
+                "2026-11-01 00:30",
+                "2026-11-01 01:30",
+                "2026-11-01 01:30",
+                "2026-11-01 02:30"
+            ]
+        );
+    }
+}
@@ -0,0 +1,357 @@
+//! Lumo-managed entries in Claude Code settings
+//!
+//! The app adds OTEL env vars and notification hooks to the `settings.json`
+//! of every Claude profile (`~/.claude` by default), and optionally its
+//! status line, and its MCP server to the profile's `.claude.json`. It
+//! records each file it touches, and the value the user had before for
+//! every env var and status line it changes, in
+//! `~/.lumo/claude-settings.json`, so uninstall can find the files and put
+//! back exactly what was there and leave everything else alone.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::settings_file;
+
+/// Environment variables that Lumo manages in Claude Code settings
+pub const OTEL_ENV_VARS: &[(&str, &str)] = &[
+    ("CLAUDE_CODE_ENABLE_TELEMETRY", "1"),
+    ("OTEL_METRICS_EXPORTER", "otlp"),
+    ("OTEL_LOGS_EXPORTER", "otlp"),
+    ("OTEL_EXPORTER_OTLP_PROTOCOL", "http/json"),
+];
+
+/// Endpoint env var, derived from the daemon address in `~/.lumo/daemon.json`
+pub const OTEL_ENDPOINT_VAR: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+
+/// Daemon binary used as the hook command, relative to the home directory.
+/// Its `hook <event>` subcommand forwards hook stdin JSON to the daemon and
+/// spools it if the daemon is down.
+pub const HOOK_BINARY: &str = ".lumo/bin/lumo-daemon";
+
+/// Marker substring to detect if a Lumo hook is already present
+pub const HOOK_MARKER: &str = ".lumo/bin/lumo-daemon";
+
+/// Marker of the old curl-based hook command
+pub const LEGACY_HOOK_MARKER: &str = "localhost:4318/notify";
+
+/// CLI binary used as the `statusLine` command, relative to the home
+/// directory. Its `statusline` subcommand prints the session's and today's
+/// spend.
+pub const STATUSLINE_BINARY: &str = ".lumo/bin/lumo";
+
+/// Marker substring to detect Lumo's status line command
+pub const STATUSLINE_MARKER: &str = ".lumo/bin/lumo\" statusline";
+
+/// Name of Lumo's entry under `mcpServers` in `.claude.json`. The server is
+/// the daemon binary's `mcp` subcommand.
+pub const MCP_SERVER_NAME: &str = "lumo";
+
+/// Hook events that Lumo subscribes to. Matchers are omitted so the hooks
+/// fire on every occurrence of the event.
+pub const HOOK_EVENTS: &[&str] = &["Notification", "Stop", "SubagentStop"];
+
+/// Check if a JSON value (at any nesting level) contains a current or
+/// legacy Lumo hook marker
+pub fn contains_hook_marker(value: &Value) -> bool {
+    match value {
+        Value::String(s) => s.contains(HOOK_MARKER) || s.contains(LEGACY_HOOK_MARKER),
+        Value::Array(arr) => arr.iter().any(contains_hook_marker),
+        Value::Object(map) => map.values().any(contains_hook_marker),
+        _ => false,
+    }
+}
+
+/// An env var Lumo set, with what it replaced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedEnvVar {
+    /// Value before Lumo first set it, None if the key was absent
+    pub original: Option<Value>,
+    /// Value Lumo last wrote
+    pub value: String,
+}
+
+/// Whether a `statusLine` setting runs Lumo's status line command
+pub fn is_lumo_status_line(value: &Value) -> bool {
+    value
+        .get("command")
+        .and_then(Value::as_str)
+        .is_some_and(|command| command.contains(STATUSLINE_MARKER))
+}
+
+/// Whether an `mcpServers` entry runs Lumo's MCP server
+pub fn is_lumo_mcp_server(value: &Value) -> bool {
+    let command = value.get("command").and_then(Value::as_str);
+    let args = value.get("args").and_then(Value::as_array);
+    command.is_some_and(|c| c.contains(HOOK_MARKER))
+        && args.is_some_and(|args| args.first().and_then(Value::as_str) == Some("mcp"))
+}
+
+/// Whether `config_dir` is `~/.claude`, the root Claude Code uses without
+/// `CLAUDE_CONFIG_DIR`
+pub fn is_home_root(config_dir: &Path) -> bool {
+    dirs::home_dir().is_some_and(|home| config_dir == home.join(".claude"))
+}
+
+/// User preferences file of a Claude config root. Claude Code keeps it
+/// beside `~/.claude` (`~/.claude.json`) and inside any other root.
+pub fn user_prefs_path(config_dir: &Path) -> PathBuf {
+    match config_dir.parent() {
+        Some(home) if is_home_root(config_dir) => home.join(".claude.json"),
+        _ => config_dir.join(".claude.json"),
+    }
+}
+
+/// The status line Lumo replaced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedStatusLine {
+    /// `statusLine` before Lumo set it, None if the key was absent
+    pub original: Option<Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManagedFile {
+    #[serde(default)]
+    env: BTreeMap<String, ManagedEnvVar>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status_line: Option<ManagedStatusLine>,
+}
+
+/// Record of Lumo's changes, per settings file path
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedSettingsRecord {
+    #[serde(default)]
+    files: BTreeMap<String, ManagedFile>,
+}
+
+impl ManagedSettingsRecord {
+    /// `~/.lumo/claude-settings.json`
+    fn path() -> Result<PathBuf> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| Error::InvalidData("Could not determine home directory".to_string()))?;
+        Ok(home.join(".lumo").join("claude-settings.json"))
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(&path)?)?)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        settings_file::write_atomic(&Self::path()?, &serde_json::to_string_pretty(self)?)
+    }
+
+    /// Note that Lumo edited `settings`
+    pub fn track_file(&mut self, settings: &Path) {
+        self.files.entry(settings.display().to_string()).or_default();
+    }
+
+    /// Settings files Lumo has edited
+    pub fn settings_paths(&self) -> Vec<PathBuf> {
+        self.files.keys().map(PathBuf::from).collect()
+    }
+
+    /// Note that Lumo set `key` to `value` in `settings`, replacing
+    /// `previous`. The first recorded original is kept.
+    pub fn record_env(
+        &mut self,
+        settings: &Path,
+        key: &str,
+        previous: Option<&Value>,
+        value: &str,
+    ) {
+        let file = self
+            .files
+            .entry(settings.display().to_string())
+            .or_default();
+        file.env
+            .entry(key.to_string())
+            .and_modify(|v| v.value = value.to_string())
+            .or_insert_with(|| ManagedEnvVar {
+                original: previous.cloned(),
+                value: value.to_string(),
+            });
+    }
+
+    /// Note that Lumo installed its status line in `settings`, replacing
+    /// `previous`. The first recorded original is kept.
+    pub fn record_status_line(&mut self, settings: &Path, previous: Option<&Value>) {
+        let file = self
+            .files
+            .entry(settings.display().to_string())
+            .or_default();
+        file.status_line.get_or_insert_with(|| ManagedStatusLine {
+            original: previous.cloned(),
+        });
+    }
+
+    /// Forget Lumo's status line in `settings`, returning the one it replaced
+    pub fn take_status_line(&mut self, settings: &Path) -> Option<ManagedStatusLine> {
+        self.files
+            .get_mut(&settings.display().to_string())?
+            .status_line
+            .take()
+    }
+
+    /// Remove Lumo's env vars, hooks, status line and MCP server from a
+    /// settings file's `root`, restoring recorded originals. Returns whether
+    /// anything changed.
+    ///
+    /// Env vars the user has changed since Lumo set them are left alone.
+    /// Known Lumo env vars with no record (set by older versions) are
+    /// removed if they still hold the value Lumo writes; pass that endpoint
+    /// as `endpoint`.
+    pub fn revert(
+        &mut self,
+        settings: &Path,
+        root: &mut Map<String, Value>,
+        endpoint: &str,
+    ) -> bool {
+        let record = self
+            .files
+            .remove(&settings.display().to_string())
+            .unwrap_or_default();
+        let mut changed = false;
+
+        if let Some(env) = root.get_mut("env").and_then(Value::as_object_mut) {
+            let known = OTEL_ENV_VARS
+                .iter()
+                .copied()
+                .chain(std::iter::once((OTEL_ENDPOINT_VAR, endpoint)));
+            for (key, value) in known {
+                if !record.env.contains_key(key)
+                    && env.get(key).and_then(Value::as_str) == Some(value)
+                {
+                    env.remove(key);
+                    changed = true;
+                }
+            }
+
+            for (key, managed) in &record.env {
+                if env.get(key).and_then(Value::as_str) != Some(managed.value.as_str()) {
+                    continue;
+                }
+                match &managed.original {
+                    Some(original) => env.insert(key.clone(), original.clone()),
+                    None => env.remove(key),
+                };
+                changed = true;
+            }
+
+            if env.is_empty() {
+                root.remove("env");
+            }
+        }
+
+        if let Some(hooks) = root.get_mut("hooks").and_then(Value::as_object_mut) {
+            for entries in hooks.values_mut() {
+                if let Some(arr) = entries.as_array_mut() {
+                    let before = arr.len();
+                    arr.retain(|entry| !contains_hook_marker(entry));
+                    changed |= arr.len() != before;
+                }
+            }
+            hooks.retain(|_, entries| entries.as_array().is_none_or(|arr| !arr.is_empty()));
+            if hooks.is_empty() {
+                root.remove("hooks");
+            }
+        }
+
+        if root.get("statusLine").is_some_and(is_lumo_status_line) {
+            match record.status_line.and_then(|s| s.original) {
+                Some(original) => root.insert("statusLine".to_string(), original),
+                None => root.remove("statusLine"),
+            };
+            changed = true;
+        }
+
+        if let Some(servers) = root.get_mut("mcpServers").and_then(Value::as_object_mut) {
+            if servers.get(MCP_SERVER_NAME).is_some_and(is_lumo_mcp_server) {
+                servers.remove(MCP_SERVER_NAME);
+                changed = true;
+            }
+            if servers.is_empty() {
+                root.remove("mcpServers");
+            }
+        }
+
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_revert_restores_originals_and_removes_hooks() {
+        let path = Path::new("/home/me/.claude/settings.json");
+        let mut record = ManagedSettingsRecord::default();
+        record.record_env(path, "OTEL_LOGS_EXPORTER", Some(&json!("console")), "otlp");
+        record.record_env(path, "OTEL_METRICS_EXPORTER", None, "otlp");
+        record.record_env(path, "OTEL_EXPORTER_OTLP_PROTOCOL", None, "http/json");
+        let original_status_line = json!({ "type": "command", "command": "~/bin/prompt.sh" });
+        record.record_status_line(path, Some(&original_status_line));
+
+        let mut root = json!({
+            "env": {
+                "OTEL_LOGS_EXPORTER": "otlp",
+                "OTEL_METRICS_EXPORTER": "otlp",
+                // Changed by the user after Lumo set it
+                "OTEL_EXPORTER_OTLP_PROTOCOL": "grpc",
+                // Set by an older Lumo without a record
+                "CLAUDE_CODE_ENABLE_TELEMETRY": "1",
+                "OTEL_EXPORTER_OTLP_ENDPOINT": "http://localhost:4318",
+                "MY_VAR": "x"
+            },
+            "hooks": {
+                "Stop": [
+                    { "hooks": [{ "type": "command", "command": "\"/home/me/.lumo/bin/lumo-daemon\" hook Stop" }] },
+                    { "hooks": [{ "type": "command", "command": "say done" }] }
+                ],
+                "Notification": [
+                    { "hooks": [{ "type": "command", "command": "curl http://localhost:4318/notify" }] }
+                ]
+            },
+            "statusLine": { "type": "command", "command": "\"/home/me/.lumo/bin/lumo\" statusline" },
+            "mcpServers": {
+                "lumo": { "type": "stdio", "command": "/home/me/.lumo/bin/lumo-daemon", "args": ["mcp"] },
+                "github": { "type": "http", "url": "https://api.githubcopilot.com/mcp/" }
+            }
+        });
+        let root = root.as_object_mut().unwrap();
+
+        assert!(record.revert(path, root, "http://localhost:4318"));
+        assert_eq!(
+            Value::Object(root.clone()),
+            json!({
+                "env": {
+                    "OTEL_LOGS_EXPORTER": "console",
+                    "OTEL_EXPORTER_OTLP_PROTOCOL": "grpc",
+                    "MY_VAR": "x"
+                },
+                "hooks": {
+                    "Stop": [
+                        { "hooks": [{ "type": "command", "command": "say done" }] }
+                    ]
+                },
+                "statusLine": original_status_line,
+                "mcpServers": {
+                    "github": { "type": "http", "url": "https://api.githubcopilot.com/mcp/" }
+                }
+            })
+        );
+        assert!(!record.revert(path, root, "http://localhost:4318"));
+    }
+}
@@ -0,0 +1,117 @@
+//! Daemon listen address
+//!
+//! The single source of truth for where the daemon listens: the
+//! `serverAddress` key of the daemon config file (`~/.lumo/daemon.json`),
+//! overridable with `LUMO_SERVER_ADDRESS`. The daemon binds it, the app's
+//! health checks connect to it, and the Claude Code OTEL endpoint is derived
+//! from it.
+
+use serde_json::{Map, Value};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
+
+use crate::error::{Error, Result};
+use crate::settings_file;
+
+/// Address used when neither the environment nor the config file sets one
+pub const DEFAULT_SERVER_ADDRESS: &str = "127.0.0.1:4318";
+
+/// Config file key holding the address
+const ADDRESS_KEY: &str = "serverAddress";
+
+/// Path of the daemon config file: `LUMO_DAEMON_CONFIG` or `~/.lumo/daemon.json`
+pub fn daemon_config_path() -> Result<PathBuf> {
+    if let Ok(path) = std::env::var("LUMO_DAEMON_CONFIG") {
+        return Ok(PathBuf::from(path));
+    }
+    let home = dirs::home_dir()
+        .ok_or_else(|| Error::InvalidData("Could not determine home directory".to_string()))?;
+    Ok(home.join(".lumo").join("daemon.json"))
+}
+
+/// Configured listen address: `LUMO_SERVER_ADDRESS`, then the config file,
+/// then [`DEFAULT_SERVER_ADDRESS`]
+pub fn server_address() -> Result<String> {
+    if let Ok(address) = std::env::var("LUMO_SERVER_ADDRESS") {
+        return Ok(address);
+    }
+    Ok(read_config()?
+        .get(ADDRESS_KEY)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| DEFAULT_SERVER_ADDRESS.to_string()))
+}
+
+/// Store a new listen address in the config file, keeping its other keys.
+///
+/// The running daemon only picks it up after a restart.
+pub fn set_server_address(address: &str) -> Result<()> {
+    parse(address)?;
+
+    let path = daemon_config_path()?;
+    let mut config = read_config()?;
+    config.insert(ADDRESS_KEY.to_string(), Value::String(address.to_string()));
+    settings_file::write_atomic(&path, &serde_json::to_string_pretty(&config)?)
+}
+
+/// Address clients should connect to. A wildcard bind address is reached
+/// through loopback.
+pub fn client_address(address: &str) -> Result<SocketAddr> {
+    let mut addr = parse(address)?;
+    match addr.ip() {
+        IpAddr::V4(ip) if ip.is_unspecified() => addr.set_ip(Ipv4Addr::LOCALHOST.into()),
+        IpAddr::V6(ip) if ip.is_unspecified() => addr.set_ip(Ipv6Addr::LOCALHOST.into()),
+        _ => {}
+    }
+    Ok(addr)
+}
+
+/// OTLP/HTTP endpoint for exporters, e.g. `http://localhost:4318`.
+///
+/// Loopback addresses are written as `localhost` so the default matches the
+/// endpoint Lumo has always configured.
+pub fn otlp_endpoint(address: &str) -> Result<String> {
+    let addr = client_address(address)?;
+    if addr.ip().is_loopback() {
+        Ok(format!("http://localhost:{}", addr.port()))
+    } else {
+        Ok(format!("http://{}", addr))
+    }
+}
+
+fn parse(address: &str) -> Result<SocketAddr> {
+    address
+        .parse()
+        .map_err(|_| Error::InvalidData(format!("Invalid daemon address: {}", address)))
+}
+
+pub(crate) fn read_config() -> Result<Map<String, Value>> {
+    let path = daemon_config_path()?;
+    if !path.exists() {
+        return Ok(Map::new());
+    }
+    let content = std::fs::read_to_string(&path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_otlp_endpoint() {
+        assert_eq!(
+            otlp_endpoint("127.0.0.1:4318").unwrap(),
+            "http://localhost:4318"
+        );
+        assert_eq!(
+            otlp_endpoint("0.0.0.0:5000").unwrap(),
+            "http://localhost:5000"
+        );
+        assert_eq!(
+            otlp_endpoint("192.168.1.10:4318").unwrap(),
+            "http://192.168.1.10:4318"
+        );
+        assert!(otlp_endpoint("localhost").is_err());
+    }
+}
@@ -0,0 +1,62 @@
+//! Daemon log files
+//!
+//! The daemon writes its logs to size/time-rotated files named
+//! `daemon-<UTC timestamp>.log` (`daemon-20261019-143000-123.log`) in
+//! `~/.lumo/logs/`, or in the `log.dir` of the daemon config file. The
+//! timestamp is UTC so names sort and parse the same across DST changes;
+//! only hourly/daily rotation periods follow local time. The app reads the
+//! files back for its log viewer.
+
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use crate::daemon_address;
+use crate::error::{Error, Result};
+
+/// File name prefix of daemon log files
+pub const LOG_FILE_PREFIX: &str = "daemon-";
+
+/// Extension of daemon log files
+pub const LOG_FILE_EXTENSION: &str = "log";
+
+/// `~/.lumo/logs`
+pub fn default_log_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir()
+        .ok_or_else(|| Error::InvalidData("Could not determine home directory".to_string()))?;
+    Ok(home.join(".lumo").join("logs"))
+}
+
+/// Configured log directory: `log.dir` in the daemon config file, else
+/// [`default_log_dir`]
+pub fn log_dir() -> Result<PathBuf> {
+    let configured = daemon_address::read_config()?
+        .get("log")
+        .and_then(|log| log.get("dir"))
+        .and_then(Value::as_str)
+        .map(PathBuf::from);
+    match configured {
+        Some(dir) => Ok(dir),
+        None => default_log_dir(),
+    }
+}
+
+/// Daemon log files in `dir`, oldest first
+pub fn log_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == LOG_FILE_EXTENSION)
+                && path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with(LOG_FILE_PREFIX))
+        })
+        .collect();
+    // Names embed the creation time, so lexical order is chronological
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,231 @@
+//! Detached daemon process without a service manager
+//!
+//! Fallback for Linux systems without a user systemd instance (containers,
+//! WSL, minimal distros). The app spawns `lumo-daemon` detached, records its
+//! pid in `~/.lumo/daemon.pid` and appends its output to `~/.lumo/logs/`,
+//! the same files the systemd unit writes. The pidfile marks this mode:
+//! `lumo-daemon uninstall` uses it to find and stop the process.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+use crate::settings_file;
+
+/// Process name the pid is checked against, so a reused pid is not mistaken
+/// for the daemon
+const DAEMON_NAME: &str = "lumo-daemon";
+
+/// How long to wait for the process to go away after SIGKILL
+const KILL_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Pidfile and log locations of a detached daemon
+#[derive(Debug, Clone)]
+pub struct DaemonProcess {
+    pub pidfile: PathBuf,
+    pub log_dir: PathBuf,
+}
+
+impl DaemonProcess {
+    /// `~/.lumo/daemon.pid` and `~/.lumo/logs/`
+    pub fn new() -> Result<Self> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| Error::InvalidData("Could not determine home directory".to_string()))?;
+        let lumo_dir = home.join(".lumo");
+        Ok(Self {
+            pidfile: lumo_dir.join("daemon.pid"),
+            log_dir: lumo_dir.join("logs"),
+        })
+    }
+
+    /// Whether the daemon is managed this way (a pidfile exists)
+    pub fn is_managed(&self) -> bool {
+        self.pidfile.exists()
+    }
+
+    /// Pid recorded in the pidfile
+    pub fn read_pid(&self) -> Option<u32> {
+        fs::read_to_string(&self.pidfile).ok()?.trim().parse().ok()
+    }
+
+    /// Pid of the recorded daemon if it is still running
+    pub fn running_pid(&self) -> Option<u32> {
+        self.read_pid().filter(|&pid| is_daemon_process(pid))
+    }
+
+    /// Start `binary` detached, with stdout/stderr appended to
+    /// `stdout.log`/`stderr.log`, and record its pid. Returns the pid.
+    pub fn spawn(&self, binary: &Path) -> Result<u32> {
+        fs::create_dir_all(&self.log_dir)?;
+        if let Some(parent) = self.pidfile.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // The shell backgrounds the daemon and exits, so the daemon is
+        // reparented to init instead of lingering as the caller's child.
+        let mut command = Command::new("/bin/sh");
+        command
+            .arg("-c")
+            .arg(r#""$0" </dev/null >>"$1" 2>>"$2" & echo $!"#)
+            .arg(binary)
+            .arg(self.log_dir.join("stdout.log"))
+            .arg(self.log_dir.join("stderr.log"))
+            .stdin(Stdio::null())
+            .stderr(Stdio::null());
+        if let Some(home) = dirs::home_dir() {
+            command.current_dir(home);
+        }
+        #[cfg(unix)]
+        {
+            // New process group: signals to the app's group (e.g. Ctrl+C in
+            // a terminal) do not reach the daemon
+            use std::os::unix::process::CommandExt;
+            command.process_group(0);
+        }
+
+        let output = command.output()?;
+        let pid: u32 = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .map_err(|_| Error::InvalidData(format!("Failed to start {}", binary.display())))?;
+
+        settings_file::write_atomic(&self.pidfile, &format!("{}\n", pid))?;
+        Ok(pid)
+    }
+
+    /// Stop the recorded daemon (SIGTERM, then SIGKILL after `timeout`) and
+    /// remove the pidfile. Returns whether a running daemon was stopped.
+    pub fn stop(&self, timeout: Duration) -> Result<bool> {
+        let stopped = match self.running_pid() {
+            Some(pid) => {
+                send_signal(pid, "TERM")?;
+                if !wait_for_exit(pid, timeout) {
+                    send_signal(pid, "KILL")?;
+                    if !wait_for_exit(pid, KILL_TIMEOUT) {
+                        return Err(Error::Conflict(format!(
+                            "{} (pid {}) did not exit after SIGKILL",
+                            DAEMON_NAME, pid
+                        )));
+                    }
+                }
+                true
+            }
+            None => false,
+        };
+
+        if self.pidfile.exists() {
+            fs::remove_file(&self.pidfile)?;
+        }
+        Ok(stopped)
+    }
+}
+
+/// Wait up to `timeout` for `pid` to exit. The daemon is init's child, not
+/// ours, so it cannot be waited on directly; init reaps it.
+fn wait_for_exit(pid: u32, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    while is_daemon_process(pid) {
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    true
+}
+
+fn send_signal(pid: u32, signal: &str) -> Result<()> {
+    Command::new("kill")
+        .arg(format!("-{}", signal))
+        .arg(pid.to_string())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    Ok(())
+}
+
+/// Whether `pid` is a live (not zombie) `lumo-daemon` process
+#[cfg(target_os = "linux")]
+fn is_daemon_process(pid: u32) -> bool {
+    let proc_dir = PathBuf::from(format!("/proc/{}", pid));
+    // State is the field after the parenthesised command name
+    let zombie = fs::read_to_string(proc_dir.join("stat"))
+        .ok()
+        .and_then(|stat| {
+            stat.rsplit_once(')')
+                .map(|(_, rest)| rest.trim_start().starts_with('Z'))
+        })
+        .unwrap_or(true);
+    if zombie {
+        return false;
+    }
+    // The program, or the script an interpreter runs
+    fs::read(proc_dir.join("cmdline")).is_ok_and(|cmdline| {
+        cmdline
+            .split(|&b| b == 0)
+            .take(2)
+            .any(|arg| {
+                Path::new(&*String::from_utf8_lossy(arg))
+                    .file_name()
+                    .is_some_and(|name| name == DAEMON_NAME)
+            })
+    })
+}
+
+/// Whether `pid` is a live process. Without procfs the name cannot be
+/// checked cheaply.
+#[cfg(not(target_os = "linux"))]
+fn is_daemon_process(pid: u32) -> bool {
+    let _ = DAEMON_NAME;
+    Command::new("kill")
+        .arg("-0")
+        .arg(pid.to_string())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spawn_and_stop() {
+        let dir = std::env::temp_dir().join(format!("lumo-process-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let binary = dir.join(DAEMON_NAME);
+        fs::write(&binary, "#!/bin/sh\necho started\nsleep 30\n").unwrap();
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&binary, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let process = DaemonProcess {
+            pidfile: dir.join("daemon.pid"),
+            log_dir: dir.join("logs"),
+        };
+        assert!(process.running_pid().is_none());
+
+        let pid = process.spawn(&binary).unwrap();
+        assert_eq!(process.read_pid(), Some(pid));
+        assert_eq!(process.running_pid(), Some(pid));
+
+        // Let the script write its output before stopping it
+        let stdout_log = dir.join("logs/stdout.log");
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !fs::read_to_string(&stdout_log).is_ok_and(|s| s.contains("started")) {
+            assert!(Instant::now() < deadline, "daemon did not start");
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        assert!(process.stop(Duration::from_secs(5)).unwrap());
+        assert!(!process.is_managed());
+        assert!(!is_daemon_process(pid));
+        assert!(!process.stop(Duration::from_secs(5)).unwrap());
+
+        let stdout = fs::read_to_string(&stdout_log).unwrap();
+        assert_eq!(stdout, "started\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,88 @@
+//! Budget entity
+//!
+//! Spend limits per calendar day, week or month, overall or for one
+//! project or model.
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Calendar period a budget's limit applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum BudgetPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// Spend a budget counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum BudgetScope {
+    /// All spend
+    Global,
+    /// Sessions whose hooks reported the scope value as their cwd
+    Project,
+    /// Requests to the model named by the scope value
+    Model,
+}
+
+/// Database row representation of a budget
+#[derive(Debug, Clone, FromRow)]
+pub struct BudgetRow {
+    pub id: i64,
+    pub name: String,
+    pub period: BudgetPeriod,
+    pub scope: BudgetScope,
+    pub scope_value: Option<String>,
+    pub limit_usd: f64,
+    pub enabled: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Budget domain type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Budget {
+    pub id: i64,
+    pub name: String,
+    pub period: BudgetPeriod,
+    pub scope: BudgetScope,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope_value: Option<String>,
+    pub limit_usd: f64,
+    pub enabled: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Budget fields for insertion or update
+#[derive(Debug, Clone)]
+pub struct NewBudget {
+    pub name: String,
+    pub period: BudgetPeriod,
+    pub scope: BudgetScope,
+    /// Project path or model name; None for global budgets
+    pub scope_value: Option<String>,
+    pub limit_usd: f64,
+    pub enabled: bool,
+}
+
+impl From<BudgetRow> for Budget {
+    fn from(row: BudgetRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            period: row.period,
+            scope: row.scope,
+            scope_value: row.scope_value,
+            limit_usd: row.limit_usd,
+            enabled: row.enabled != 0,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
@@ -39,6 +39,7 @@ pub struct EventRow {
     pub user_email: Option<String>,
     pub event_sequence: Option<i64>,
     pub tool_result_size_bytes: Option<i64>,
+    pub agent: String,
 }
 
 /// Event entity for internal use
@@ -99,11 +100,12 @@ pub struct Event {
     pub event_sequence: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_result_size_bytes: Option<i64>,
+    pub agent: String,
     pub received_at: String,
 }
 
 /// New event for insertion
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewEvent {
     pub id: String,
     pub session_id: String,
@@ -135,6 +137,8 @@ pub struct NewEvent {
     pub user_email: Option<String>,
     pub event_sequence: Option<i64>,
     pub tool_result_size_bytes: Option<i64>,
+    /// Coding agent that produced the event (e.g. "claude_code", "codex")
+    pub agent: String,
 }
 
 impl From<EventRow> for Event {
@@ -169,6 +173,7 @@ impl From<EventRow> for Event {
             user_email: row.user_email,
             event_sequence: row.event_sequence,
             tool_result_size_bytes: row.tool_result_size_bytes,
+            agent: row.agent,
             received_at: row.received_at,
         }
     }
@@ -0,0 +1,16 @@
+//! Ingest cursor entity
+//!
+//! Tracks read progress for file-based telemetry sources.
+
+use sqlx::FromRow;
+
+/// Read position within a source file, plus context carried between scans.
+#[derive(Debug, Clone, FromRow)]
+pub struct IngestCursor {
+    pub path: String,
+    pub agent: String,
+    pub byte_offset: i64,
+    /// Source-specific JSON state carried between scans
+    pub context: Option<String>,
+    pub updated_at: i64,
+}
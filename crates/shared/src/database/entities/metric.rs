@@ -28,6 +28,7 @@ pub struct MetricRow {
     pub user_email: Option<String>,
     pub unit: Option<String>,
     pub description: Option<String>,
+    pub agent: String,
 }
 
 /// Metric entity for internal use
@@ -65,11 +66,12 @@ pub struct Metric {
     pub unit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    pub agent: String,
     pub received_at: String,
 }
 
 /// New metric for insertion (without received_at)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewMetric {
     pub id: String,
     pub session_id: String,
@@ -90,6 +92,8 @@ pub struct NewMetric {
     pub user_email: Option<String>,
     pub unit: Option<String>,
     pub description: Option<String>,
+    /// Coding agent that produced the metric (e.g. "claude_code", "codex")
+    pub agent: String,
 }
 
 impl From<MetricRow> for Metric {
@@ -113,6 +117,7 @@ impl From<MetricRow> for Metric {
             user_email: row.user_email,
             unit: row.unit,
             description: row.description,
+            agent: row.agent,
             received_at: row.received_at,
         }
     }
@@ -2,14 +2,20 @@
 //!
 //! These structs represent the data stored in the database.
 
+mod budget;
 mod event;
+mod ingest_cursor;
 mod metric;
 mod notification;
 mod notification_setting;
+mod quarantine;
 mod session;
 
+pub use budget::{Budget, BudgetPeriod, BudgetRow, BudgetScope, NewBudget};
 pub use event::{Event, EventRow, NewEvent};
+pub use ingest_cursor::IngestCursor;
 pub use metric::{Metric, MetricRow, NewMetric};
 pub use notification::{NewNotification, Notification, NotificationRow};
 pub use notification_setting::{NewNotificationSetting, NotificationSetting, NotificationSettingRow};
+pub use quarantine::{NewQuarantine, Quarantine, QuarantineRow, QuarantinedRecord};
 pub use session::Session;
@@ -0,0 +1,131 @@
+//! Quarantine entity
+//!
+//! Telemetry records rejected by validation, kept for inspection.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use super::{NewEvent, NewMetric};
+
+/// Database row representation of a quarantined record
+#[derive(Debug, Clone, FromRow)]
+pub struct QuarantineRow {
+    pub id: i64,
+    pub signal: String,
+    pub reason: String,
+    pub detail: Option<String>,
+    pub name: String,
+    pub session_id: Option<String>,
+    pub timestamp: i64,
+    pub agent: String,
+    pub payload: String,
+    pub received_at: i64,
+}
+
+/// Quarantined record for internal use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Quarantine {
+    pub id: i64,
+    pub signal: String,
+    pub reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    pub timestamp: i64,
+    pub agent: String,
+    pub payload: String,
+    pub received_at: i64,
+}
+
+impl Quarantine {
+    /// Event or metric data point has no `session.id` attribute
+    pub const MISSING_SESSION_ID: &'static str = "missing_session_id";
+    /// Neither `time_unix_nano` nor `observed_time_unix_nano` is set
+    pub const MISSING_TIMESTAMP: &'static str = "missing_timestamp";
+    /// A numeric attribute could not be parsed
+    pub const INVALID_NUMBER: &'static str = "invalid_number";
+    /// A metric data point carries no value
+    pub const MISSING_VALUE: &'static str = "missing_value";
+}
+
+/// Parsed record held in quarantine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuarantinedRecord {
+    Event(NewEvent),
+    Metric(NewMetric),
+}
+
+impl QuarantinedRecord {
+    /// Signal name stored in the `signal` column
+    pub fn signal(&self) -> &'static str {
+        match self {
+            Self::Event(_) => "event",
+            Self::Metric(_) => "metric",
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Event(e) => &e.name,
+            Self::Metric(m) => &m.name,
+        }
+    }
+
+    pub fn session_id(&self) -> &str {
+        match self {
+            Self::Event(e) => &e.session_id,
+            Self::Metric(m) => &m.session_id,
+        }
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        match self {
+            Self::Event(e) => e.timestamp,
+            Self::Metric(m) => m.timestamp,
+        }
+    }
+
+    pub fn agent(&self) -> &str {
+        match self {
+            Self::Event(e) => &e.agent,
+            Self::Metric(m) => &m.agent,
+        }
+    }
+}
+
+/// New quarantined record for insertion
+#[derive(Debug, Clone)]
+pub struct NewQuarantine {
+    /// One of the reason constants on [`Quarantine`]
+    pub reason: String,
+    /// Human-readable detail, e.g. the offending attribute and value
+    pub detail: Option<String>,
+    pub record: QuarantinedRecord,
+    /// Attribute values that did not parse, by attribute key. The record
+    /// holds `None` in their place, so the payload keeps them verbatim
+    /// under `raw_values`.
+    pub raw_values: BTreeMap<String, String>,
+}
+
+impl From<QuarantineRow> for Quarantine {
+    fn from(row: QuarantineRow) -> Self {
+        Self {
+            id: row.id,
+            signal: row.signal,
+            reason: row.reason,
+            detail: row.detail,
+            name: row.name,
+            session_id: row.session_id,
+            timestamp: row.timestamp,
+            agent: row.agent,
+            payload: row.payload,
+            received_at: row.received_at,
+        }
+    }
+}
@@ -31,4 +31,5 @@ pub struct Session {
     pub terminal_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub app_version: Option<String>,
+    pub agent: String,
 }
@@ -0,0 +1,38 @@
+//! Anomaly alert repository
+//!
+//! Records the anomalies already notified, so each fires once per window.
+
+use sqlx::Sqlite;
+
+use crate::error::Result;
+
+/// Repository for anomaly alert operations
+pub struct AnomalyAlertRepository;
+
+impl AnomalyAlertRepository {
+    /// Record an anomaly of `kind` for `session_id` ('' for none) in the
+    /// window starting at `window_start`. Returns false if it already was.
+    pub async fn record<'e, E>(
+        executor: E,
+        kind: &str,
+        session_id: &str,
+        window_start: i64,
+    ) -> Result<bool>
+    where
+        E: sqlx::Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO anomaly_alerts (kind, session_id, window_start)
+            VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(kind)
+        .bind(session_id)
+        .bind(window_start)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,169 @@
+//! Budget repository
+//!
+//! Provides CRUD operations for budgets and records the threshold alerts
+//! already sent for them.
+
+use sqlx::{Sqlite, SqlitePool};
+
+use crate::database::entities::{Budget, BudgetRow, BudgetScope, NewBudget};
+use crate::error::{Error, Result};
+
+/// Repository for budget operations
+pub struct BudgetRepository;
+
+impl BudgetRepository {
+    /// Get all budgets
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Budget>> {
+        let rows: Vec<BudgetRow> = sqlx::query_as(r#"SELECT * FROM budgets ORDER BY id ASC"#)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(rows.into_iter().map(Budget::from).collect())
+    }
+
+    /// Get the budgets alerts are evaluated for
+    pub async fn find_enabled(pool: &SqlitePool) -> Result<Vec<Budget>> {
+        let rows: Vec<BudgetRow> =
+            sqlx::query_as(r#"SELECT * FROM budgets WHERE enabled = 1 ORDER BY id ASC"#)
+                .fetch_all(pool)
+                .await?;
+
+        Ok(rows.into_iter().map(Budget::from).collect())
+    }
+
+    /// Find a budget by ID
+    pub async fn find_by_id(pool: &SqlitePool, id: i64) -> Result<Budget> {
+        let row: Option<BudgetRow> = sqlx::query_as(r#"SELECT * FROM budgets WHERE id = ?"#)
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+
+        row.map(Budget::from)
+            .ok_or_else(|| Error::NotFound(format!("budget {}", id)))
+    }
+
+    /// Insert a new budget, returning its ID
+    pub async fn insert(pool: &SqlitePool, budget: &NewBudget) -> Result<i64> {
+        Self::validate(budget)?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO budgets (name, period, scope, scope_value, limit_usd, enabled)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&budget.name)
+        .bind(budget.period)
+        .bind(budget.scope)
+        .bind(&budget.scope_value)
+        .bind(budget.limit_usd)
+        .bind(budget.enabled as i32)
+        .execute(pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Replace a budget's fields. Alerts already sent in the current period
+    /// are kept, so editing a budget does not repeat them.
+    pub async fn update(pool: &SqlitePool, id: i64, budget: &NewBudget) -> Result<()> {
+        Self::validate(budget)?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE budgets SET
+                name = ?,
+                period = ?,
+                scope = ?,
+                scope_value = ?,
+                limit_usd = ?,
+                enabled = ?,
+                updated_at = unixepoch() * 1000
+            WHERE id = ?
+            "#,
+        )
+        .bind(&budget.name)
+        .bind(budget.period)
+        .bind(budget.scope)
+        .bind(&budget.scope_value)
+        .bind(budget.limit_usd)
+        .bind(budget.enabled as i32)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(Error::NotFound(format!("budget {}", id)));
+        }
+        Ok(())
+    }
+
+    /// Delete a budget and its alert history
+    pub async fn delete(pool: &SqlitePool, id: i64) -> Result<bool> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query(r#"DELETE FROM budget_alerts WHERE budget_id = ?"#)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query(r#"DELETE FROM budgets WHERE id = ?"#)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record that `threshold` was reached in the period starting at
+    /// `period_start`. Returns false if it already was, so each alert is
+    /// sent once per period even when batches are evaluated concurrently.
+    pub async fn record_alert<'e, E>(
+        executor: E,
+        budget_id: i64,
+        period_start: i64,
+        threshold: u32,
+    ) -> Result<bool>
+    where
+        E: sqlx::Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO budget_alerts (budget_id, period_start, threshold)
+            VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(budget_id)
+        .bind(period_start)
+        .bind(threshold)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn validate(budget: &NewBudget) -> Result<()> {
+        if budget.name.trim().is_empty() {
+            return Err(Error::InvalidData("Budget name is empty".to_string()));
+        }
+        if !(budget.limit_usd.is_finite() && budget.limit_usd > 0.0) {
+            return Err(Error::InvalidData(format!(
+                "Budget limit must be positive, got {}",
+                budget.limit_usd
+            )));
+        }
+        let has_value = budget
+            .scope_value
+            .as_deref()
+            .is_some_and(|v| !v.trim().is_empty());
+        match (budget.scope, has_value) {
+            (BudgetScope::Global, true) => Err(Error::InvalidData(
+                "Global budgets take no project or model".to_string(),
+            )),
+            (BudgetScope::Project | BudgetScope::Model, false) => Err(Error::InvalidData(
+                "Project and model budgets need a project or model".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
@@ -27,7 +27,8 @@ impl EventRepository {
                 prompt_length, prompt,
                 account_uuid, organization_id, terminal_type, app_version,
                 resource,
-                user_id, user_email, event_sequence, tool_result_size_bytes
+                user_id, user_email, event_sequence, tool_result_size_bytes,
+                agent
             ) VALUES (
                 ?, ?, ?, ?,
                 ?, ?, ?,
@@ -37,7 +38,8 @@ impl EventRepository {
                 ?, ?,
                 ?, ?, ?, ?,
                 ?,
-                ?, ?, ?, ?
+                ?, ?, ?, ?,
+                ?
             )
             "#,
         )
@@ -71,6 +73,7 @@ impl EventRepository {
         .bind(&event.user_email)
         .bind(event.event_sequence)
         .bind(event.tool_result_size_bytes)
+        .bind(&event.agent)
         .execute(executor)
         .await?;
 
@@ -109,6 +112,30 @@ impl EventRepository {
         Ok(rows.into_iter().map(Event::from).collect())
     }
 
+    /// Find a page of a session's events, oldest first
+    pub async fn find_by_session_paginated(
+        pool: &SqlitePool,
+        session_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Event>> {
+        let rows: Vec<EventRow> = sqlx::query_as(
+            r#"
+            SELECT * FROM events
+            WHERE session_id = ?
+            ORDER BY timestamp ASC, id ASC
+            LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(session_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Event::from).collect())
+    }
+
     /// Find events by name
     pub async fn find_by_name(pool: &SqlitePool, name: &str) -> Result<Vec<Event>> {
         let rows: Vec<EventRow> = sqlx::query_as(
@@ -228,4 +255,21 @@ impl EventRepository {
 
         Ok(result.rows_affected())
     }
+
+    /// Delete all events of a session
+    pub async fn delete_by_session<'e, E>(executor: E, session_id: &str) -> Result<u64>
+    where
+        E: sqlx::Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM events WHERE session_id = ?
+            "#,
+        )
+        .bind(session_id)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
 }
@@ -0,0 +1,55 @@
+//! Ingest cursor repository
+//!
+//! Provides read/upsert operations for file ingest cursors.
+
+use sqlx::{Sqlite, SqlitePool};
+
+use crate::database::entities::IngestCursor;
+use crate::error::Result;
+
+/// Repository for ingest cursor operations
+pub struct IngestCursorRepository;
+
+impl IngestCursorRepository {
+    /// Find the cursor for a file, if it has been scanned before
+    pub async fn find_by_path(pool: &SqlitePool, path: &str) -> Result<Option<IngestCursor>> {
+        let cursor: Option<IngestCursor> =
+            sqlx::query_as(r#"SELECT * FROM ingest_cursors WHERE path = ?"#)
+                .bind(path)
+                .fetch_optional(pool)
+                .await?;
+
+        Ok(cursor)
+    }
+
+    /// Insert or advance a cursor into any executor (pool or transaction)
+    pub async fn upsert<'e, E>(
+        executor: E,
+        path: &str,
+        agent: &str,
+        byte_offset: i64,
+        context: Option<&str>,
+    ) -> Result<()>
+    where
+        E: sqlx::Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO ingest_cursors (path, agent, byte_offset, context, updated_at)
+            VALUES (?, ?, ?, ?, unixepoch() * 1000)
+            ON CONFLICT(path) DO UPDATE SET
+                byte_offset = excluded.byte_offset,
+                context = excluded.context,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(path)
+        .bind(agent)
+        .bind(byte_offset)
+        .bind(context)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+}
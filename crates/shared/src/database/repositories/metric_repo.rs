@@ -23,13 +23,15 @@ impl MetricRepository {
                 metric_type, model, tool, decision, language,
                 account_uuid, organization_id, terminal_type, app_version,
                 resource,
-                user_id, user_email, unit, description
+                user_id, user_email, unit, description,
+                agent
             ) VALUES (
                 ?, ?, ?, ?, ?,
                 ?, ?, ?, ?, ?,
                 ?, ?, ?, ?,
                 ?,
-                ?, ?, ?, ?
+                ?, ?, ?, ?,
+                ?
             )
             "#,
         )
@@ -52,6 +54,7 @@ impl MetricRepository {
         .bind(&metric.user_email)
         .bind(&metric.unit)
         .bind(&metric.description)
+        .bind(&metric.agent)
         .execute(executor)
         .await?;
 
@@ -2,14 +2,22 @@
 //!
 //! Provides CRUD operations for database entities.
 
+mod anomaly_alert_repo;
+mod budget_repo;
 mod event_repo;
+mod ingest_cursor_repo;
 mod metric_repo;
 mod notification_repo;
 mod notification_setting_repo;
+mod quarantine_repo;
 mod session_repo;
 
+pub use anomaly_alert_repo::AnomalyAlertRepository;
+pub use budget_repo::BudgetRepository;
 pub use event_repo::EventRepository;
+pub use ingest_cursor_repo::IngestCursorRepository;
 pub use metric_repo::{MetricRepository, TokenUsageByModel};
 pub use notification_repo::NotificationRepository;
 pub use notification_setting_repo::NotificationSettingRepository;
+pub use quarantine_repo::{QuarantineReasonCount, QuarantineRepository};
 pub use session_repo::{SessionRepository, SessionsSummary, TotalTokens};
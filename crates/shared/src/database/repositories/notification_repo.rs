@@ -2,7 +2,7 @@
 //!
 //! Provides CRUD operations for notifications.
 
-use sqlx::SqlitePool;
+use sqlx::{Sqlite, SqlitePool};
 
 use crate::database::entities::{NewNotification, Notification, NotificationRow};
 use crate::error::Result;
@@ -13,7 +13,10 @@ pub struct NotificationRepository;
 impl NotificationRepository {
     /// Insert a new notification, returning its ID.
     /// Title and message default to empty string if not provided (column is NOT NULL).
-    pub async fn insert(pool: &SqlitePool, notif: &NewNotification) -> Result<i64> {
+    pub async fn insert<'e, E>(executor: E, notif: &NewNotification) -> Result<i64>
+    where
+        E: sqlx::Executor<'e, Database = Sqlite>,
+    {
         let title = notif.title.as_deref().unwrap_or("");
         let message = notif.message.as_deref().unwrap_or("");
 
@@ -33,7 +36,7 @@ impl NotificationRepository {
         .bind(&notif.agent_type)
         .bind(&notif.cwd)
         .bind(&notif.transcript_path)
-        .execute(pool)
+        .execute(executor)
         .await?;
 
         Ok(result.last_insert_rowid())
@@ -0,0 +1,221 @@
+//! Quarantine repository
+//!
+//! Stores, lists, re-attributes and purges quarantined telemetry.
+
+use sqlx::{Sqlite, SqlitePool};
+
+use crate::database::entities::{NewQuarantine, Quarantine, QuarantineRow, QuarantinedRecord};
+use crate::database::repositories::{EventRepository, MetricRepository};
+use crate::error::{Error, Result};
+
+/// Repository for quarantine operations
+pub struct QuarantineRepository;
+
+impl QuarantineRepository {
+    /// Insert a quarantined record into any executor (pool or transaction)
+    pub async fn insert<'e, E>(executor: E, entry: &NewQuarantine) -> Result<()>
+    where
+        E: sqlx::Executor<'e, Database = Sqlite>,
+    {
+        let record = &entry.record;
+        let session_id = Some(record.session_id()).filter(|s| !s.is_empty());
+
+        let mut payload = serde_json::to_value(record)?;
+        if !entry.raw_values.is_empty() {
+            // Inside the tagged record, where deserializing it ignores them
+            let fields = payload
+                .as_object_mut()
+                .and_then(|tagged| tagged.values_mut().next())
+                .and_then(|fields| fields.as_object_mut());
+            if let Some(fields) = fields {
+                fields.insert(
+                    "raw_values".to_string(),
+                    serde_json::to_value(&entry.raw_values)?,
+                );
+            }
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO quarantine (
+                signal, reason, detail, name, session_id, timestamp, agent, payload
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(record.signal())
+        .bind(&entry.reason)
+        .bind(&entry.detail)
+        .bind(record.name())
+        .bind(session_id)
+        .bind(record.timestamp())
+        .bind(record.agent())
+        .bind(payload.to_string())
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Insert multiple quarantined records using a single transaction
+    pub async fn insert_batch(pool: &SqlitePool, entries: &[NewQuarantine]) -> Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = pool.begin().await?;
+
+        for entry in entries {
+            Self::insert(&mut *tx, entry).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Find recent quarantined records, optionally filtered by reason
+    pub async fn find_recent(
+        pool: &SqlitePool,
+        reason: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Quarantine>> {
+        let rows: Vec<QuarantineRow> = sqlx::query_as(
+            r#"
+            SELECT * FROM quarantine
+            WHERE reason = COALESCE(?, reason)
+            ORDER BY received_at DESC, id DESC
+            LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(reason)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Quarantine::from).collect())
+    }
+
+    /// Count quarantined records per reason
+    pub async fn count_by_reason(pool: &SqlitePool) -> Result<Vec<QuarantineReasonCount>> {
+        let counts: Vec<QuarantineReasonCount> = sqlx::query_as(
+            r#"
+            SELECT reason, COUNT(*) as count
+            FROM quarantine
+            GROUP BY reason
+            ORDER BY count DESC
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// Move quarantined records into events/metrics.
+    ///
+    /// `session_id` overrides the record's session; it is required for records
+    /// that have none. Records without a timestamp take the time they were
+    /// received. Returns the number of records moved.
+    pub async fn reattribute(
+        pool: &SqlitePool,
+        ids: &[i64],
+        session_id: Option<&str>,
+    ) -> Result<u64> {
+        let mut tx = pool.begin().await?;
+        let mut moved = 0;
+
+        for id in ids {
+            let row: Option<QuarantineRow> =
+                sqlx::query_as(r#"SELECT * FROM quarantine WHERE id = ?"#)
+                    .bind(id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            let Some(row) = row else {
+                return Err(Error::NotFound(format!("quarantine record {}", id)));
+            };
+
+            let mut record: QuarantinedRecord = serde_json::from_str(&row.payload)?;
+            match &mut record {
+                QuarantinedRecord::Event(e) => {
+                    Self::fix_up(&mut e.session_id, &mut e.timestamp, session_id, &row)?;
+                    EventRepository::insert(&mut *tx, e).await?;
+                }
+                QuarantinedRecord::Metric(m) => {
+                    Self::fix_up(&mut m.session_id, &mut m.timestamp, session_id, &row)?;
+                    MetricRepository::insert(&mut *tx, m).await?;
+                }
+            }
+
+            sqlx::query(r#"DELETE FROM quarantine WHERE id = ?"#)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            moved += 1;
+        }
+
+        tx.commit().await?;
+        Ok(moved)
+    }
+
+    /// Apply the session override and timestamp fallback to a record
+    fn fix_up(
+        record_session: &mut String,
+        record_timestamp: &mut i64,
+        session_id: Option<&str>,
+        row: &QuarantineRow,
+    ) -> Result<()> {
+        if let Some(session_id) = session_id {
+            *record_session = session_id.to_string();
+        }
+        if record_session.is_empty() {
+            return Err(Error::InvalidData(format!(
+                "quarantine record {} has no session id; pass one to re-attribute",
+                row.id
+            )));
+        }
+        if *record_timestamp == 0 {
+            *record_timestamp = row.received_at;
+        }
+        Ok(())
+    }
+
+    /// Delete specific quarantined records. Returns the number deleted.
+    pub async fn delete(pool: &SqlitePool, ids: &[i64]) -> Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let placeholders: Vec<&str> = ids.iter().map(|_| "?").collect();
+        let query = format!(
+            "DELETE FROM quarantine WHERE id IN ({})",
+            placeholders.join(", ")
+        );
+
+        let mut q = sqlx::query(&query);
+        for id in ids {
+            q = q.bind(id);
+        }
+        let result = q.execute(pool).await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Delete all quarantined records, optionally only those with a reason
+    pub async fn delete_all(pool: &SqlitePool, reason: Option<&str>) -> Result<u64> {
+        let result = sqlx::query(r#"DELETE FROM quarantine WHERE reason = COALESCE(?, reason)"#)
+            .bind(reason)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+/// Number of quarantined records with a given reason
+#[derive(Debug, Clone, sqlx::FromRow, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuarantineReasonCount {
+    pub reason: String,
+    pub count: i64,
+}
@@ -63,6 +63,27 @@ impl SessionRepository {
         session.ok_or_else(|| Error::NotFound(format!("Session not found: {}", id)))
     }
 
+    /// The session with the most recent event, if any
+    pub async fn find_most_recent(pool: &SqlitePool) -> Result<Option<Session>> {
+        // Two steps: SQLite only narrows the view to one session for a
+        // literal ID, not for a subquery
+        let id: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT session_id FROM events
+            WHERE session_id != 'unknown'
+            ORDER BY timestamp DESC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        match id {
+            Some(id) => Ok(Some(Self::find_by_id(pool, &id).await?)),
+            None => Ok(None),
+        }
+    }
+
     /// Find sessions within a time range
     pub async fn find_by_time_range(
         pool: &SqlitePool,
@@ -85,6 +106,38 @@ impl SessionRepository {
         Ok(sessions)
     }
 
+    /// Find a page of the sessions active within a time range, newest first,
+    /// optionally of one agent only
+    pub async fn find_by_time_range_paginated(
+        pool: &SqlitePool,
+        start_time: i64,
+        end_time: i64,
+        agent: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Session>> {
+        let sessions: Vec<Session> = sqlx::query_as(
+            r#"
+            SELECT * FROM sessions
+            WHERE start_time <= ? AND end_time >= ?
+              AND id != 'unknown'
+              AND (? IS NULL OR agent = ?)
+            ORDER BY start_time DESC
+            LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(end_time)
+        .bind(start_time)
+        .bind(agent)
+        .bind(agent)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(sessions)
+    }
+
     /// Count total number of sessions
     pub async fn count(pool: &SqlitePool) -> Result<i64> {
         let (count,): (i64,) = sqlx::query_as(
@@ -0,0 +1,801 @@
+//! End-to-end diagnostics
+//!
+//! Walks the path telemetry takes from Claude Code into the database and
+//! reports the first places it breaks: Claude Code settings, per-project
+//! overrides, the daemon listener and auth token, a synthetic OTLP record
+//! posted to the daemon and read back from the database, and the database
+//! itself. Every failing check carries fixes the user can act on.
+//!
+//! Used by `lumo-daemon doctor` and the app's diagnostics command.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use sqlx::SqlitePool;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::claude_settings::{
+    self, ManagedSettingsRecord, HOOK_EVENTS, HOOK_MARKER, LEGACY_HOOK_MARKER, OTEL_ENDPOINT_VAR,
+    OTEL_ENV_VARS,
+};
+use crate::daemon_address;
+use crate::error::{Error, Result};
+use crate::settings_file::SettingsFile;
+use crate::EventRepository;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Header marking doctor's requests, so the daemon neither captures them
+/// nor counts their rejections
+pub const PROBE_HEADER: &str = "x-lumo-probe";
+
+/// Prefix of the session id the synthetic OTLP record is posted under
+const PROBE_SESSION_PREFIX: &str = "lumo-doctor-";
+
+/// Event name of the synthetic OTLP record
+const PROBE_EVENT_NAME: &str = "claude_code.lumo_doctor";
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+    /// Not run because a check it depends on failed
+    Skip,
+}
+
+/// A single diagnostic and what to do about it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    /// Actionable steps, empty when the check passed
+    pub fixes: Vec<String>,
+}
+
+impl DoctorCheck {
+    fn new(name: &str, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            message: message.into(),
+            fixes: Vec::new(),
+        }
+    }
+
+    fn pass(name: &str, message: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Pass, message)
+    }
+
+    fn warn(name: &str, message: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Warn, message)
+    }
+
+    fn fail(name: &str, message: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Fail, message)
+    }
+
+    fn skip(name: &str, message: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Skip, message)
+    }
+
+    fn fix(mut self, fix: impl Into<String>) -> Self {
+        self.fixes.push(fix.into());
+        self
+    }
+}
+
+/// All checks, in the order they ran
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    /// Whether any check failed
+    pub fn has_failures(&self) -> bool {
+        self.checks.iter().any(|c| c.status == CheckStatus::Fail)
+    }
+}
+
+/// A Claude Code config root to check
+#[derive(Debug, Clone)]
+pub struct ClaudeConfigRoot {
+    /// Shown in check names when more than one root is checked
+    pub label: String,
+    /// `settings.json` carrying Lumo's env vars and hooks
+    pub settings_path: PathBuf,
+    /// `.claude.json`, whose `projects` lists the projects Claude Code has
+    /// been used in
+    pub user_prefs_path: PathBuf,
+}
+
+impl ClaudeConfigRoot {
+    /// Root for a Claude config directory. `~/.claude` keeps its user prefs
+    /// in `~/.claude.json`; other roots keep them inside the directory.
+    pub fn from_dir(label: &str, dir: &Path) -> Self {
+        Self {
+            label: label.to_string(),
+            settings_path: dir.join("settings.json"),
+            user_prefs_path: claude_settings::user_prefs_path(dir),
+        }
+    }
+
+    /// Roots Lumo has configured, plus `$CLAUDE_CONFIG_DIR` or `~/.claude`
+    pub fn discover() -> Result<Vec<Self>> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| Error::InvalidData("Could not determine home directory".to_string()))?;
+        let default_dir = std::env::var("CLAUDE_CONFIG_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| home.join(".claude"));
+        let record = ManagedSettingsRecord::load()?;
+        Ok(Self::discover_in(default_dir, &record))
+    }
+
+    fn discover_in(default_dir: PathBuf, record: &ManagedSettingsRecord) -> Vec<Self> {
+        let mut dirs = vec![default_dir];
+        for path in record.settings_paths() {
+            if let Some(dir) = config_dir_of(&path) {
+                if !dirs.contains(&dir) {
+                    dirs.push(dir);
+                }
+            }
+        }
+
+        dirs.iter()
+            .map(|dir| {
+                let label = dir
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| dir.display().to_string());
+                Self::from_dir(&label, dir)
+            })
+            .collect()
+    }
+}
+
+/// Config root a file Lumo edited belongs to. `settings.json` and
+/// `settings.local.json` live inside it; `.claude.json` inside it too,
+/// except `~/.claude.json`, which belongs to `~/.claude`.
+fn config_dir_of(path: &Path) -> Option<PathBuf> {
+    let parent = path.parent()?;
+    match path.file_name()?.to_str()? {
+        "settings.json" | "settings.local.json" => Some(parent.to_path_buf()),
+        ".claude.json" => {
+            let home_root = parent.join(".claude");
+            if claude_settings::is_home_root(&home_root) {
+                Some(home_root)
+            } else {
+                Some(parent.to_path_buf())
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Run every check. `pool` is the database the daemon writes to, or None
+/// if it does not exist yet.
+pub async fn run(roots: &[ClaudeConfigRoot], pool: Option<&SqlitePool>) -> DoctorReport {
+    let mut checks = Vec::new();
+
+    let address = daemon_address::server_address()
+        .unwrap_or_else(|_| daemon_address::DEFAULT_SERVER_ADDRESS.to_string());
+    let endpoint = daemon_address::otlp_endpoint(&address);
+
+    let labelled = roots.len() > 1;
+    for root in roots {
+        let name = if labelled {
+            format!("Claude settings ({})", root.label)
+        } else {
+            "Claude settings".to_string()
+        };
+        checks.push(match &endpoint {
+            Ok(endpoint) => check_claude_settings(&name, root, endpoint),
+            Err(e) => DoctorCheck::fail(&name, format!("Daemon address is invalid: {}", e))
+                .fix("Fix `serverAddress` in ~/.lumo/daemon.json, e.g. \"127.0.0.1:4318\""),
+        });
+    }
+    if let Ok(endpoint) = &endpoint {
+        checks.push(check_project_overrides(roots, endpoint));
+    }
+
+    match daemon_address::client_address(&address) {
+        Ok(addr) => {
+            let listener = check_listener(addr).await;
+            let reachable = listener.status != CheckStatus::Fail;
+            checks.push(listener);
+            if reachable {
+                checks.push(check_auth(addr).await);
+                checks.push(check_round_trip(addr, pool).await);
+            } else {
+                checks.push(DoctorCheck::skip(
+                    "Authentication",
+                    "Daemon is not reachable",
+                ));
+                checks.push(DoctorCheck::skip(
+                    "OTLP round trip",
+                    "Daemon is not reachable",
+                ));
+            }
+        }
+        Err(e) => checks.push(
+            DoctorCheck::fail("Daemon listener", e.to_string())
+                .fix("Fix `serverAddress` in ~/.lumo/daemon.json, e.g. \"127.0.0.1:4318\""),
+        ),
+    }
+
+    match pool {
+        Some(pool) => {
+            checks.push(check_integrity(pool).await);
+            checks.push(check_migrations(pool).await);
+        }
+        None => checks.push(
+            DoctorCheck::fail("Database", "~/.lumo/lumo.db does not exist")
+                .fix("Start the daemon once; it creates the database on startup"),
+        ),
+    }
+
+    DoctorReport { checks }
+}
+
+/// Lumo's OTEL env vars and hooks in a root's settings.json
+fn check_claude_settings(name: &str, root: &ClaudeConfigRoot, endpoint: &str) -> DoctorCheck {
+    let path = root.settings_path.display();
+    let reapply = "Restart Lumo; it re-applies its Claude Code settings on startup";
+
+    if !root.settings_path.exists() {
+        return DoctorCheck::fail(name, format!("{} does not exist", path)).fix(reapply);
+    }
+    let settings = match SettingsFile::read(&root.settings_path) {
+        Ok(settings) => settings,
+        Err(e) => {
+            return DoctorCheck::fail(name, format!("{} could not be read: {}", path, e))
+                .fix(format!("Fix the JSON syntax in {}", path))
+        }
+    };
+
+    let env = settings.root.get("env").and_then(Value::as_object);
+    let mut problems = Vec::new();
+    for (key, expected) in expected_env(endpoint) {
+        match env.and_then(|env| env.get(key)) {
+            Some(Value::String(value)) if value == expected => {}
+            Some(value) => {
+                problems.push(format!("{} is {} (expected \"{}\")", key, value, expected))
+            }
+            None => problems.push(format!("{} is not set", key)),
+        }
+    }
+
+    let hooks = settings.root.get("hooks");
+    if hooks.is_some_and(|h| contains(h, LEGACY_HOOK_MARKER)) {
+        problems.push("hooks still use the legacy curl command".to_string());
+    }
+    let missing_hooks: Vec<&str> = HOOK_EVENTS
+        .iter()
+        .copied()
+        .filter(|event| {
+            !hooks
+                .and_then(|h| h.get(*event))
+                .is_some_and(|v| contains(v, HOOK_MARKER))
+        })
+        .collect();
+    if !missing_hooks.is_empty() {
+        problems.push(format!("hooks missing for {}", missing_hooks.join(", ")));
+    }
+    if settings.root.get("disableAllHooks") == Some(&Value::Bool(true)) {
+        problems.push("disableAllHooks is true".to_string());
+    }
+
+    if problems.is_empty() {
+        return DoctorCheck::pass(name, format!("{} has Lumo's OTEL env vars and hooks", path));
+    }
+    DoctorCheck::fail(name, format!("{}: {}", path, problems.join("; ")))
+        .fix(reapply)
+        .fix(format!(
+            "Or set these under \"env\" in {}: {}",
+            path,
+            Value::Object(
+                expected_env(endpoint)
+                    .map(|(k, v)| (k.to_string(), Value::String(v.to_string())))
+                    .collect()
+            )
+        ))
+}
+
+/// Project settings that override Lumo's user-level settings. Claude Code
+/// gives `.claude/settings.json` and `.claude/settings.local.json` in the
+/// project precedence over the user's settings.json.
+fn check_project_overrides(roots: &[ClaudeConfigRoot], endpoint: &str) -> DoctorCheck {
+    const NAME: &str = "Project overrides";
+
+    let mut projects: Vec<PathBuf> = Vec::new();
+    for root in roots {
+        let Ok(prefs) = SettingsFile::read(&root.user_prefs_path) else {
+            continue;
+        };
+        if let Some(map) = prefs.root.get("projects").and_then(Value::as_object) {
+            for project in map.keys().map(PathBuf::from) {
+                if !projects.contains(&project) {
+                    projects.push(project);
+                }
+            }
+        }
+    }
+
+    let mut conflicts = Vec::new();
+    let mut fixes = Vec::new();
+    for project in &projects {
+        for file in ["settings.local.json", "settings.json"] {
+            let path = project.join(".claude").join(file);
+            if !path.is_file() {
+                continue;
+            }
+            let Ok(settings) = SettingsFile::read(&path) else {
+                continue;
+            };
+            let overrides = conflicting_overrides(&settings.root, endpoint);
+            if !overrides.is_empty() {
+                conflicts.push(format!("{} ({})", path.display(), overrides.join(", ")));
+                fixes.push(format!(
+                    "Remove {} from {}",
+                    overrides.join(", "),
+                    path.display()
+                ));
+            }
+        }
+    }
+
+    if conflicts.is_empty() {
+        return DoctorCheck::pass(
+            NAME,
+            format!("No overrides in {} known project(s)", projects.len()),
+        );
+    }
+    let mut check = DoctorCheck::warn(
+        NAME,
+        format!(
+            "{} project settings file(s) override Lumo's settings: {}",
+            conflicts.len(),
+            conflicts.join("; ")
+        ),
+    );
+    check.fixes = fixes;
+    check
+}
+
+/// Keys in a project settings `root` that disable or redirect telemetry
+fn conflicting_overrides(root: &Map<String, Value>, endpoint: &str) -> Vec<String> {
+    let mut overrides = Vec::new();
+    if let Some(env) = root.get("env").and_then(Value::as_object) {
+        for (key, expected) in expected_env(endpoint) {
+            if let Some(value) = env.get(key) {
+                if value.as_str() != Some(expected) {
+                    overrides.push(format!("env.{}", key));
+                }
+            }
+        }
+    }
+    if root.get("disableAllHooks") == Some(&Value::Bool(true)) {
+        overrides.push("disableAllHooks".to_string());
+    }
+    overrides
+}
+
+/// GET /health
+async fn check_listener(addr: SocketAddr) -> DoctorCheck {
+    const NAME: &str = "Daemon listener";
+
+    let response = match http(addr, "GET", "/health", None, "").await {
+        Ok(response) => response,
+        Err(e) => {
+            return DoctorCheck::fail(NAME, format!("Nothing answered on {}: {}", addr, e))
+                .fix("Start Lumo, which starts the daemon, or run `lumo-daemon` directly")
+                .fix(format!(
+                    "If the port is taken, check with `lsof -i :{}` and change \
+                     `serverAddress` in ~/.lumo/daemon.json",
+                    addr.port()
+                ))
+        }
+    };
+
+    let body: Value = serde_json::from_str(&response.body).unwrap_or(Value::Null);
+    if body.get("service").and_then(Value::as_str) != Some("lumo-daemon") {
+        return DoctorCheck::fail(
+            NAME,
+            format!(
+                "{} answered with HTTP {} but is not the Lumo daemon",
+                addr, response.status
+            ),
+        )
+        .fix(format!(
+            "Another program holds port {}; stop it (`lsof -i :{}`) or change \
+             `serverAddress` in ~/.lumo/daemon.json",
+            addr.port(),
+            addr.port()
+        ));
+    }
+
+    let version = body.get("version").and_then(Value::as_str).unwrap_or("?");
+    if response.status != 200 {
+        return DoctorCheck::warn(
+            NAME,
+            format!(
+                "lumo-daemon v{} on {} reports its database as unavailable",
+                version, addr
+            ),
+        )
+        .fix("Check the daemon logs in ~/.lumo/logs for database errors");
+    }
+    DoctorCheck::pass(
+        NAME,
+        format!("lumo-daemon v{} is listening on {}", version, addr),
+    )
+}
+
+/// The `/notify` token check. An empty body passes auth and is then
+/// rejected by validation, so nothing is stored; [`PROBE_HEADER`] keeps
+/// the rejection out of the daemon's stats and captures.
+async fn check_auth(addr: SocketAddr) -> DoctorCheck {
+    const NAME: &str = "Authentication";
+
+    let token = std::env::var("LUMO_AUTH_TOKEN")
+        .ok()
+        .or_else(|| {
+            daemon_address::read_config()
+                .ok()?
+                .get("authToken")?
+                .as_str()
+                .map(str::to_string)
+        })
+        .filter(|t| !t.is_empty());
+
+    let status = |token: Option<String>| async move {
+        http(addr, "POST", "/notify", token.as_deref(), "{}")
+            .await
+            .map(|r| r.status)
+    };
+
+    match token {
+        Some(token) => match (status(Some(token)).await, status(None).await) {
+            (Ok(401), _) => DoctorCheck::fail(
+                NAME,
+                "The daemon rejects the auth token in ~/.lumo/daemon.json",
+            )
+            .fix("Restart the daemon so it picks up the current token")
+            .fix("Check that LUMO_AUTH_TOKEN is not set differently in the service environment"),
+            (Ok(_), Ok(401)) => {
+                DoctorCheck::pass(NAME, "Hooks authenticate with the configured token")
+            }
+            (Ok(_), Ok(_)) => DoctorCheck::warn(
+                NAME,
+                "A token is configured but the daemon accepts unauthenticated hook requests",
+            )
+            .fix("Restart the daemon so it enforces the configured token"),
+            (Err(e), _) | (_, Err(e)) => {
+                DoctorCheck::fail(NAME, format!("Request to /notify failed: {}", e))
+            }
+        },
+        None => match status(None).await {
+            Ok(401) => DoctorCheck::fail(
+                NAME,
+                "The daemon requires a token but none is configured for hooks",
+            )
+            .fix("Set `authToken` in ~/.lumo/daemon.json to the daemon's token")
+            .fix("Or remove LUMO_AUTH_TOKEN from the daemon's service environment"),
+            Ok(_) => DoctorCheck::pass(NAME, "No auth token is configured; hooks are accepted"),
+            Err(e) => DoctorCheck::fail(NAME, format!("Request to /notify failed: {}", e)),
+        },
+    }
+}
+
+/// Post a synthetic OTLP log record, read it back from the database, and
+/// delete it again
+async fn check_round_trip(addr: SocketAddr, pool: Option<&SqlitePool>) -> DoctorCheck {
+    const NAME: &str = "OTLP round trip";
+
+    let session_id = format!("{}{}", PROBE_SESSION_PREFIX, uuid::Uuid::new_v4());
+    let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    let body = json!({
+        "resourceLogs": [{
+            "resource": {
+                "attributes": [
+                    { "key": "service.name", "value": { "stringValue": "claude-code" } }
+                ]
+            },
+            "scopeLogs": [{
+                "logRecords": [{
+                    "timeUnixNano": now_ns.to_string(),
+                    "body": { "stringValue": PROBE_EVENT_NAME },
+                    "attributes": [
+                        { "key": "event.name", "value": { "stringValue": PROBE_EVENT_NAME } },
+                        { "key": "session.id", "value": { "stringValue": session_id } }
+                    ]
+                }]
+            }]
+        }]
+    });
+
+    let response = match http(addr, "POST", "/v1/logs", None, &body.to_string()).await {
+        Ok(response) => response,
+        Err(e) => return DoctorCheck::fail(NAME, format!("POST /v1/logs failed: {}", e)),
+    };
+    if response.status != 200 {
+        return DoctorCheck::fail(
+            NAME,
+            format!(
+                "The daemon rejected an OTLP record with HTTP {}: {}",
+                response.status,
+                response.body.trim()
+            ),
+        )
+        .fix("Check the daemon logs in ~/.lumo/logs for the cause");
+    }
+
+    let Some(pool) = pool else {
+        return DoctorCheck::warn(
+            NAME,
+            "The daemon accepted an OTLP record; no database to read it back from",
+        );
+    };
+    let stored = EventRepository::find_by_session(pool, &session_id).await;
+    let _ = EventRepository::delete_by_session(pool, &session_id).await;
+    match stored {
+        Ok(events) if !events.is_empty() => {
+            DoctorCheck::pass(NAME, "A synthetic OTLP record was stored and read back")
+        }
+        Ok(_) => DoctorCheck::fail(
+            NAME,
+            "The daemon accepted an OTLP record but it is not in ~/.lumo/lumo.db",
+        )
+        .fix(
+            "The daemon is writing to a different database; check that its service \
+             runs with the same HOME as you",
+        ),
+        Err(e) => DoctorCheck::fail(NAME, format!("Reading the record back failed: {}", e)),
+    }
+}
+
+/// `PRAGMA integrity_check`
+async fn check_integrity(pool: &SqlitePool) -> DoctorCheck {
+    const NAME: &str = "Database integrity";
+
+    let rows: std::result::Result<Vec<String>, _> =
+        sqlx::query_scalar("PRAGMA integrity_check(10)")
+            .fetch_all(pool)
+            .await;
+    match rows {
+        Ok(rows) if rows.len() == 1 && rows[0] == "ok" => {
+            DoctorCheck::pass(NAME, "integrity_check reports ok")
+        }
+        Ok(rows) => DoctorCheck::fail(NAME, format!("integrity_check: {}", rows.join("; ")))
+            .fix("Stop the daemon, back up ~/.lumo/lumo.db, and recover it with `sqlite3 ~/.lumo/lumo.db .recover`"),
+        Err(e) => DoctorCheck::fail(NAME, format!("integrity_check failed: {}", e))
+            .fix("Check that ~/.lumo/lumo.db is readable and not locked by another process"),
+    }
+}
+
+/// Applied migrations against the ones this build ships
+async fn check_migrations(pool: &SqlitePool) -> DoctorCheck {
+    const NAME: &str = "Database migrations";
+    let restart = "Restart the daemon; it applies pending migrations on startup";
+
+    let applied: std::result::Result<Vec<(i64, bool, Vec<u8>)>, _> =
+        sqlx::query_as("SELECT version, success, checksum FROM _sqlx_migrations ORDER BY version")
+            .fetch_all(pool)
+            .await;
+    let applied = match applied {
+        Ok(applied) => applied,
+        Err(_) => {
+            return DoctorCheck::fail(NAME, "The database has no migration history").fix(restart)
+        }
+    };
+
+    let migrator = sqlx::migrate!("./migrations");
+    let known: Vec<_> = migrator
+        .iter()
+        .filter(|m| m.migration_type.is_up_migration())
+        .collect();
+
+    let failed: Vec<String> = applied
+        .iter()
+        .filter(|(_, success, _)| !success)
+        .map(|(version, _, _)| version.to_string())
+        .collect();
+    if !failed.is_empty() {
+        return DoctorCheck::fail(
+            NAME,
+            format!("Migration(s) {} did not complete", failed.join(", ")),
+        )
+        .fix("Check the daemon logs in ~/.lumo/logs for the migration error, then restart it");
+    }
+
+    let pending: Vec<String> = known
+        .iter()
+        .filter(|m| !applied.iter().any(|(version, _, _)| *version == m.version))
+        .map(|m| m.version.to_string())
+        .collect();
+    let unknown: Vec<String> = applied
+        .iter()
+        .filter(|(version, _, _)| !known.iter().any(|m| m.version == *version))
+        .map(|(version, _, _)| version.to_string())
+        .collect();
+    let modified: Vec<String> = known
+        .iter()
+        .filter(|m| {
+            applied
+                .iter()
+                .any(|(version, _, checksum)| *version == m.version && **checksum != *m.checksum)
+        })
+        .map(|m| m.version.to_string())
+        .collect();
+
+    if !pending.is_empty() {
+        return DoctorCheck::fail(
+            NAME,
+            format!(
+                "{} migration(s) pending: {}",
+                pending.len(),
+                pending.join(", ")
+            ),
+        )
+        .fix(restart);
+    }
+    if !unknown.is_empty() {
+        return DoctorCheck::warn(
+            NAME,
+            format!(
+                "The database has migration(s) {} from a newer Lumo",
+                unknown.join(", ")
+            ),
+        )
+        .fix("Update Lumo and the daemon to the latest version");
+    }
+    if !modified.is_empty() {
+        return DoctorCheck::warn(
+            NAME,
+            format!(
+                "Applied migration(s) {} differ from this build",
+                modified.join(", ")
+            ),
+        )
+        .fix("Reinstall the daemon so it matches the app version");
+    }
+    DoctorCheck::pass(NAME, format!("All {} migrations applied", known.len()))
+}
+
+/// Managed env vars and the values Lumo expects for them
+fn expected_env(endpoint: &str) -> impl Iterator<Item = (&'static str, &str)> {
+    OTEL_ENV_VARS
+        .iter()
+        .copied()
+        .chain(std::iter::once((OTEL_ENDPOINT_VAR, endpoint)))
+}
+
+/// Whether any string in `value` contains `marker`
+fn contains(value: &Value, marker: &str) -> bool {
+    match value {
+        Value::String(s) => s.contains(marker),
+        Value::Array(arr) => arr.iter().any(|v| contains(v, marker)),
+        Value::Object(map) => map.values().any(|v| contains(v, marker)),
+        _ => false,
+    }
+}
+
+struct HttpResponse {
+    status: u16,
+    body: String,
+}
+
+/// Minimal HTTP/1.1 request over a fresh connection
+async fn http(
+    addr: SocketAddr,
+    method: &str,
+    path: &str,
+    token: Option<&str>,
+    body: &str,
+) -> std::io::Result<HttpResponse> {
+    let exchange = async {
+        let mut stream = TcpStream::connect(addr).await?;
+        let auth = token
+            .map(|token| format!("Authorization: Bearer {}\r\n", token))
+            .unwrap_or_default();
+        let request = format!(
+            "{} {} HTTP/1.1\r\n\
+             Host: {}\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: {}\r\n\
+             Connection: close\r\n\
+             {}: doctor\r\n\
+             {}\r\n\
+             {}",
+            method,
+            path,
+            addr,
+            body.len(),
+            PROBE_HEADER,
+            auth,
+            body
+        );
+        stream.write_all(request.as_bytes()).await?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await?;
+        let response = String::from_utf8_lossy(&response);
+        let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+        let status = head
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|code| code.parse().ok())
+            .ok_or_else(|| std::io::Error::other("malformed HTTP response"))?;
+        Ok(HttpResponse {
+            status,
+            body: body.to_string(),
+        })
+    };
+
+    tokio::time::timeout(HTTP_TIMEOUT, exchange)
+        .await
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "timed out"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflicting_overrides() {
+        let endpoint = "http://localhost:4318";
+        let root = json!({
+            "env": {
+                "OTEL_LOGS_EXPORTER": "console",
+                "OTEL_METRICS_EXPORTER": "otlp",
+                "OTEL_EXPORTER_OTLP_ENDPOINT": "http://localhost:9999",
+                "OTHER": "x"
+            },
+            "disableAllHooks": true
+        });
+        assert_eq!(
+            conflicting_overrides(root.as_object().unwrap(), endpoint),
+            vec![
+                "env.OTEL_LOGS_EXPORTER",
+                "env.OTEL_EXPORTER_OTLP_ENDPOINT",
+                "disableAllHooks"
+            ]
+        );
+
+        let root = json!({ "env": { "OTEL_METRICS_EXPORTER": "otlp" }, "permissions": {} });
+        assert!(conflicting_overrides(root.as_object().unwrap(), endpoint).is_empty());
+    }
+
+    #[test]
+    fn test_discover_maps_user_prefs_to_their_root() {
+        let home = dirs::home_dir().unwrap();
+        let work = std::env::temp_dir().join("lumo-work-claude");
+        let mut record = ManagedSettingsRecord::default();
+        record.track_file(&home.join(".claude").join("settings.json"));
+        record.track_file(&home.join(".claude.json"));
+        record.track_file(&work.join(".claude.json"));
+        record.track_file(&work.join("settings.json"));
+
+        let roots = ClaudeConfigRoot::discover_in(home.join(".claude"), &record);
+        let settings: Vec<_> = roots.iter().map(|r| r.settings_path.clone()).collect();
+        assert_eq!(
+            settings,
+            [
+                home.join(".claude").join("settings.json"),
+                work.join("settings.json")
+            ]
+        );
+        assert_eq!(roots[0].user_prefs_path, home.join(".claude.json"));
+        assert_eq!(roots[1].user_prefs_path, work.join(".claude.json"));
+    }
+}
@@ -25,4 +25,7 @@ pub enum Error {
 
     #[error("Invalid data: {0}")]
     InvalidData(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
 }
@@ -3,18 +3,29 @@
 //! Contains database entities, repositories, and utilities shared between
 //! the daemon and Tauri application.
 
+pub mod agent;
+pub mod analytics;
+pub mod calendar;
+pub mod claude_settings;
+pub mod daemon_address;
+pub mod daemon_logs;
+pub mod daemon_process;
 pub mod database;
+pub mod doctor;
 pub mod error;
+pub mod settings_file;
 
 // Re-export commonly used types
 pub use database::connection::{create_pool, get_db_path, run_migrations};
 pub use database::entities::{
-    Event, EventRow, Metric, MetricRow, NewEvent, NewMetric, NewNotification,
-    NewNotificationSetting, Notification, NotificationRow, NotificationSetting,
-    NotificationSettingRow, Session,
+    Budget, BudgetPeriod, BudgetRow, BudgetScope, Event, EventRow, IngestCursor, Metric, MetricRow,
+    NewBudget, NewEvent, NewMetric, NewNotification, NewNotificationSetting, NewQuarantine,
+    Notification, NotificationRow, NotificationSetting, NotificationSettingRow, Quarantine,
+    QuarantinedRecord, Session,
 };
 pub use database::repositories::{
-    EventRepository, MetricRepository, NotificationRepository, NotificationSettingRepository,
-    SessionRepository, SessionsSummary, TokenUsageByModel, TotalTokens,
+    AnomalyAlertRepository, BudgetRepository, EventRepository, IngestCursorRepository,
+    MetricRepository, NotificationRepository, NotificationSettingRepository, QuarantineReasonCount,
+    QuarantineRepository, SessionRepository, SessionsSummary, TokenUsageByModel, TotalTokens,
 };
 pub use error::{Error, Result};
@@ -0,0 +1,413 @@
+//! Safe edits to hand-edited JSON settings files
+//!
+//! Used for Claude Code's settings. Every write:
+//! - keeps key order, indentation and the trailing newline of the original,
+//! - saves the previous content to a timestamped backup in `~/.lumo/backups/`,
+//! - is refused if the file changed on disk since it was read,
+//! - lands atomically via a temp file in the same directory plus rename.
+//!
+//! Also lists and restores those backups, and diffs proposed changes for
+//! preview.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tracing::info;
+
+use crate::error::{Error, Result};
+
+/// Backups kept per settings file; older ones are pruned on each backup
+const MAX_BACKUPS_PER_FILE: usize = 20;
+
+/// A settings file as read from disk, remembering enough to detect
+/// concurrent modification before writing it back
+pub struct SettingsFile {
+    pub path: PathBuf,
+    pub root: Map<String, Value>,
+    /// Raw content as read, None if the file did not exist
+    original: Option<String>,
+    modified: Option<SystemTime>,
+}
+
+impl SettingsFile {
+    /// Read and parse a settings file. A missing file yields an empty object.
+    pub fn read(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self {
+                path: path.to_path_buf(),
+                root: Map::new(),
+                original: None,
+                modified: None,
+            });
+        }
+
+        let content = fs::read_to_string(path)?;
+        let root = serde_json::from_str(&content)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            root,
+            original: Some(content),
+            modified: fs::metadata(path).and_then(|m| m.modified()).ok(),
+        })
+    }
+
+    /// Hash of the content as read, used to tie a previewed change to the
+    /// file version it was computed from
+    pub fn fingerprint(&self) -> String {
+        content_hash(self.original())
+    }
+
+    /// Content as read, or an empty string for a missing file
+    pub fn original(&self) -> &str {
+        self.original.as_deref().unwrap_or("")
+    }
+
+    /// Serialize `root` the way it will be written, reusing the original's
+    /// indentation
+    pub fn render(&self) -> Result<String> {
+        let indent = self
+            .original
+            .as_deref()
+            .and_then(detect_indent)
+            .unwrap_or("  ");
+        let mut buf = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+        let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+        self.root.serialize(&mut serializer)?;
+        let mut content = String::from_utf8(buf)
+            .map_err(|e| Error::InvalidData(format!("Settings are not UTF-8: {}", e)))?;
+        // Keep the original's trailing newline (new files get one)
+        if self.original.as_deref().is_none_or(|o| o.ends_with('\n')) {
+            content.push('\n');
+        }
+        Ok(content)
+    }
+
+    /// Write `root` back, backing up the previous content.
+    ///
+    /// Fails with [`Error::Conflict`] without touching the file if it changed
+    /// since it was read. Returns the backup path, if there was a previous
+    /// file to back up.
+    pub fn write(&self) -> Result<Option<PathBuf>> {
+        self.ensure_unchanged()?;
+
+        let backup = match &self.original {
+            Some(content) => Some(backup(&self.path, content)?),
+            None => None,
+        };
+
+        write_atomic(&self.path, &self.render()?)?;
+        Ok(backup)
+    }
+
+    /// Check the file on disk still matches what was read: mtime first,
+    /// then content hash (an mtime bump alone, e.g. `touch`, is fine)
+    fn ensure_unchanged(&self) -> Result<()> {
+        let unchanged = match (&self.original, self.path.exists()) {
+            (None, false) => true,
+            (None, true) | (Some(_), false) => false,
+            (Some(original), true) => {
+                let modified = fs::metadata(&self.path).and_then(|m| m.modified()).ok();
+                modified == self.modified
+                    || fs::read_to_string(&self.path)
+                        .is_ok_and(|current| content_hash(&current) == content_hash(original))
+            }
+        };
+
+        if !unchanged {
+            return Err(Error::Conflict(format!(
+                "{} was modified by another program since it was read; reload and try again",
+                self.path.display()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Write via a temp file in the same directory and rename over the target
+pub fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| Error::InvalidData(format!("{} has no parent", path.display())))?;
+    fs::create_dir_all(parent)?;
+
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| Error::InvalidData(format!("{} has no file name", path.display())))?;
+    let tmp_path = parent.join(format!(".{}.lumo-tmp", file_name));
+
+    let result = (|| -> std::io::Result<()> {
+        let mut tmp = fs::File::create(&tmp_path)?;
+        tmp.write_all(content.as_bytes())?;
+        tmp.sync_all()?;
+        // Keep the original file's permissions
+        if let Ok(metadata) = fs::metadata(path) {
+            fs::set_permissions(&tmp_path, metadata.permissions())?;
+        }
+        fs::rename(&tmp_path, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    Ok(result?)
+}
+
+/// `~/.lumo/backups`
+pub fn backups_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir()
+        .ok_or_else(|| Error::InvalidData("Could not determine home directory".to_string()))?;
+    Ok(home.join(".lumo").join("backups"))
+}
+
+/// Backup name prefix identifying the original file, e.g.
+/// `~/.claude/settings.json` becomes `claude_settings.json`
+pub fn backup_prefix(path: &Path) -> String {
+    let relative = dirs::home_dir()
+        .and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| path.to_path_buf());
+    relative
+        .to_string_lossy()
+        .trim_start_matches(['/', '\\', '.'])
+        .replace(['/', '\\', ':'], "_")
+}
+
+/// Prefix part of a backup file name (everything before the timestamp)
+pub fn backup_source_prefix(backup: &Path) -> Option<String> {
+    let stem = backup.file_stem()?.to_str()?;
+    let (prefix, _timestamp) = stem.rsplit_once('.')?;
+    Some(prefix.to_string())
+}
+
+/// All backup files in `dir`
+pub fn backup_paths(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "bak"))
+        .collect())
+}
+
+/// Save `content` as `<prefix>.<timestamp>.bak` and prune old backups
+pub fn backup(path: &Path, content: &str) -> Result<PathBuf> {
+    let dir = backups_dir()?;
+    fs::create_dir_all(&dir)?;
+
+    let prefix = backup_prefix(path);
+    let backup_path = dir.join(format!(
+        "{}.{}.bak",
+        prefix,
+        chrono::Local::now().format("%Y%m%d-%H%M%S-%3f")
+    ));
+    fs::write(&backup_path, content)?;
+
+    // Timestamps sort lexically, so the oldest come first
+    let mut existing: Vec<PathBuf> = backup_paths(&dir)?
+        .into_iter()
+        .filter(|p| backup_source_prefix(p).as_deref() == Some(prefix.as_str()))
+        .collect();
+    existing.sort();
+    let excess = existing.len().saturating_sub(MAX_BACKUPS_PER_FILE);
+    for old in &existing[..excess] {
+        let _ = fs::remove_file(old);
+    }
+
+    info!("Backed up {} to {}", path.display(), backup_path.display());
+    Ok(backup_path)
+}
+
+/// A backup of one of Lumo's settings files
+#[derive(Debug, Clone)]
+pub struct Backup {
+    pub path: PathBuf,
+    /// Settings file the backup restores to
+    pub target: PathBuf,
+    /// Unix ms
+    pub created_at: i64,
+    pub size_bytes: u64,
+}
+
+impl Backup {
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// Backups of the given settings files, newest first
+pub fn list_backups(targets: &[PathBuf]) -> Result<Vec<Backup>> {
+    let mut backups = Vec::new();
+    for path in backup_paths(&backups_dir()?)? {
+        let Some(prefix) = backup_source_prefix(&path) else {
+            continue;
+        };
+        let Some(target) = targets.iter().find(|t| backup_prefix(t) == prefix) else {
+            continue;
+        };
+        let metadata = fs::metadata(&path)?;
+        let created_at = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        backups.push(Backup {
+            path,
+            target: target.clone(),
+            created_at,
+            size_bytes: metadata.len(),
+        });
+    }
+    backups.sort_by(|a, b| b.path.file_name().cmp(&a.path.file_name()));
+    Ok(backups)
+}
+
+/// Restore the backup `file_name` over whichever of `targets` it was taken
+/// from, and return that target.
+///
+/// The current content is backed up first, so a restore can be undone.
+pub fn restore_backup(file_name: &str, targets: &[PathBuf]) -> Result<PathBuf> {
+    let invalid_name = || Error::InvalidData(format!("Invalid backup name: {}", file_name));
+    if file_name.contains(['/', '\\']) {
+        return Err(invalid_name());
+    }
+    let backup_path = backups_dir()?.join(file_name);
+    let prefix = backup_source_prefix(&backup_path).ok_or_else(invalid_name)?;
+    let target = targets
+        .iter()
+        .find(|t| backup_prefix(t) == prefix)
+        .ok_or_else(|| Error::NotFound(format!("No settings file matches backup {}", file_name)))?;
+
+    let content = fs::read_to_string(&backup_path)?;
+    serde_json::from_str::<Value>(&content).map_err(|e| {
+        Error::InvalidData(format!("Backup {} is not valid JSON: {}", file_name, e))
+    })?;
+
+    if target.exists() {
+        let current = fs::read_to_string(target)?;
+        backup(target, &current)?;
+    }
+    write_atomic(target, &content)?;
+    Ok(target.clone())
+}
+
+/// How a line of a [`diff_lines`] diff changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    Context,
+    Added,
+    Removed,
+}
+
+impl DiffKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Context => "context",
+            Self::Added => "added",
+            Self::Removed => "removed",
+        }
+    }
+}
+
+/// Line diff between two texts (longest common subsequence)
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<(DiffKind, &'a str)> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] = LCS length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push((DiffKind::Context, old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push((DiffKind::Removed, old[i]));
+            i += 1;
+        } else {
+            diff.push((DiffKind::Added, new[j]));
+            j += 1;
+        }
+    }
+    diff.extend(old[i..].iter().map(|l| (DiffKind::Removed, *l)));
+    diff.extend(new[j..].iter().map(|l| (DiffKind::Added, *l)));
+    diff
+}
+
+/// Leading whitespace of the first indented line, e.g. two or four spaces
+fn detect_indent(content: &str) -> Option<&str> {
+    content
+        .lines()
+        .skip(1)
+        .find(|line| line.starts_with([' ', '\t']))
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+}
+
+fn content_hash(content: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_keeps_order_and_indent() {
+        let path =
+            std::env::temp_dir().join(format!("lumo-settings-{}.json", uuid::Uuid::new_v4()));
+        fs::write(&path, "{\n    \"zeta\": 1,\n    \"alpha\": true\n}").unwrap();
+
+        let mut file = SettingsFile::read(&path).unwrap();
+        file.root
+            .insert("env".to_string(), Value::Object(Map::new()));
+        assert_eq!(
+            file.render().unwrap(),
+            "{\n    \"zeta\": 1,\n    \"alpha\": true,\n    \"env\": {}\n}"
+        );
+
+        // A concurrent edit makes the write fail
+        fs::write(&path, "{}").unwrap();
+        assert!(matches!(file.write(), Err(Error::Conflict(_))));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = diff_lines("{\n  \"a\": 1\n}", "{\n  \"a\": 2,\n  \"b\": 3\n}");
+        assert_eq!(
+            diff,
+            [
+                (DiffKind::Context, "{"),
+                (DiffKind::Removed, "  \"a\": 1"),
+                (DiffKind::Added, "  \"a\": 2,"),
+                (DiffKind::Added, "  \"b\": 3"),
+                (DiffKind::Context, "}"),
+            ]
+        );
+    }
+}
@@ -1,7 +1,10 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
   ActivityDay,
+  BreakdownRequest,
+  BreakdownRow,
   CacheHitTrend,
+  CustomTimeRange,
   ErrorRateStats,
   HourlyActivity,
   SessionBucket,
@@ -13,30 +16,52 @@ import type {
  */
 export class AnalyticsBridge {
   static async getHourlyActivity(
-    timeRange: TimeRange,
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
   ): Promise<HourlyActivity[]> {
-    return invoke<HourlyActivity[]>("get_hourly_activity", { timeRange });
+    return invoke<HourlyActivity[]>("get_hourly_activity", {
+      timeRange,
+      agent,
+    });
   }
 
   static async getSessionLengthDistribution(
-    timeRange: TimeRange,
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
   ): Promise<SessionBucket[]> {
     return invoke<SessionBucket[]>("get_session_length_distribution", {
       timeRange,
+      agent,
     });
   }
 
-  static async getErrorRate(timeRange: TimeRange): Promise<ErrorRateStats> {
-    return invoke<ErrorRateStats>("get_error_rate", { timeRange });
+  static async getErrorRate(
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
+  ): Promise<ErrorRateStats> {
+    return invoke<ErrorRateStats>("get_error_rate", { timeRange, agent });
   }
 
   static async getCacheHitTrend(
-    timeRange: TimeRange,
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
   ): Promise<CacheHitTrend[]> {
-    return invoke<CacheHitTrend[]>("get_cache_hit_trend", { timeRange });
+    return invoke<CacheHitTrend[]>("get_cache_hit_trend", { timeRange, agent });
+  }
+
+  static async runBreakdownQuery(
+    timeRange: TimeRange | CustomTimeRange,
+    request: BreakdownRequest,
+    agent?: string,
+  ): Promise<BreakdownRow[]> {
+    return invoke<BreakdownRow[]>("run_breakdown_query", {
+      timeRange,
+      request,
+      agent,
+    });
   }
 
-  static async getActivityHeatmap(): Promise<ActivityDay[]> {
-    return invoke<ActivityDay[]>("get_activity_heatmap");
+  static async getActivityHeatmap(agent?: string): Promise<ActivityDay[]> {
+    return invoke<ActivityDay[]>("get_activity_heatmap", { agent });
   }
 }
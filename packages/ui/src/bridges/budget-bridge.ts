@@ -0,0 +1,49 @@
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  Budget,
+  BudgetRequest,
+  BudgetStatus,
+  BudgetPeriod,
+  BurnDownPoint,
+  SpendForecast,
+} from "../generated/typeshare-types";
+
+/**
+ * Budget Bridge - Frontend interface for spend budgets
+ */
+export class BudgetBridge {
+  static async getBudgets(): Promise<BudgetStatus[]> {
+    return invoke<BudgetStatus[]>("get_budgets");
+  }
+
+  static async createBudget(request: BudgetRequest): Promise<Budget> {
+    return invoke<Budget>("create_budget", { request });
+  }
+
+  static async updateBudget(
+    id: number,
+    request: BudgetRequest,
+  ): Promise<Budget> {
+    return invoke<Budget>("update_budget", { id, request });
+  }
+
+  static async deleteBudget(id: number): Promise<boolean> {
+    return invoke<boolean>("delete_budget", { id });
+  }
+
+  static async getBudgetBurnDown(id: number): Promise<BurnDownPoint[]> {
+    return invoke<BurnDownPoint[]>("get_budget_burn_down", { id });
+  }
+
+  static async getSpendForecast(
+    period: BudgetPeriod,
+    limit?: number,
+    agent?: string,
+  ): Promise<SpendForecast> {
+    return invoke<SpendForecast>("get_spend_forecast", {
+      period,
+      limit,
+      agent,
+    });
+  }
+}
@@ -0,0 +1,44 @@
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  ClaudeProfileInfo,
+  SettingsBackup,
+  SettingsPreview,
+} from "@/generated/typeshare-types";
+
+export const ClaudeConfigBridge = {
+  listProfiles: () => invoke<ClaudeProfileInfo[]>("list_claude_profiles"),
+
+  addProfile: (label: string, path: string) =>
+    invoke<ClaudeProfileInfo[]>("add_claude_profile", { label, path }),
+
+  removeProfile: (label: string) =>
+    invoke<ClaudeProfileInfo[]>("remove_claude_profile", { label }),
+
+  previewChanges: (profile: string | null = null) =>
+    invoke<SettingsPreview>("preview_claude_settings_changes", { profile }),
+
+  applyChanges: (fingerprint: string, profile: string | null = null) =>
+    invoke<void>("apply_claude_settings_changes", { profile, fingerprint }),
+
+  listBackups: () => invoke<SettingsBackup[]>("list_claude_settings_backups"),
+
+  restoreBackup: (fileName: string) =>
+    invoke<void>("restore_claude_settings_backup", { fileName }),
+
+  isStatuslineInstalled: () =>
+    invoke<boolean>("get_claude_statusline_installed"),
+
+  setStatusline: (enabled: boolean) =>
+    invoke<void>("set_claude_statusline", { enabled }),
+
+  isMcpServerInstalled: () =>
+    invoke<boolean>("get_claude_mcp_server_installed"),
+
+  setMcpServer: (enabled: boolean) =>
+    invoke<void>("set_claude_mcp_server", { enabled }),
+
+  getDailySpendTarget: () => invoke<number | null>("get_daily_spend_target"),
+
+  setDailySpendTarget: (target: number | null) =>
+    invoke<void>("set_daily_spend_target", { target }),
+};
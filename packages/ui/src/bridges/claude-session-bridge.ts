@@ -12,9 +12,11 @@ export class ClaudeSessionBridge {
     projectPath: string | null,
     offset: number,
     limit: number,
+    profile: string | null = null,
   ): Promise<ClaudeSessionPage> {
     return invoke<ClaudeSessionPage>("get_claude_sessions_page", {
       projectPath,
+      profile,
       offset,
       limit,
     });
@@ -0,0 +1,18 @@
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  DaemonAddressStatus,
+  DaemonStatus,
+  DoctorReport,
+} from "@/generated/typeshare-types";
+
+export const DaemonBridge = {
+  getStatus: () => invoke<DaemonStatus>("get_daemon_status"),
+
+  getAddressStatus: () =>
+    invoke<DaemonAddressStatus>("get_daemon_address_status"),
+
+  setAddress: (address: string) =>
+    invoke<DaemonAddressStatus>("set_daemon_address", { address }),
+
+  runDoctor: () => invoke<DoctorReport>("run_daemon_doctor"),
+};
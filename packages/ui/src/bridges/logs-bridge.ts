@@ -0,0 +1,15 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { LogEntry, LogQuery } from "../generated/typeshare-types";
+
+/**
+ * Logs Bridge - Frontend interface for the daemon and app log viewer
+ */
+export class LogsBridge {
+  static async getEntries(query: LogQuery): Promise<LogEntry[]> {
+    return invoke<LogEntry[]>("get_log_entries", { query });
+  }
+
+  static async openLogDirectory(): Promise<void> {
+    return invoke<void>("open_log_directory");
+  }
+}
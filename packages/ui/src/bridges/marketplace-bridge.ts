@@ -20,33 +20,45 @@ export class MarketplaceBridge {
   static async installPlugin(
     name: string,
     projectPath: string | null = null,
+    profile: string | null = null,
   ): Promise<MarketplaceCommandResult> {
     return invoke<MarketplaceCommandResult>("install_marketplace_plugin", {
       name,
       projectPath,
+      profile,
     });
   }
 
   static async uninstallPlugin(
     name: string,
     projectPath: string | null = null,
+    profile: string | null = null,
   ): Promise<MarketplaceCommandResult> {
     return invoke<MarketplaceCommandResult>("uninstall_marketplace_plugin", {
       name,
       projectPath,
+      profile,
     });
   }
 
   static async addMarketplace(
     source: string,
+    profile: string | null = null,
   ): Promise<MarketplaceCommandResult> {
-    return invoke<MarketplaceCommandResult>("add_marketplace", { source });
+    return invoke<MarketplaceCommandResult>("add_marketplace", {
+      source,
+      profile,
+    });
   }
 
   static async removeMarketplace(
     name: string,
+    profile: string | null = null,
   ): Promise<MarketplaceCommandResult> {
-    return invoke<MarketplaceCommandResult>("remove_marketplace", { name });
+    return invoke<MarketplaceCommandResult>("remove_marketplace", {
+      name,
+      profile,
+    });
   }
 
   static async updateMarketplaces(): Promise<MarketplaceCommandResult> {
@@ -30,8 +30,13 @@ export class SkillsBridge {
   static async createSkill(
     name: string,
     projectPath: string | null = null,
+    profile: string | null = null,
   ): Promise<SkillCommandResult> {
-    return invoke<SkillCommandResult>("create_skill", { name, projectPath });
+    return invoke<SkillCommandResult>("create_skill", {
+      name,
+      projectPath,
+      profile,
+    });
   }
 
   static async installSkill(
@@ -1,6 +1,11 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
+  AgentStats,
+  CalendarSettings,
+  CustomTimeRange,
+  ModelComparison,
   ModelStats,
+  SummaryComparison,
   SummaryStats,
   TimeRange,
   TokenStats,
@@ -13,21 +18,79 @@ export class StatsBridge {
   /**
    * Get summary statistics for a time range
    */
-  static async getSummaryStats(timeRange: TimeRange): Promise<SummaryStats> {
-    return invoke<SummaryStats>("get_summary_stats", { timeRange });
+  static async getSummaryStats(
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
+  ): Promise<SummaryStats> {
+    return invoke<SummaryStats>("get_summary_stats", { timeRange, agent });
   }
 
   /**
    * Get model usage statistics for a time range
    */
-  static async getModelStats(timeRange: TimeRange): Promise<ModelStats[]> {
-    return invoke<ModelStats[]>("get_model_stats", { timeRange });
+  static async getModelStats(
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
+  ): Promise<ModelStats[]> {
+    return invoke<ModelStats[]>("get_model_stats", { timeRange, agent });
   }
 
   /**
    * Get token statistics by model for a time range
    */
-  static async getTokenStats(timeRange: TimeRange): Promise<TokenStats[]> {
-    return invoke<TokenStats[]>("get_token_stats", { timeRange });
+  static async getTokenStats(
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
+  ): Promise<TokenStats[]> {
+    return invoke<TokenStats[]>("get_token_stats", { timeRange, agent });
+  }
+
+  /**
+   * Get per-agent usage statistics (Claude Code vs Codex) for a time range
+   */
+  static async getAgentStats(
+    timeRange: TimeRange | CustomTimeRange,
+  ): Promise<AgentStats[]> {
+    return invoke<AgentStats[]>("get_agent_stats", { timeRange });
+  }
+
+  /**
+   * Get summary statistics against the previous period
+   */
+  static async getSummaryComparison(
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
+  ): Promise<SummaryComparison> {
+    return invoke<SummaryComparison>("get_summary_comparison", {
+      timeRange,
+      agent,
+    });
+  }
+
+  /**
+   * Get model usage statistics against the previous period
+   */
+  static async getModelComparison(
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
+  ): Promise<ModelComparison[]> {
+    return invoke<ModelComparison[]>("get_model_comparison", {
+      timeRange,
+      agent,
+    });
+  }
+
+  /**
+   * Get the timezone and week start used for date buckets
+   */
+  static async getCalendarSettings(): Promise<CalendarSettings> {
+    return invoke<CalendarSettings>("get_calendar_settings");
+  }
+
+  /**
+   * Set the timezone and week start; unset fields restore the defaults
+   */
+  static async setCalendarSettings(settings: CalendarSettings): Promise<void> {
+    return invoke<void>("set_calendar_settings", { settings });
   }
 }
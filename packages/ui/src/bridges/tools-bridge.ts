@@ -1,7 +1,9 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
   CodeEditLanguageStats,
+  CustomTimeRange,
   TimeRange,
+  ToolComparison,
   ToolTrend,
   ToolUsageStats,
 } from "../generated/typeshare-types";
@@ -11,20 +13,39 @@ import type {
  */
 export class ToolsBridge {
   static async getToolUsageStats(
-    timeRange: TimeRange,
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
   ): Promise<ToolUsageStats[]> {
-    return invoke<ToolUsageStats[]>("get_tool_usage_stats", { timeRange });
+    return invoke<ToolUsageStats[]>("get_tool_usage_stats", {
+      timeRange,
+      agent,
+    });
   }
 
   static async getCodeEditByLanguage(
-    timeRange: TimeRange,
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
   ): Promise<CodeEditLanguageStats[]> {
     return invoke<CodeEditLanguageStats[]>("get_code_edit_by_language", {
       timeRange,
+      agent,
     });
   }
 
-  static async getToolTrends(timeRange: TimeRange): Promise<ToolTrend[]> {
-    return invoke<ToolTrend[]>("get_tool_trends", { timeRange });
+  static async getToolTrends(
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
+  ): Promise<ToolTrend[]> {
+    return invoke<ToolTrend[]>("get_tool_trends", { timeRange, agent });
+  }
+
+  static async getToolComparison(
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
+  ): Promise<ToolComparison[]> {
+    return invoke<ToolComparison[]>("get_tool_comparison", {
+      timeRange,
+      agent,
+    });
   }
 }
@@ -2,8 +2,10 @@ import { invoke } from "@tauri-apps/api/core";
 import type {
   CostByModelTrend,
   CostEfficiencyTrend,
+  CustomTimeRange,
   TimeRange,
   UsageTrend,
+  UsageTrendComparison,
 } from "../generated/typeshare-types";
 
 /**
@@ -13,23 +15,44 @@ export class TrendsBridge {
   /**
    * Get usage trends for a time range
    */
-  static async getUsageTrends(timeRange: TimeRange): Promise<UsageTrend[]> {
-    return invoke<UsageTrend[]>("get_usage_trends", { timeRange });
+  static async getUsageTrends(
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
+  ): Promise<UsageTrend[]> {
+    return invoke<UsageTrend[]>("get_usage_trends", { timeRange, agent });
   }
 
   static async getCostByModelTrends(
-    timeRange: TimeRange,
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
   ): Promise<CostByModelTrend[]> {
     return invoke<CostByModelTrend[]>("get_cost_by_model_trends", {
       timeRange,
+      agent,
     });
   }
 
   static async getCostEfficiencyTrend(
-    timeRange: TimeRange,
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
   ): Promise<CostEfficiencyTrend[]> {
     return invoke<CostEfficiencyTrend[]>("get_cost_efficiency_trend", {
       timeRange,
+      agent,
+    });
+  }
+
+  /**
+   * Get usage trends with the previous period's buckets aligned, for
+   * overlay charts
+   */
+  static async getUsageTrendComparison(
+    timeRange: TimeRange | CustomTimeRange,
+    agent?: string,
+  ): Promise<UsageTrendComparison[]> {
+    return invoke<UsageTrendComparison[]>("get_usage_trend_comparison", {
+      timeRange,
+      agent,
     });
   }
 }
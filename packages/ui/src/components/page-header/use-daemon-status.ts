@@ -1,7 +1,7 @@
 "use client";
 
 import { useQuery } from "@tanstack/react-query";
-import { invoke } from "@tauri-apps/api/core";
+import { DaemonBridge } from "@/bridges/daemon-bridge";
 
 export type DaemonStatus = "connected" | "disconnected";
 
@@ -12,7 +12,8 @@ export function useDaemonStatus(): DaemonStatus {
     queryKey: ["daemon-status"],
     queryFn: async () => {
       try {
-        return await invoke<boolean>("get_daemon_status");
+        const status = await DaemonBridge.getStatus();
+        return status.connected;
       } catch {
         return false;
       }
@@ -22,7 +22,16 @@ const ROWS = 7;
 const CELL_SIZE = 10;
 const GAP = 3;
 const LABEL_WIDTH = 20;
-const DAY_LABELS = ["", "Mon", "", "Wed", "", "Fri", ""];
+const WEEKDAYS = [
+  "sunday",
+  "monday",
+  "tuesday",
+  "wednesday",
+  "thursday",
+  "friday",
+  "saturday",
+];
+const DAY_NAMES = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
 const MONTH_NAMES = [
   "Jan",
   "Feb",
@@ -38,6 +47,27 @@ const MONTH_NAMES = [
   "Dec",
 ];
 
+/**
+ * Today in `timeZone` (the browser's when unset), as UTC midnight so that
+ * stepping through days is not affected by DST
+ */
+function todayIn(timeZone?: string | null): Date {
+  const format = (zone?: string) =>
+    new Intl.DateTimeFormat("en-CA", {
+      timeZone: zone,
+      year: "numeric",
+      month: "2-digit",
+      day: "2-digit",
+    }).format(new Date());
+  let today: string;
+  try {
+    today = format(timeZone ?? undefined);
+  } catch {
+    today = format();
+  }
+  return new Date(`${today}T00:00:00Z`);
+}
+
 function getLevel(count: number, max: number): number {
   if (count === 0) return 0;
   const ratio = count / Math.max(max, 1);
@@ -48,7 +78,7 @@ function getLevel(count: number, max: number): number {
 }
 
 export function ActivityHeatmap() {
-  const { data, isLoading, error, refetch } = useService();
+  const { data, calendar, isLoading, error, refetch } = useService();
   const [maxCols, setMaxCols] = useState<number | null>(null);
   const observerRef = useRef<ResizeObserver | null>(null);
 
@@ -73,17 +103,29 @@ export function ActivityHeatmap() {
     observerRef.current = observer;
   }, []);
 
+  // Rows follow the configured week start, Monday by default
+  const weekStart = Math.max(
+    WEEKDAYS.indexOf(calendar.weekStart?.toLowerCase() ?? "monday"),
+    0,
+  );
+  const dayLabels = Array.from({ length: ROWS }, (_, row) => {
+    const day = (weekStart + row) % 7;
+    return day % 2 === 1 && day < 6 ? DAY_NAMES[day] : "";
+  });
+
   const fullYear = useMemo(() => {
-    const now = new Date();
+    const now = todayIn(calendar.timezone);
     const map = new Map<string, number>();
     for (const d of data) {
       map.set(d.date, d.count);
     }
 
     const startDate = new Date(now);
-    startDate.setFullYear(startDate.getFullYear() - 1);
-    startDate.setDate(startDate.getDate() + 1);
-    startDate.setDate(startDate.getDate() - startDate.getDay());
+    startDate.setUTCFullYear(startDate.getUTCFullYear() - 1);
+    startDate.setUTCDate(startDate.getUTCDate() + 1);
+    startDate.setUTCDate(
+      startDate.getUTCDate() - ((startDate.getUTCDay() - weekStart + 7) % 7),
+    );
 
     const cells: Array<{
       date: string;
@@ -101,15 +143,15 @@ export function ActivityHeatmap() {
     let row = 0;
 
     while (currentDate <= now) {
-      const y = currentDate.getFullYear();
-      const m = String(currentDate.getMonth() + 1).padStart(2, "0");
-      const dd = String(currentDate.getDate()).padStart(2, "0");
+      const y = currentDate.getUTCFullYear();
+      const m = String(currentDate.getUTCMonth() + 1).padStart(2, "0");
+      const dd = String(currentDate.getUTCDate()).padStart(2, "0");
       const dateStr = `${y}-${m}-${dd}`;
       const count = map.get(dateStr) ?? 0;
       max = Math.max(max, count);
       total += count;
 
-      const month = currentDate.getMonth();
+      const month = currentDate.getUTCMonth();
       if (month !== lastMonth) {
         monthLabels.push({ label: MONTH_NAMES[month], col });
         lastMonth = month;
@@ -123,7 +165,7 @@ export function ActivityHeatmap() {
         col++;
       }
 
-      currentDate.setDate(currentDate.getDate() + 1);
+      currentDate.setUTCDate(currentDate.getUTCDate() + 1);
     }
 
     return {
@@ -133,7 +175,7 @@ export function ActivityHeatmap() {
       maxCount: max,
       totalSessions: total,
     };
-  }, [data]);
+  }, [data, calendar.timezone, weekStart]);
 
   const { visibleCells, visibleMonths, colCount, maxCount, totalSessions } =
     useMemo(() => {
@@ -218,7 +260,7 @@ export function ActivityHeatmap() {
                   className="flex flex-col gap-[3px]"
                   style={{ width: LABEL_WIDTH }}
                 >
-                  {DAY_LABELS.map((label, i) => (
+                  {dayLabels.map((label, i) => (
                     <span
                       key={i}
                       className="text-[10px] leading-none text-muted-foreground flex items-center justify-end"
@@ -2,17 +2,26 @@
 
 import { useQuery } from "@tanstack/react-query";
 import { AnalyticsBridge } from "@/bridges/analytics-bridge";
+import { StatsBridge } from "@/bridges/stats-bridge";
+import type { CalendarSettings } from "@/generated/typeshare-types";
 import { foregroundRefreshQueryOptions } from "@/lib/query-options";
 
+const DEFAULT_CALENDAR: CalendarSettings = {};
+
 export function useService() {
   const { data, isLoading, error, refetch } = useQuery({
     ...foregroundRefreshQueryOptions,
     queryKey: ["activity-heatmap"],
     queryFn: () => AnalyticsBridge.getActivityHeatmap(),
   });
+  const { data: calendar } = useQuery({
+    queryKey: ["calendar-settings"],
+    queryFn: () => StatsBridge.getCalendarSettings(),
+  });
 
   return {
     data: data ?? [],
+    calendar: calendar ?? DEFAULT_CALENDAR,
     isLoading,
     error: error as Error | null,
     refetch,
@@ -18,6 +18,7 @@ const LIVE_FOLLOW_THRESHOLD_PX = 200;
 interface SessionFileChangedPayload {
   sessionId: string;
   filePath: string;
+  profile: string;
 }
 
 const ACTIVE_TIMEOUT_MS = 8_000;
@@ -35,6 +35,18 @@ export const HOOK_EVENT_CONFIG = [
     description: "A subagent finishes its work",
     category: "Session",
   },
+  {
+    key: "Budget",
+    label: "Budget Alerts",
+    description: "Spend reaches 50%, 80% or 100% of a budget",
+    category: "Usage",
+  },
+  {
+    key: "Anomaly",
+    label: "Anomalies",
+    description: "Spend spikes, error bursts, retry storms or costly sessions",
+    category: "Usage",
+  },
 ] as const;
 
 export const NOTIFICATION_ACTION_OPTIONS = [
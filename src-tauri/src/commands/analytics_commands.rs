@@ -7,17 +7,19 @@ use tauri::{command, AppHandle, Manager};
 
 use crate::services::AnalyticsService;
 use crate::types::{
-    ActivityDay, CacheHitTrend, ErrorRateStats, HourlyActivity, SessionBucket, TimeRange,
+    ActivityDay, BreakdownRequest, BreakdownRow, CacheHitTrend, ErrorRateStats, HourlyActivity,
+    SessionBucket, TimeRangeFilter,
 };
 
 /// Get hourly activity distribution
 #[command]
 pub async fn get_hourly_activity(
     app_handle: AppHandle,
-    time_range: TimeRange,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
 ) -> Result<Vec<HourlyActivity>, String> {
     let pool = app_handle.state::<SqlitePool>();
-    AnalyticsService::get_hourly_activity(&pool, time_range)
+    AnalyticsService::get_hourly_activity(&pool, time_range, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
@@ -26,10 +28,11 @@ pub async fn get_hourly_activity(
 #[command]
 pub async fn get_session_length_distribution(
     app_handle: AppHandle,
-    time_range: TimeRange,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
 ) -> Result<Vec<SessionBucket>, String> {
     let pool = app_handle.state::<SqlitePool>();
-    AnalyticsService::get_session_length_distribution(&pool, time_range)
+    AnalyticsService::get_session_length_distribution(&pool, time_range, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
@@ -38,10 +41,11 @@ pub async fn get_session_length_distribution(
 #[command]
 pub async fn get_error_rate(
     app_handle: AppHandle,
-    time_range: TimeRange,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
 ) -> Result<ErrorRateStats, String> {
     let pool = app_handle.state::<SqlitePool>();
-    AnalyticsService::get_error_rate(&pool, time_range)
+    AnalyticsService::get_error_rate(&pool, time_range, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
@@ -50,19 +54,37 @@ pub async fn get_error_rate(
 #[command]
 pub async fn get_cache_hit_trend(
     app_handle: AppHandle,
-    time_range: TimeRange,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
 ) -> Result<Vec<CacheHitTrend>, String> {
     let pool = app_handle.state::<SqlitePool>();
-    AnalyticsService::get_cache_hit_trend(&pool, time_range)
+    AnalyticsService::get_cache_hit_trend(&pool, time_range, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
 
 /// Get activity heatmap data (last 90 days)
 #[command]
-pub async fn get_activity_heatmap(app_handle: AppHandle) -> Result<Vec<ActivityDay>, String> {
+pub async fn get_activity_heatmap(
+    app_handle: AppHandle,
+    agent: Option<String>,
+) -> Result<Vec<ActivityDay>, String> {
+    let pool = app_handle.state::<SqlitePool>();
+    AnalyticsService::get_activity_heatmap(&pool, agent.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Run a breakdown query: any measures split by any dimensions
+#[command]
+pub async fn run_breakdown_query(
+    app_handle: AppHandle,
+    time_range: TimeRangeFilter,
+    request: BreakdownRequest,
+    agent: Option<String>,
+) -> Result<Vec<BreakdownRow>, String> {
     let pool = app_handle.state::<SqlitePool>();
-    AnalyticsService::get_activity_heatmap(&pool)
+    AnalyticsService::run_breakdown_query(&pool, time_range, request, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
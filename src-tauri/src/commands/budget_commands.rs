@@ -0,0 +1,81 @@
+//! Budget commands
+//!
+//! IPC handlers for spend budgets and forecasts.
+
+use sqlx::SqlitePool;
+use tauri::{command, AppHandle, Manager};
+
+use crate::services::BudgetService;
+use crate::types::{
+    Budget, BudgetPeriod, BudgetRequest, BudgetStatus, BurnDownPoint, SpendForecast,
+};
+
+/// Get all budgets with their spend in the current period
+#[command]
+pub async fn get_budgets(app_handle: AppHandle) -> Result<Vec<BudgetStatus>, String> {
+    let pool = app_handle.state::<SqlitePool>();
+    BudgetService::list_budgets(&pool)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create a budget
+#[command]
+pub async fn create_budget(
+    app_handle: AppHandle,
+    request: BudgetRequest,
+) -> Result<Budget, String> {
+    let pool = app_handle.state::<SqlitePool>();
+    BudgetService::create_budget(&pool, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Update a budget
+#[command]
+pub async fn update_budget(
+    app_handle: AppHandle,
+    id: i32,
+    request: BudgetRequest,
+) -> Result<Budget, String> {
+    let pool = app_handle.state::<SqlitePool>();
+    BudgetService::update_budget(&pool, i64::from(id), request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a budget
+#[command]
+pub async fn delete_budget(app_handle: AppHandle, id: i32) -> Result<bool, String> {
+    let pool = app_handle.state::<SqlitePool>();
+    BudgetService::delete_budget(&pool, i64::from(id))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get cumulative spend across a budget's current period
+#[command]
+pub async fn get_budget_burn_down(
+    app_handle: AppHandle,
+    id: i32,
+) -> Result<Vec<BurnDownPoint>, String> {
+    let pool = app_handle.state::<SqlitePool>();
+    BudgetService::get_budget_burn_down(&pool, i64::from(id))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Forecast spend to the end of the current period, and the day it goes
+/// over `limit`
+#[command]
+pub async fn get_spend_forecast(
+    app_handle: AppHandle,
+    period: BudgetPeriod,
+    limit: Option<f64>,
+    agent: Option<String>,
+) -> Result<SpendForecast, String> {
+    let pool = app_handle.state::<SqlitePool>();
+    BudgetService::get_spend_forecast(&pool, period, limit, agent.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
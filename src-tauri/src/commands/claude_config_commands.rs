@@ -0,0 +1,106 @@
+//! Claude Code settings commands
+//!
+//! IPC handlers for the Claude profile registry, previewing Lumo's edits to
+//! Claude Code settings, restoring backups, the `lumo statusline` status
+//! line and the Lumo MCP server.
+
+use tauri::command;
+
+use crate::services::{ClaudeConfigService, ClaudeProfileService, ConfigService};
+use crate::types::{ClaudeProfileInfo, SettingsBackup, SettingsPreview};
+
+/// List the Claude config roots Lumo reads
+#[command]
+pub async fn list_claude_profiles() -> Result<Vec<ClaudeProfileInfo>, String> {
+    ClaudeProfileService::list_info().map_err(|e| e.to_string())
+}
+
+/// Register a Claude config root (a `CLAUDE_CONFIG_DIR`) and configure its
+/// settings for Lumo
+#[command]
+pub async fn add_claude_profile(
+    label: String,
+    path: String,
+) -> Result<Vec<ClaudeProfileInfo>, String> {
+    let profiles = ClaudeProfileService::add(&label, &path).map_err(|e| e.to_string())?;
+    if let Err(e) = ClaudeConfigService::ensure_otel_config() {
+        log::warn!("Failed to configure Claude Code OTEL: {}", e);
+    }
+    if let Err(e) = ClaudeConfigService::ensure_hooks_config() {
+        log::warn!("Failed to configure Claude Code hooks: {}", e);
+    }
+    Ok(profiles)
+}
+
+/// Unregister a Claude config root; its files are left untouched
+#[command]
+pub async fn remove_claude_profile(label: String) -> Result<Vec<ClaudeProfileInfo>, String> {
+    ClaudeProfileService::remove(&label).map_err(|e| e.to_string())
+}
+
+/// Diff of the changes Lumo would make to a Claude profile's settings.json
+/// (the primary profile's by default)
+#[command]
+pub async fn preview_claude_settings_changes(
+    profile: Option<String>,
+) -> Result<SettingsPreview, String> {
+    ClaudeConfigService::preview_changes(profile.as_deref()).map_err(|e| e.to_string())
+}
+
+/// Apply previewed changes; fails if the file changed since the preview
+#[command]
+pub async fn apply_claude_settings_changes(
+    profile: Option<String>,
+    fingerprint: String,
+) -> Result<(), String> {
+    ClaudeConfigService::apply_changes(profile.as_deref(), &fingerprint)
+        .map_err(|e| e.to_string())
+}
+
+/// List backups of Claude Code settings files, newest first
+#[command]
+pub async fn list_claude_settings_backups() -> Result<Vec<SettingsBackup>, String> {
+    ClaudeConfigService::list_backups().map_err(|e| e.to_string())
+}
+
+/// Restore a Claude Code settings file from a backup
+#[command]
+pub async fn restore_claude_settings_backup(file_name: String) -> Result<(), String> {
+    ClaudeConfigService::restore_backup(&file_name).map_err(|e| e.to_string())
+}
+
+/// Whether Claude Code shows Lumo's status line
+#[command]
+pub async fn get_claude_statusline_installed() -> Result<bool, String> {
+    ClaudeConfigService::is_statusline_installed().map_err(|e| e.to_string())
+}
+
+/// Install or remove Lumo's status line in Claude Code settings
+#[command]
+pub async fn set_claude_statusline(enabled: bool) -> Result<(), String> {
+    ClaudeConfigService::set_statusline(enabled).map_err(|e| e.to_string())
+}
+
+/// Whether Claude Code has Lumo's MCP server registered
+#[command]
+pub async fn get_claude_mcp_server_installed() -> Result<bool, String> {
+    ClaudeConfigService::is_mcp_server_installed().map_err(|e| e.to_string())
+}
+
+/// Register or remove Lumo's MCP server in Claude Code's config
+#[command]
+pub async fn set_claude_mcp_server(enabled: bool) -> Result<(), String> {
+    ClaudeConfigService::set_mcp_server(enabled).map_err(|e| e.to_string())
+}
+
+/// Daily spend target (USD) the status line compares today's spend with
+#[command]
+pub async fn get_daily_spend_target() -> Result<Option<f64>, String> {
+    ConfigService::get_daily_spend_target().map_err(|e| e.to_string())
+}
+
+/// Set or clear the daily spend target
+#[command]
+pub async fn set_daily_spend_target(target: Option<f64>) -> Result<(), String> {
+    ConfigService::set_daily_spend_target(target).map_err(|e| e.to_string())
+}
@@ -7,15 +7,22 @@ use crate::services::ClaudeSessionService;
 use crate::types::{ClaudeSessionDetail, ClaudeSessionPage};
 use tauri::{AppHandle, Manager};
 
-/// Get paginated Claude Code sessions for a project or all projects.
+/// Get paginated Claude Code sessions for a project or all projects,
+/// optionally limited to one Claude profile.
 #[tauri::command]
 pub fn get_claude_sessions_page(
     project_path: Option<String>,
+    profile: Option<String>,
     offset: usize,
     limit: usize,
 ) -> Result<ClaudeSessionPage, String> {
-    ClaudeSessionService::get_sessions_page(project_path.as_deref(), offset, limit)
-        .map_err(|e| e.to_string())
+    ClaudeSessionService::get_sessions_page(
+        project_path.as_deref(),
+        profile.as_deref(),
+        offset,
+        limit,
+    )
+    .map_err(|e| e.to_string())
 }
 
 /// Get Claude Code session detail with messages
@@ -1,8 +1,82 @@
-use tauri::command;
+use sqlx::SqlitePool;
+use tauri::{command, AppHandle, Manager};
 
-use crate::daemon::check_daemon_health;
+use crate::daemon::{check_daemon_health, fetch_daemon_status, DaemonManager};
+use crate::services::ClaudeConfigService;
+use crate::types::{DaemonAddressStatus, DaemonSignalStatus, DaemonStatus, DoctorReport};
 
+/// Get daemon connectivity and, when available, its ingest statistics
 #[command]
-pub async fn get_daemon_status() -> Result<bool, String> {
-    Ok(check_daemon_health().await.is_some())
+pub async fn get_daemon_status() -> Result<DaemonStatus, String> {
+    if let Some(status) = fetch_daemon_status().await {
+        return Ok(DaemonStatus {
+            connected: true,
+            version: Some(status.version),
+            uptime_seconds: Some(status.uptime_seconds as f64),
+            listeners: status.listeners,
+            signals: status
+                .ingest
+                .into_iter()
+                .map(|s| DaemonSignalStatus {
+                    signal: s.signal,
+                    last_ingest_at: s.last_ingest_at.map(|t| t as f64),
+                    requests: s.requests as f64,
+                    rows: s.rows as f64,
+                    quarantined: s.quarantined as f64,
+                    rejected: s.rejected as f64,
+                })
+                .collect(),
+            database_status: Some(status.database.status),
+            database_size_bytes: status.database.size_bytes.map(|b| b as f64),
+            wal_size_bytes: status.database.wal_size_bytes.map(|b| b as f64),
+            migration_version: status.database.migration_version.map(|v| v.to_string()),
+        });
+    }
+
+    // Older daemons only serve /health
+    let health = check_daemon_health().await;
+    Ok(DaemonStatus {
+        connected: health.is_some(),
+        version: health.map(|h| h.version),
+        uptime_seconds: None,
+        listeners: Vec::new(),
+        signals: Vec::new(),
+        database_status: None,
+        database_size_bytes: None,
+        wal_size_bytes: None,
+        migration_version: None,
+    })
+}
+
+/// Check that Claude Code settings and the running daemon match the
+/// configured daemon address
+#[command]
+pub async fn get_daemon_address_status() -> Result<DaemonAddressStatus, String> {
+    let listeners = fetch_daemon_status().await.map(|s| s.listeners);
+    ClaudeConfigService::check_address_drift(listeners.as_deref()).map_err(|e| e.to_string())
+}
+
+/// Change the daemon listen address: store it, point Claude Code at it and
+/// restart the daemon service
+#[command]
+pub async fn set_daemon_address(
+    app_handle: AppHandle,
+    address: String,
+) -> Result<DaemonAddressStatus, String> {
+    shared::daemon_address::set_server_address(&address).map_err(|e| e.to_string())?;
+    ClaudeConfigService::ensure_otel_config().map_err(|e| e.to_string())?;
+
+    let manager = DaemonManager::new(&app_handle).map_err(|e| e.to_string())?;
+    manager.restart().await.map_err(|e| e.to_string())?;
+
+    get_daemon_address_status().await
+}
+
+/// Run end-to-end diagnostics (`lumo-daemon doctor`): Claude Code settings
+/// and project overrides, listener, auth, an OTLP round trip and the database
+#[command]
+pub async fn run_daemon_doctor(app_handle: AppHandle) -> Result<DoctorReport, String> {
+    let roots = ClaudeConfigService::doctor_roots().map_err(|e| e.to_string())?;
+    let pool = app_handle.state::<SqlitePool>();
+    Ok(shared::doctor::run(&roots, Some(pool.inner())).await.into())
 }
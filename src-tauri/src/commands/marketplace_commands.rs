@@ -23,13 +23,15 @@ pub async fn list_marketplaces() -> Result<Vec<MarketplaceInfo>, String> {
         .map_err(|e| e.to_string())
 }
 
-/// Install a plugin from marketplace.
+/// Install a plugin from marketplace into a Claude profile (the primary one
+/// by default).
 #[command]
 pub async fn install_marketplace_plugin(
     name: String,
     project_path: Option<String>,
+    profile: Option<String>,
 ) -> Result<MarketplaceCommandResult, String> {
-    MarketplaceService::install_plugin(&name, project_path.as_deref())
+    MarketplaceService::install_plugin(&name, project_path.as_deref(), profile.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
@@ -39,29 +41,36 @@ pub async fn install_marketplace_plugin(
 pub async fn uninstall_marketplace_plugin(
     name: String,
     project_path: Option<String>,
+    profile: Option<String>,
 ) -> Result<MarketplaceCommandResult, String> {
-    MarketplaceService::uninstall_plugin(&name, project_path.as_deref())
+    MarketplaceService::uninstall_plugin(&name, project_path.as_deref(), profile.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
 
 /// Add a marketplace source (URL, path, or GitHub repo).
 #[command]
-pub async fn add_marketplace(source: String) -> Result<MarketplaceCommandResult, String> {
-    MarketplaceService::add_marketplace(&source)
+pub async fn add_marketplace(
+    source: String,
+    profile: Option<String>,
+) -> Result<MarketplaceCommandResult, String> {
+    MarketplaceService::add_marketplace(&source, profile.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
 
 /// Remove a configured marketplace by name.
 #[command]
-pub async fn remove_marketplace(name: String) -> Result<MarketplaceCommandResult, String> {
-    MarketplaceService::remove_marketplace(&name)
+pub async fn remove_marketplace(
+    name: String,
+    profile: Option<String>,
+) -> Result<MarketplaceCommandResult, String> {
+    MarketplaceService::remove_marketplace(&name, profile.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
 
-/// Update all marketplace sources.
+/// Update all marketplace sources in every Claude profile.
 #[command]
 pub async fn update_marketplaces() -> Result<MarketplaceCommandResult, String> {
     MarketplaceService::update_marketplaces()
@@ -1,4 +1,6 @@
 pub mod analytics_commands;
+pub mod budget_commands;
+pub mod claude_config_commands;
 pub mod claude_session_commands;
 pub mod daemon_commands;
 pub mod export_commands;
@@ -18,6 +20,8 @@ pub mod uninstall_commands;
 pub mod wrapped_commands;
 
 pub use analytics_commands::*;
+pub use budget_commands::*;
+pub use claude_config_commands::*;
 pub use claude_session_commands::*;
 pub use daemon_commands::*;
 pub use export_commands::*;
@@ -54,10 +58,16 @@ macro_rules! app_commands {
             commands::get_summary_stats,
             commands::get_model_stats,
             commands::get_token_stats,
+            commands::get_agent_stats,
+            commands::get_summary_comparison,
+            commands::get_model_comparison,
+            commands::get_calendar_settings,
+            commands::set_calendar_settings,
             // Trends commands
             commands::get_usage_trends,
             commands::get_cost_by_model_trends,
             commands::get_cost_efficiency_trend,
+            commands::get_usage_trend_comparison,
             // Projects commands
             commands::get_projects,
             commands::get_global_skill_count,
@@ -68,18 +78,44 @@ macro_rules! app_commands {
             commands::get_tool_usage_stats,
             commands::get_code_edit_by_language,
             commands::get_tool_trends,
+            commands::get_tool_comparison,
             // Analytics commands
             commands::get_hourly_activity,
             commands::get_session_length_distribution,
             commands::get_error_rate,
             commands::get_cache_hit_trend,
+            commands::run_breakdown_query,
             commands::get_activity_heatmap,
+            // Budget commands
+            commands::get_budgets,
+            commands::create_budget,
+            commands::update_budget,
+            commands::delete_budget,
+            commands::get_budget_burn_down,
+            commands::get_spend_forecast,
             // Wrapped commands
             commands::get_wrapped_data,
             // Export commands
             commands::save_image_to_path,
             // Daemon commands
             commands::get_daemon_status,
+            commands::get_daemon_address_status,
+            commands::set_daemon_address,
+            commands::run_daemon_doctor,
+            // Claude config commands
+            commands::list_claude_profiles,
+            commands::add_claude_profile,
+            commands::remove_claude_profile,
+            commands::preview_claude_settings_changes,
+            commands::apply_claude_settings_changes,
+            commands::list_claude_settings_backups,
+            commands::restore_claude_settings_backup,
+            commands::get_claude_statusline_installed,
+            commands::set_claude_statusline,
+            commands::get_claude_mcp_server_installed,
+            commands::set_claude_mcp_server,
+            commands::get_daily_spend_target,
+            commands::set_daily_spend_target,
             // Usage commands
             commands::get_usage_limits,
             commands::save_api_key,
@@ -118,6 +154,7 @@ macro_rules! app_commands {
             commands::uninstall_app,
             // System commands
             commands::open_log_directory,
+            commands::get_log_entries,
         ]
     };
 }
@@ -31,13 +31,15 @@ pub async fn update_skill(path: String, content: String) -> Result<SkillCommandR
         .map_err(|e| e.to_string())
 }
 
-/// Create a new skill. If project_path is provided, creates in project scope.
+/// Create a new skill. If project_path is provided, creates in project scope,
+/// otherwise in the given Claude profile (the primary one by default).
 #[command]
 pub async fn create_skill(
     name: String,
     project_path: Option<String>,
+    profile: Option<String>,
 ) -> Result<SkillCommandResult, String> {
-    SkillsService::create_skill(&name, project_path.as_deref())
+    SkillsService::create_skill(&name, project_path.as_deref(), profile.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
@@ -5,17 +5,21 @@
 use sqlx::SqlitePool;
 use tauri::{command, AppHandle, Manager};
 
-use crate::services::StatsService;
-use crate::types::{ModelStats, SummaryStats, TimeRange, TokenStats};
+use crate::services::{ConfigService, StatsService};
+use crate::types::{
+    AgentStats, CalendarSettings, ModelComparison, ModelStats, SummaryComparison, SummaryStats,
+    TimeRangeFilter, TokenStats,
+};
 
 /// Get summary statistics for a time range
 #[command]
 pub async fn get_summary_stats(
     app_handle: AppHandle,
-    time_range: TimeRange,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
 ) -> Result<SummaryStats, String> {
     let pool = app_handle.state::<SqlitePool>();
-    StatsService::get_summary(&pool, time_range)
+    StatsService::get_summary(&pool, time_range, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
@@ -24,10 +28,11 @@ pub async fn get_summary_stats(
 #[command]
 pub async fn get_model_stats(
     app_handle: AppHandle,
-    time_range: TimeRange,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
 ) -> Result<Vec<ModelStats>, String> {
     let pool = app_handle.state::<SqlitePool>();
-    StatsService::get_model_stats(&pool, time_range)
+    StatsService::get_model_stats(&pool, time_range, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
@@ -36,10 +41,61 @@ pub async fn get_model_stats(
 #[command]
 pub async fn get_token_stats(
     app_handle: AppHandle,
-    time_range: TimeRange,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
 ) -> Result<Vec<TokenStats>, String> {
     let pool = app_handle.state::<SqlitePool>();
-    StatsService::get_token_stats(&pool, time_range)
+    StatsService::get_token_stats(&pool, time_range, agent.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get per-agent usage statistics (Claude Code vs Codex) for a time range
+#[command]
+pub async fn get_agent_stats(
+    app_handle: AppHandle,
+    time_range: TimeRangeFilter,
+) -> Result<Vec<AgentStats>, String> {
+    let pool = app_handle.state::<SqlitePool>();
+    StatsService::get_agent_stats(&pool, time_range)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Timezone and week start used for every date bucket
+#[command]
+pub async fn get_calendar_settings() -> Result<CalendarSettings, String> {
+    ConfigService::get_calendar_settings().map_err(|e| e.to_string())
+}
+
+/// Set the timezone and week start; unset fields restore the defaults
+#[command]
+pub async fn set_calendar_settings(settings: CalendarSettings) -> Result<(), String> {
+    ConfigService::set_calendar_settings(settings).map_err(|e| e.to_string())
+}
+
+/// Get summary statistics against the previous period
+#[command]
+pub async fn get_summary_comparison(
+    app_handle: AppHandle,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
+) -> Result<SummaryComparison, String> {
+    let pool = app_handle.state::<SqlitePool>();
+    StatsService::get_summary_comparison(&pool, time_range, agent.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get model usage statistics against the previous period
+#[command]
+pub async fn get_model_comparison(
+    app_handle: AppHandle,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
+) -> Result<Vec<ModelComparison>, String> {
+    let pool = app_handle.state::<SqlitePool>();
+    StatsService::get_model_comparison(&pool, time_range, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
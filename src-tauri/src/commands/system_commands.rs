@@ -4,6 +4,9 @@
 
 use tauri::{command, AppHandle, Manager};
 
+use crate::services::LogService;
+use crate::types::{LogEntry, LogQuery};
+
 /// Open the app log directory in the system file manager
 #[command]
 pub async fn open_log_directory(app_handle: AppHandle) -> Result<(), String> {
@@ -38,3 +41,21 @@ pub async fn open_log_directory(app_handle: AppHandle) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Get recent daemon and app log entries, filtered by source, level, time
+/// range and search text
+#[command]
+pub async fn get_log_entries(
+    app_handle: AppHandle,
+    query: LogQuery,
+) -> Result<Vec<LogEntry>, String> {
+    let log_dir = app_handle
+        .path()
+        .app_log_dir()
+        .map_err(|e| e.to_string())?;
+    // Reading and filtering the files is blocking IO
+    tokio::task::spawn_blocking(move || LogService::get_entries(&log_dir, &query))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
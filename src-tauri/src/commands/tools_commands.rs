@@ -6,16 +6,19 @@ use sqlx::SqlitePool;
 use tauri::{command, AppHandle, Manager};
 
 use crate::services::ToolsService;
-use crate::types::{CodeEditLanguageStats, TimeRange, ToolTrend, ToolUsageStats};
+use crate::types::{
+    CodeEditLanguageStats, TimeRangeFilter, ToolComparison, ToolTrend, ToolUsageStats,
+};
 
 /// Get tool usage statistics
 #[command]
 pub async fn get_tool_usage_stats(
     app_handle: AppHandle,
-    time_range: TimeRange,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
 ) -> Result<Vec<ToolUsageStats>, String> {
     let pool = app_handle.state::<SqlitePool>();
-    ToolsService::get_tool_usage_stats(&pool, time_range)
+    ToolsService::get_tool_usage_stats(&pool, time_range, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
@@ -24,10 +27,11 @@ pub async fn get_tool_usage_stats(
 #[command]
 pub async fn get_code_edit_by_language(
     app_handle: AppHandle,
-    time_range: TimeRange,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
 ) -> Result<Vec<CodeEditLanguageStats>, String> {
     let pool = app_handle.state::<SqlitePool>();
-    ToolsService::get_code_edit_by_language(&pool, time_range)
+    ToolsService::get_code_edit_by_language(&pool, time_range, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
@@ -36,10 +40,24 @@ pub async fn get_code_edit_by_language(
 #[command]
 pub async fn get_tool_trends(
     app_handle: AppHandle,
-    time_range: TimeRange,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
 ) -> Result<Vec<ToolTrend>, String> {
     let pool = app_handle.state::<SqlitePool>();
-    ToolsService::get_tool_trends(&pool, time_range)
+    ToolsService::get_tool_trends(&pool, time_range, agent.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get tool usage statistics against the previous period
+#[command]
+pub async fn get_tool_comparison(
+    app_handle: AppHandle,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
+) -> Result<Vec<ToolComparison>, String> {
+    let pool = app_handle.state::<SqlitePool>();
+    ToolsService::get_tool_comparison(&pool, time_range, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
@@ -6,16 +6,19 @@ use sqlx::SqlitePool;
 use tauri::{command, AppHandle, Manager};
 
 use crate::services::TrendsService;
-use crate::types::{CostByModelTrend, CostEfficiencyTrend, TimeRange, UsageTrend};
+use crate::types::{
+    CostByModelTrend, CostEfficiencyTrend, TimeRangeFilter, UsageTrend, UsageTrendComparison,
+};
 
 /// Get usage trends for a time range
 #[command]
 pub async fn get_usage_trends(
     app_handle: AppHandle,
-    time_range: TimeRange,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
 ) -> Result<Vec<UsageTrend>, String> {
     let pool = app_handle.state::<SqlitePool>();
-    TrendsService::get_usage_trends(&pool, time_range)
+    TrendsService::get_usage_trends(&pool, time_range, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
@@ -24,10 +27,11 @@ pub async fn get_usage_trends(
 #[command]
 pub async fn get_cost_efficiency_trend(
     app_handle: AppHandle,
-    time_range: TimeRange,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
 ) -> Result<Vec<CostEfficiencyTrend>, String> {
     let pool = app_handle.state::<SqlitePool>();
-    TrendsService::get_cost_efficiency_trend(&pool, time_range)
+    TrendsService::get_cost_efficiency_trend(&pool, time_range, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
@@ -36,10 +40,24 @@ pub async fn get_cost_efficiency_trend(
 #[command]
 pub async fn get_cost_by_model_trends(
     app_handle: AppHandle,
-    time_range: TimeRange,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
 ) -> Result<Vec<CostByModelTrend>, String> {
     let pool = app_handle.state::<SqlitePool>();
-    TrendsService::get_cost_by_model_trends(&pool, time_range)
+    TrendsService::get_cost_by_model_trends(&pool, time_range, agent.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get usage trends with the previous period's buckets aligned for overlays
+#[command]
+pub async fn get_usage_trend_comparison(
+    app_handle: AppHandle,
+    time_range: TimeRangeFilter,
+    agent: Option<String>,
+) -> Result<Vec<UsageTrendComparison>, String> {
+    let pool = app_handle.state::<SqlitePool>();
+    TrendsService::get_usage_trend_comparison(&pool, time_range, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
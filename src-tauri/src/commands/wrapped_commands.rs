@@ -13,9 +13,10 @@ use crate::types::{WrappedData, WrappedPeriod};
 pub async fn get_wrapped_data(
     app_handle: AppHandle,
     period: WrappedPeriod,
+    agent: Option<String>,
 ) -> Result<WrappedData, String> {
     let pool = app_handle.state::<SqlitePool>();
-    WrappedService::get_wrapped_data(&pool, period)
+    WrappedService::get_wrapped_data(&pool, period, agent.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
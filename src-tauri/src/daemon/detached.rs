@@ -0,0 +1,95 @@
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use shared::daemon_process::DaemonProcess;
+
+use super::health::check_daemon_health;
+
+/// How often the supervisor checks the detached daemon
+const SUPERVISE_INTERVAL: Duration = Duration::from_secs(10);
+/// A daemon that stayed up this long resets the crash backoff
+const STABLE_AFTER: Duration = Duration::from_secs(60);
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Start the daemon detached unless the recorded one is still running.
+pub async fn start_service(binary_path: &Path) -> Result<()> {
+    let process = DaemonProcess::new()?;
+    if let Some(pid) = process.running_pid() {
+        log::info!("Detached daemon already running (pid {})", pid);
+        return Ok(());
+    }
+    let pid = process
+        .spawn(binary_path)
+        .context("Failed to spawn detached daemon")?;
+    log::info!(
+        "Started detached daemon (pid {}), logs in {}",
+        pid,
+        process.log_dir.display()
+    );
+    Ok(())
+}
+
+/// Stop the detached daemon if there is one. Ignores a missing pidfile.
+pub async fn stop_service() -> Result<()> {
+    let process = DaemonProcess::new()?;
+    if !process.is_managed() {
+        return Ok(());
+    }
+    let stopped = tokio::task::spawn_blocking(move || process.stop(Duration::from_secs(5)))
+        .await
+        .context("Detached daemon stop task failed")??;
+    if stopped {
+        log::info!("Stopped detached daemon");
+    }
+    Ok(())
+}
+
+/// Restart the detached daemon when it dies, with exponential backoff
+/// between crashes. Does nothing while the daemon is managed by systemd
+/// (no pidfile) or stopped on purpose (pidfile removed).
+pub async fn supervise(binary_path: &Path) {
+    let mut crashes: u32 = 0;
+    let mut started_at = Instant::now();
+
+    loop {
+        tokio::time::sleep(SUPERVISE_INTERVAL).await;
+
+        let Ok(process) = DaemonProcess::new() else {
+            continue;
+        };
+        if !process.is_managed() || process.running_pid().is_some() {
+            if started_at.elapsed() >= STABLE_AFTER {
+                crashes = 0;
+            }
+            continue;
+        }
+        // Something else (e.g. a manually started daemon) is serving
+        if check_daemon_health().await.is_some() {
+            continue;
+        }
+
+        let backoff = SUPERVISE_INTERVAL
+            .saturating_mul(2u32.saturating_pow(crashes))
+            .min(MAX_BACKOFF);
+        crashes = crashes.saturating_add(1);
+        log::warn!(
+            "Detached daemon exited (pid {:?}); restarting in {}s",
+            process.read_pid(),
+            backoff.as_secs()
+        );
+        tokio::time::sleep(backoff).await;
+
+        // Stopped on purpose while we waited
+        if !process.is_managed() {
+            continue;
+        }
+        match process.spawn(binary_path) {
+            Ok(pid) => {
+                started_at = Instant::now();
+                log::info!("Restarted detached daemon (pid {})", pid);
+            }
+            Err(e) => log::error!("Failed to restart detached daemon: {}", e),
+        }
+    }
+}
@@ -1,14 +1,19 @@
 use std::time::Duration;
 
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
 
-const DEFAULT_DAEMON_ADDR: &str = "127.0.0.1:4318";
 const HEALTH_TIMEOUT: Duration = Duration::from_secs(2);
 
-fn daemon_addr() -> String {
-    std::env::var("LUMO_SERVER_ADDRESS").unwrap_or_else(|_| DEFAULT_DAEMON_ADDR.to_string())
+/// Address to reach the daemon at, from the shared daemon config.
+/// Read on every call so an address change takes effect without a restart.
+fn daemon_addr() -> Option<String> {
+    let address = shared::daemon_address::server_address().ok()?;
+    shared::daemon_address::client_address(&address)
+        .ok()
+        .map(|addr| addr.to_string())
 }
 
 #[derive(Debug, Deserialize)]
@@ -18,16 +23,55 @@ pub struct HealthResponse {
     pub version: String,
 }
 
+/// Daemon status from GET /v1/status
+#[derive(Debug, Deserialize)]
+pub struct StatusResponse {
+    pub version: String,
+    pub uptime_seconds: i64,
+    pub listeners: Vec<String>,
+    pub ingest: Vec<SignalStatusResponse>,
+    pub database: DatabaseStatusResponse,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SignalStatusResponse {
+    pub signal: String,
+    pub last_ingest_at: Option<i64>,
+    pub requests: u64,
+    pub rows: u64,
+    pub quarantined: u64,
+    pub rejected: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DatabaseStatusResponse {
+    pub status: String,
+    pub size_bytes: Option<u64>,
+    pub wal_size_bytes: Option<u64>,
+    pub migration_version: Option<i64>,
+}
+
 /// Send a GET /health request to the daemon and parse the response.
 /// Returns None if the daemon is not reachable.
 pub async fn check_daemon_health() -> Option<HealthResponse> {
+    get_json("/health").await
+}
+
+/// Send a GET /v1/status request to the daemon and parse the response.
+/// Returns None if the daemon is not reachable or predates the endpoint.
+pub async fn fetch_daemon_status() -> Option<StatusResponse> {
+    get_json("/v1/status").await
+}
+
+/// GET a JSON document from the daemon over a raw HTTP/1.1 connection
+async fn get_json<T: DeserializeOwned>(path: &str) -> Option<T> {
     tokio::time::timeout(HEALTH_TIMEOUT, async {
-        let addr = daemon_addr();
+        let addr = daemon_addr()?;
         let mut stream = TcpStream::connect(&addr).await.ok()?;
 
         let request = format!(
-            "GET /health HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
-            addr
+            "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+            path, addr
         );
         stream.write_all(request.as_bytes()).await.ok()?;
 
@@ -39,7 +83,7 @@ pub async fn check_daemon_health() -> Option<HealthResponse> {
         // Find JSON body after the blank line
         let body = response_str.split("\r\n\r\n").nth(1)?;
 
-        serde_json::from_str::<HealthResponse>(body).ok()
+        serde_json::from_str::<T>(body).ok()
     })
     .await
     .ok()
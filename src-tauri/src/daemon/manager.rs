@@ -12,6 +12,9 @@ const EXPECTED_VERSION: &str = env!("DAEMON_VERSION");
 /// Daemon binary name.
 const DAEMON_BINARY: &str = "lumo-daemon";
 
+/// CLI binary name, installed next to the daemon for `lumo statusline`.
+const CLI_BINARY: &str = "lumo";
+
 pub struct DaemonManager {
     /// ~/.lumo/bin/lumo-daemon
     binary_path: PathBuf,
@@ -21,12 +24,16 @@ pub struct DaemonManager {
     service_file_path: PathBuf,
     /// Platform-specific log directory:
     /// - macOS: ~/Library/Logs/com.lumo.daemon/
-    /// - Linux: ~/.lumo/logs/
+    /// - Linux: ~/.lumo/logs/ (systemd unit and detached fallback alike)
     log_dir: PathBuf,
     /// User home directory
     home_dir: PathBuf,
     /// Source binary path (from app bundle or dev target dir)
     source_binary: PathBuf,
+    /// ~/.lumo/bin/lumo
+    cli_binary_path: PathBuf,
+    /// Source CLI binary, if bundled (or built in dev)
+    source_cli_binary: Option<PathBuf>,
 }
 
 impl DaemonManager {
@@ -34,10 +41,12 @@ impl DaemonManager {
         let home_dir = dirs::home_dir().context("Could not determine home directory")?;
 
         let binary_path = home_dir.join(".lumo/bin").join(DAEMON_BINARY);
+        let cli_binary_path = home_dir.join(".lumo/bin").join(CLI_BINARY);
 
         let (service_file_path, log_dir) = Self::platform_paths(&home_dir);
 
-        let source_binary = Self::resolve_source_binary(app_handle)?;
+        let source_binary = Self::resolve_source_binary(app_handle, DAEMON_BINARY)?;
+        let source_cli_binary = Self::resolve_source_binary(app_handle, CLI_BINARY).ok();
 
         Ok(Self {
             binary_path,
@@ -45,6 +54,8 @@ impl DaemonManager {
             log_dir,
             home_dir,
             source_binary,
+            cli_binary_path,
+            source_cli_binary,
         })
     }
 
@@ -83,6 +94,10 @@ impl DaemonManager {
         if let Some(health) = check_daemon_health().await {
             if health.version == EXPECTED_VERSION {
                 log::info!("Daemon already running (v{})", health.version);
+                // Installs from before the CLI was bundled lack it
+                if !self.cli_binary_path.exists() {
+                    self.install_cli_binary();
+                }
                 return Ok(());
             }
             // Version mismatch — upgrade.
@@ -99,7 +114,7 @@ impl DaemonManager {
             // Binary exists but service is not running — try to load.
             log::info!("Daemon binary found but not running. Starting...");
             self.install_service_file()?;
-            Self::start_service(&self.service_file_path).await?;
+            self.start_service().await?;
             return self.wait_for_health().await;
         }
 
@@ -108,6 +123,25 @@ impl DaemonManager {
         self.install().await
     }
 
+    /// Restart the service so the daemon picks up configuration changes
+    /// (e.g. a new listen address in ~/.lumo/daemon.json).
+    pub async fn restart(&self) -> Result<()> {
+        self.stop_service().await?;
+        self.install_service_file()?;
+        self.start_service().await?;
+        self.wait_for_health().await
+    }
+
+    /// Keep the daemon running for as long as the app runs. Only the Linux
+    /// detached fallback needs this; launchd and systemd restart the daemon
+    /// themselves.
+    pub async fn supervise(self) {
+        #[cfg(target_os = "linux")]
+        {
+            super::detached::supervise(&self.binary_path).await
+        }
+    }
+
     /// Full install: copy binary, create service file, start service.
     async fn install(&self) -> Result<()> {
         self.do_install().await
@@ -116,11 +150,11 @@ impl DaemonManager {
     /// Upgrade: stop service, replace binary, restart.
     /// If installation fails, attempt to reload the old service.
     async fn upgrade(&self) -> Result<()> {
-        Self::stop_service(&self.service_file_path).await?;
+        self.stop_service().await?;
 
         if let Err(e) = self.do_install().await {
             log::error!("Upgrade failed, reloading old service: {}", e);
-            let _ = Self::start_service(&self.service_file_path).await;
+            let _ = self.start_service().await;
             return Err(e);
         }
 
@@ -131,8 +165,9 @@ impl DaemonManager {
     async fn do_install(&self) -> Result<()> {
         self.ensure_directories()?;
         self.install_binary()?;
+        self.install_cli_binary();
         self.install_service_file()?;
-        Self::start_service(&self.service_file_path).await?;
+        self.start_service().await?;
         self.wait_for_health().await
     }
 
@@ -156,6 +191,37 @@ impl DaemonManager {
         Ok(())
     }
 
+    /// Copy the `lumo` CLI to ~/.lumo/bin/. Only the status line needs it,
+    /// so failures are logged rather than failing the daemon install.
+    fn install_cli_binary(&self) {
+        let Some(source) = &self.source_cli_binary else {
+            log::warn!("CLI binary not found; `lumo statusline` will be unavailable");
+            return;
+        };
+        if let Err(e) = std::fs::copy(source, &self.cli_binary_path) {
+            log::warn!(
+                "Failed to copy {} -> {}: {}",
+                source.display(),
+                self.cli_binary_path.display(),
+                e
+            );
+            return;
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if let Err(e) = std::fs::set_permissions(
+                &self.cli_binary_path,
+                std::fs::Permissions::from_mode(0o755),
+            ) {
+                log::warn!("Failed to make the CLI binary executable: {}", e);
+            }
+        }
+
+        log::info!("Installed CLI binary to {}", self.cli_binary_path.display());
+    }
+
     /// Write (or overwrite) the platform-specific service file.
     fn install_service_file(&self) -> Result<()> {
         let content = self.render_service_file();
@@ -189,41 +255,49 @@ impl DaemonManager {
     }
 
     /// Start the daemon service (platform-specific).
-    async fn start_service(_service_file_path: &PathBuf) -> Result<()> {
+    ///
+    /// On Linux without a usable systemd user instance the daemon is spawned
+    /// detached instead, and `supervise` restarts it if it dies.
+    async fn start_service(&self) -> Result<()> {
         #[cfg(target_os = "macos")]
         {
-            super::plist::load_service(_service_file_path).await
+            super::plist::load_service(&self.service_file_path).await
         }
 
         #[cfg(target_os = "linux")]
         {
-            let _ = _service_file_path;
-            super::systemd::start_service().await
+            if super::systemd::is_available().await {
+                match super::systemd::start_service().await {
+                    Ok(()) => return Ok(()),
+                    Err(e) => log::warn!("{}; starting the daemon detached instead", e),
+                }
+            } else {
+                log::info!("No systemd user instance; starting the daemon detached");
+            }
+            super::detached::start_service(&self.binary_path).await
         }
 
         #[cfg(target_os = "windows")]
         {
-            let _ = _service_file_path;
             anyhow::bail!("Windows daemon management is not yet supported")
         }
     }
 
     /// Stop the daemon service (platform-specific).
-    async fn stop_service(_service_file_path: &PathBuf) -> Result<()> {
+    async fn stop_service(&self) -> Result<()> {
         #[cfg(target_os = "macos")]
         {
-            super::plist::unload_service(_service_file_path).await
+            super::plist::unload_service(&self.service_file_path).await
         }
 
         #[cfg(target_os = "linux")]
         {
-            let _ = _service_file_path;
-            super::systemd::stop_service().await
+            super::systemd::stop_service().await?;
+            super::detached::stop_service().await
         }
 
         #[cfg(target_os = "windows")]
         {
-            let _ = _service_file_path;
             anyhow::bail!("Windows daemon management is not yet supported")
         }
     }
@@ -252,17 +326,17 @@ impl DaemonManager {
         }
     }
 
-    /// Locate daemon binary: bundled resource (production) or target dir (dev).
-    fn resolve_source_binary(app_handle: &tauri::AppHandle) -> Result<PathBuf> {
+    /// Locate a bundled binary: bundled resource (production) or target dir (dev).
+    fn resolve_source_binary(app_handle: &tauri::AppHandle, name: &str) -> Result<PathBuf> {
         // Production: binary bundled as a Tauri resource.
         if let Ok(resource_dir) = app_handle.path().resource_dir() {
-            let bundled = resource_dir.join(DAEMON_BINARY);
+            let bundled = resource_dir.join(name);
             if bundled.exists() {
                 return Ok(bundled);
             }
 
             // Some bundle layouts keep files under a `resources/` subdirectory.
-            let bundled_in_subdir = resource_dir.join("resources").join(DAEMON_BINARY);
+            let bundled_in_subdir = resource_dir.join("resources").join(name);
             if bundled_in_subdir.exists() {
                 return Ok(bundled_in_subdir);
             }
@@ -274,17 +348,20 @@ impl DaemonManager {
             .expect("CARGO_MANIFEST_DIR has no parent")
             .to_path_buf();
 
-        let release_path = workspace_root.join("target/release").join(DAEMON_BINARY);
+        let release_path = workspace_root.join("target/release").join(name);
         if release_path.exists() {
             return Ok(release_path);
         }
 
-        let debug_path = workspace_root.join("target/debug").join(DAEMON_BINARY);
+        let debug_path = workspace_root.join("target/debug").join(name);
         if debug_path.exists() {
             return Ok(debug_path);
         }
 
-        anyhow::bail!("Daemon binary not found. Run `cargo build -p lumo-daemon` first.")
+        anyhow::bail!(
+            "{} binary not found. Run `cargo build -p lumo-daemon -p lumo-cli` first.",
+            name
+        )
     }
 
     /// Wait for the daemon to become healthy after starting, with retries.
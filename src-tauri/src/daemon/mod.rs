@@ -7,5 +7,8 @@ mod plist;
 #[cfg(target_os = "linux")]
 mod systemd;
 
-pub use health::check_daemon_health;
+#[cfg(target_os = "linux")]
+mod detached;
+
+pub use health::{check_daemon_health, fetch_daemon_status};
 pub use manager::DaemonManager;
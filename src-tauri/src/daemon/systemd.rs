@@ -14,6 +14,16 @@ pub fn render_unit(daemon_path: &Path, log_dir: &Path, home_dir: &Path) -> Strin
         .replace("{{HOME}}", &home_dir.display().to_string())
 }
 
+/// Whether a systemd user instance is reachable. Containers, WSL and
+/// distros without systemd have none, so `systemctl --user` fails.
+pub async fn is_available() -> bool {
+    tokio::process::Command::new("systemctl")
+        .args(["--user", "show-environment"])
+        .output()
+        .await
+        .is_ok_and(|output| output.status.success())
+}
+
 /// Stop and disable the systemd user service. Ignores errors (service may not exist).
 pub async fn stop_service() -> Result<()> {
     let _ = tokio::process::Command::new("systemctl")
@@ -45,6 +45,8 @@ pub fn run() {
                         if let Err(e) = manager.ensure_running().await {
                             log::warn!("Daemon setup warning: {}", e);
                         }
+                        // Restart a crashed daemon when no service manager does.
+                        tokio::spawn(manager.supervise());
                     }
                     Err(e) => log::error!("Failed to init daemon manager: {}", e),
                 }
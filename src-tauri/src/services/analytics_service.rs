@@ -5,12 +5,17 @@
 use std::collections::HashMap;
 
 use anyhow::Result;
-use chrono::{Local, TimeZone};
+use chrono::Utc;
+use shared::analytics::{
+    self,
+    time_range::{bucket_sql, generate_date_labels, get_time_range_bounds},
+};
+use shared::calendar::Calendar;
 use sqlx::SqlitePool;
 
-use super::time_range::{generate_date_labels, get_time_range_bounds};
 use crate::types::{
-    ActivityDay, CacheHitTrend, ErrorRateStats, HourlyActivity, SessionBucket, TimeRange,
+    ActivityDay, BreakdownRequest, BreakdownRow, CacheHitTrend, ErrorRateStats, HourlyActivity,
+    SessionBucket, TimeRangeFilter,
 };
 
 /// Service for analytics operations
@@ -20,26 +25,31 @@ impl AnalyticsService {
     /// Get hourly activity distribution (API requests per hour of day)
     pub async fn get_hourly_activity(
         pool: &SqlitePool,
-        time_range: TimeRange,
+        time_range: TimeRangeFilter,
+        agent: Option<&str>,
     ) -> Result<Vec<HourlyActivity>> {
-        let (start_time, end_time) = get_time_range_bounds(time_range);
+        let (start_time, end_time) = get_time_range_bounds(time_range.try_into()?);
 
-        let rows: Vec<HourlyRow> = sqlx::query_as(
+        let query = format!(
             r#"
             SELECT
-                CAST(strftime('%H', datetime(timestamp / 1000, 'unixepoch', 'localtime')) AS INTEGER) as hour,
+                CAST(strftime('%H', {}, 'unixepoch') AS INTEGER) as hour,
                 COUNT(*) as count
             FROM events
             WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
                 AND name = 'claude_code.api_request'
             GROUP BY hour
             ORDER BY hour ASC
             "#,
-        )
-        .bind(start_time)
-        .bind(end_time)
-        .fetch_all(pool)
-        .await?;
+            Calendar::current().local_seconds_sql("timestamp", start_time, end_time)
+        );
+        let rows: Vec<HourlyRow> = sqlx::query_as(&query)
+            .bind(start_time)
+            .bind(end_time)
+            .bind(agent)
+            .fetch_all(pool)
+            .await?;
 
         // Fill all 24 hours
         let mut result = vec![HourlyActivity { hour: 0, count: 0 }; 24];
@@ -58,9 +68,10 @@ impl AnalyticsService {
     /// Get session length distribution
     pub async fn get_session_length_distribution(
         pool: &SqlitePool,
-        time_range: TimeRange,
+        time_range: TimeRangeFilter,
+        agent: Option<&str>,
     ) -> Result<Vec<SessionBucket>> {
-        let (start_time, end_time) = get_time_range_bounds(time_range);
+        let (start_time, end_time) = get_time_range_bounds(time_range.try_into()?);
 
         let rows: Vec<BucketRow> = sqlx::query_as(
             r#"
@@ -75,6 +86,7 @@ impl AnalyticsService {
                 COUNT(*) as count
             FROM sessions
             WHERE start_time >= ? AND start_time <= ?
+                AND agent = COALESCE(?, agent)
                 AND id != 'unknown'
             GROUP BY bucket
             ORDER BY MIN(duration_ms) ASC
@@ -82,6 +94,7 @@ impl AnalyticsService {
         )
         .bind(start_time)
         .bind(end_time)
+        .bind(agent)
         .fetch_all(pool)
         .await?;
 
@@ -107,9 +120,10 @@ impl AnalyticsService {
     /// Get error rate statistics
     pub async fn get_error_rate(
         pool: &SqlitePool,
-        time_range: TimeRange,
+        time_range: TimeRangeFilter,
+        agent: Option<&str>,
     ) -> Result<ErrorRateStats> {
-        let (start_time, end_time) = get_time_range_bounds(time_range);
+        let (start_time, end_time) = get_time_range_bounds(time_range.try_into()?);
 
         let row: Option<ErrorRateRow> = sqlx::query_as(
             r#"
@@ -118,11 +132,13 @@ impl AnalyticsService {
                 COALESCE(SUM(CASE WHEN name = 'claude_code.api_error' THEN 1 ELSE 0 END), 0) as total_errors
             FROM events
             WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
                 AND name IN ('claude_code.api_request', 'claude_code.api_error')
             "#,
         )
         .bind(start_time)
         .bind(end_time)
+        .bind(agent)
         .fetch_optional(pool)
         .await?;
 
@@ -146,41 +162,37 @@ impl AnalyticsService {
     /// Get cache hit rate trend over time
     pub async fn get_cache_hit_trend(
         pool: &SqlitePool,
-        time_range: TimeRange,
+        time_range: TimeRangeFilter,
+        agent: Option<&str>,
     ) -> Result<Vec<CacheHitTrend>> {
+        let time_range = analytics::TimeRange::try_from(time_range)?
+            .resolve(pool)
+            .await?;
         let (start_time, end_time) = get_time_range_bounds(time_range);
-
-        let (format_str, group_expr) = match time_range {
-            TimeRange::Today => (
-                "%H:00",
-                "strftime('%Y-%m-%d %H', datetime(timestamp / 1000, 'unixepoch', 'localtime'))",
-            ),
-            TimeRange::Week | TimeRange::Month => (
-                "%Y-%m-%d",
-                "strftime('%Y-%m-%d', datetime(timestamp / 1000, 'unixepoch', 'localtime'))",
-            ),
-        };
+        let (label_expr, group_expr) = bucket_sql(time_range, "timestamp");
 
         let query = format!(
             r#"
             SELECT
-                strftime('{}', datetime(timestamp / 1000, 'unixepoch', 'localtime')) as date,
+                {} as date,
                 COALESCE(
                     SUM(cache_read_tokens) * 100.0 / NULLIF(SUM(input_tokens + cache_read_tokens), 0),
                     0.0
                 ) as rate
             FROM events
             WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
                 AND name = 'claude_code.api_request'
             GROUP BY {}
             ORDER BY MIN(timestamp) ASC
             "#,
-            format_str, group_expr
+            label_expr, group_expr
         );
 
         let rows: Vec<CacheHitRow> = sqlx::query_as(&query)
             .bind(start_time)
             .bind(end_time)
+            .bind(agent)
             .fetch_all(pool)
             .await?;
 
@@ -200,34 +212,36 @@ impl AnalyticsService {
     }
 
     /// Get activity heatmap data (last 365 days, daily session counts)
-    pub async fn get_activity_heatmap(pool: &SqlitePool) -> Result<Vec<ActivityDay>> {
-        let now = Local::now();
-        let start = (now - chrono::Duration::days(365))
-            .date_naive()
-            .and_hms_opt(0, 0, 0)
-            .unwrap();
-        let start_time = Local
-            .from_local_datetime(&start)
-            .unwrap()
-            .timestamp_millis();
+    pub async fn get_activity_heatmap(
+        pool: &SqlitePool,
+        agent: Option<&str>,
+    ) -> Result<Vec<ActivityDay>> {
+        let calendar = Calendar::current();
+        let now = Utc::now();
+        let start_date = calendar.local(now).date_naive() - chrono::Duration::days(365);
+        let start_time = calendar.start_of_day(start_date).timestamp_millis();
         let end_time = now.timestamp_millis();
 
-        let rows: Vec<ActivityDayRow> = sqlx::query_as(
+        let query = format!(
             r#"
             SELECT
-                strftime('%Y-%m-%d', datetime(timestamp / 1000, 'unixepoch', 'localtime')) as date,
+                strftime('%Y-%m-%d', {}, 'unixepoch') as date,
                 COUNT(DISTINCT session_id) as count
             FROM events
             WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
                 AND session_id != 'unknown'
             GROUP BY date
             ORDER BY date ASC
             "#,
-        )
-        .bind(start_time)
-        .bind(end_time)
-        .fetch_all(pool)
-        .await?;
+            calendar.local_seconds_sql("timestamp", start_time, end_time)
+        );
+        let rows: Vec<ActivityDayRow> = sqlx::query_as(&query)
+            .bind(start_time)
+            .bind(end_time)
+            .bind(agent)
+            .fetch_all(pool)
+            .await?;
 
         Ok(rows
             .into_iter()
@@ -237,6 +251,24 @@ impl AnalyticsService {
             })
             .collect())
     }
+
+    /// Run a breakdown of any measures by any dimensions over a time range
+    pub async fn run_breakdown_query(
+        pool: &SqlitePool,
+        time_range: TimeRangeFilter,
+        request: BreakdownRequest,
+        agent: Option<&str>,
+    ) -> Result<Vec<BreakdownRow>> {
+        let mut query = request.into_query(time_range.try_into()?);
+        if let Some(agent) = agent {
+            query.filters.push(analytics::Filter {
+                dimension: analytics::Dimension::Agent,
+                values: vec![agent.to_string()],
+            });
+        }
+        let rows = query.run(pool).await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
 }
 
 #[derive(Debug, sqlx::FromRow)]
@@ -0,0 +1,67 @@
+//! Budget service
+//!
+//! Business logic for managing spend budgets, reporting their status and
+//! forecasting spend.
+
+use anyhow::Result;
+use shared::analytics::{BudgetAnalytics, ForecastAnalytics};
+use shared::BudgetRepository;
+use sqlx::SqlitePool;
+
+use crate::types::{
+    Budget, BudgetPeriod, BudgetRequest, BudgetStatus, BurnDownPoint, SpendForecast,
+};
+
+pub struct BudgetService;
+
+impl BudgetService {
+    /// Get all budgets with their spend in the current period
+    pub async fn list_budgets(pool: &SqlitePool) -> Result<Vec<BudgetStatus>> {
+        let budgets = BudgetRepository::find_all(pool).await?;
+        let mut statuses = Vec::with_capacity(budgets.len());
+        for budget in budgets {
+            statuses.push(BudgetAnalytics::status(pool, &budget).await?.into());
+        }
+        Ok(statuses)
+    }
+
+    /// Create a budget
+    pub async fn create_budget(pool: &SqlitePool, request: BudgetRequest) -> Result<Budget> {
+        let id = BudgetRepository::insert(pool, &request.into()).await?;
+        Ok(BudgetRepository::find_by_id(pool, id).await?.into())
+    }
+
+    /// Update a budget
+    pub async fn update_budget(
+        pool: &SqlitePool,
+        id: i64,
+        request: BudgetRequest,
+    ) -> Result<Budget> {
+        BudgetRepository::update(pool, id, &request.into()).await?;
+        Ok(BudgetRepository::find_by_id(pool, id).await?.into())
+    }
+
+    /// Delete a budget
+    pub async fn delete_budget(pool: &SqlitePool, id: i64) -> Result<bool> {
+        Ok(BudgetRepository::delete(pool, id).await?)
+    }
+
+    /// Cumulative spend across a budget's current period
+    pub async fn get_budget_burn_down(pool: &SqlitePool, id: i64) -> Result<Vec<BurnDownPoint>> {
+        let budget = BudgetRepository::find_by_id(pool, id).await?;
+        let points = BudgetAnalytics::burn_down(pool, &budget).await?;
+        Ok(points.into_iter().map(BurnDownPoint::from).collect())
+    }
+
+    /// Projected spend at the end of the current period, and the day it
+    /// goes over `limit`
+    pub async fn get_spend_forecast(
+        pool: &SqlitePool,
+        period: BudgetPeriod,
+        limit: Option<f64>,
+        agent: Option<&str>,
+    ) -> Result<SpendForecast> {
+        let forecast = ForecastAnalytics::forecast(pool, period.into(), limit, agent).await?;
+        Ok(forecast.into())
+    }
+}
@@ -1,105 +1,156 @@
 //! Claude Code configuration service
 //!
-//! Manages `~/.claude/settings.json` to configure OTEL telemetry export
-//! pointing at the Lumo daemon, and hooks for notification forwarding.
+//! Manages `settings.json` of every Claude profile (`~/.claude` by default)
+//! to configure OTEL telemetry export pointing at the Lumo daemon, hooks
+//! for notification forwarding, and optionally the `lumo statusline` status
+//! line and the `lumo-daemon mcp` MCP server (in `.claude.json`).
 
 use anyhow::{Context, Result};
 use serde_json::{json, Map, Value};
-use std::fs;
 use std::path::PathBuf;
 
-/// Environment variables that Lumo manages in Claude Code settings.
-const OTEL_ENV_VARS: &[(&str, &str)] = &[
-    ("CLAUDE_CODE_ENABLE_TELEMETRY", "1"),
-    ("OTEL_METRICS_EXPORTER", "otlp"),
-    ("OTEL_LOGS_EXPORTER", "otlp"),
-    ("OTEL_EXPORTER_OTLP_PROTOCOL", "http/json"),
-    ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://localhost:4318"),
-];
+use shared::claude_settings::{
+    self, ManagedSettingsRecord, HOOK_BINARY, HOOK_EVENTS, HOOK_MARKER, LEGACY_HOOK_MARKER,
+    MCP_SERVER_NAME, OTEL_ENDPOINT_VAR, OTEL_ENV_VARS, STATUSLINE_BINARY,
+};
+use shared::doctor::ClaudeConfigRoot;
+use shared::settings_file::{self, SettingsFile};
 
-/// The command used by Lumo hooks — pipes hook stdin JSON to the daemon.
-/// Uses --noproxy to bypass any system proxy (e.g. SOCKS5) for localhost.
-const HOOK_COMMAND: &str =
-    "curl -s --noproxy localhost -X POST http://localhost:4318/notify -H 'Content-Type: application/json' -d \"$(cat)\"";
+use super::claude_profile_service::ClaudeProfile;
+use super::ClaudeProfileService;
+use crate::types::{DaemonAddressStatus, SettingsBackup, SettingsPreview};
 
-/// Marker substring to detect if a Lumo hook is already present.
-const HOOK_MARKER: &str = "localhost:4318/notify";
-
-/// Hook events that Lumo subscribes to.
-/// We omit matchers entirely so they fire on every occurrence of the event.
-const HOOK_EVENTS: &[&str] = &["Notification", "Stop", "SubagentStop"];
+/// A managed env var Lumo changed, with the value it replaced.
+struct EnvChange {
+    key: &'static str,
+    previous: Option<Value>,
+    value: String,
+}
 
 pub struct ClaudeConfigService;
 
 impl ClaudeConfigService {
-    fn settings_path() -> Result<PathBuf> {
-        let home = dirs::home_dir().context("Could not find home directory")?;
-        Ok(home.join(".claude").join("settings.json"))
-    }
-
-    /// Path to ~/.claude.json (Claude Code's user preferences file).
-    fn user_prefs_path() -> Result<PathBuf> {
-        let home = dirs::home_dir().context("Could not find home directory")?;
-        Ok(home.join(".claude.json"))
-    }
-
-    fn read_settings(path: &PathBuf) -> Result<Map<String, Value>> {
-        if path.exists() {
-            let content = fs::read_to_string(path).context("Failed to read Claude settings")?;
-            serde_json::from_str(&content).context("Failed to parse Claude settings")
-        } else {
-            Ok(Map::new())
+    /// Profiles whose config root exists. Lumo configures those it finds
+    /// rather than creating roots Claude Code has never used.
+    fn existing_profiles() -> Result<Vec<ClaudeProfile>> {
+        let profiles = ClaudeProfileService::list()?;
+        let total = profiles.len();
+        let existing: Vec<_> = profiles
+            .into_iter()
+            .filter(|p| p.config_dir.is_dir())
+            .collect();
+        // A fresh install has no ~/.claude yet; configure it anyway so
+        // Claude Code picks Lumo up on first run.
+        if existing.is_empty() && total > 0 {
+            return Ok(vec![ClaudeProfileService::primary()?]);
         }
+        Ok(existing)
     }
 
-    fn write_settings(path: &PathBuf, root: &Map<String, Value>) -> Result<()> {
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent).context("Failed to create ~/.claude directory")?;
-        }
-        let content =
-            serde_json::to_string_pretty(root).context("Failed to serialize Claude settings")?;
-        fs::write(path, content).context("Failed to write Claude settings")?;
-        Ok(())
+    /// Settings files Lumo edits, and so may restore from backup.
+    fn managed_files() -> Result<Vec<PathBuf>> {
+        Ok(ClaudeProfileService::list()?
+            .iter()
+            .flat_map(|p| [p.settings_path(), p.user_prefs_path()])
+            .collect())
     }
 
-    /// Read the `preferredNotifChannel` value from ~/.claude.json.
-    /// Returns the raw string value, or None if not set.
+    /// Read the `preferredNotifChannel` value from the primary profile's
+    /// `.claude.json`. Returns the raw string value, or None if not set.
     pub fn get_preferred_notif_channel() -> Result<Option<String>> {
-        let path = Self::user_prefs_path()?;
-        let prefs = Self::read_settings(&path)?;
+        let path = ClaudeProfileService::primary()?.user_prefs_path();
+        let prefs = SettingsFile::read(&path)?;
         Ok(prefs
+            .root
             .get("preferredNotifChannel")
             .and_then(|v| v.as_str())
             .map(|s| s.to_string()))
     }
 
-    /// Write the `preferredNotifChannel` value to ~/.claude.json.
-    /// None removes the key entirely (Claude Code's default/auto behavior).
+    /// Write the `preferredNotifChannel` value to `.claude.json` of every
+    /// profile. None removes the key entirely (Claude Code's default/auto
+    /// behavior).
     pub fn set_preferred_notif_channel(channel: Option<&str>) -> Result<()> {
-        let path = Self::user_prefs_path()?;
-        let mut prefs = Self::read_settings(&path)?;
-        match channel {
-            Some(value) => {
-                prefs.insert(
-                    "preferredNotifChannel".to_string(),
-                    Value::String(value.to_string()),
-                );
-                log::info!("Set preferredNotifChannel to '{}'", value);
-            }
-            None => {
-                prefs.remove("preferredNotifChannel");
-                log::info!("Removed preferredNotifChannel (auto)");
+        for profile in Self::existing_profiles()? {
+            let mut prefs = SettingsFile::read(&profile.user_prefs_path())?;
+            match channel {
+                Some(value) => {
+                    prefs.root.insert(
+                        "preferredNotifChannel".to_string(),
+                        Value::String(value.to_string()),
+                    );
+                    log::info!(
+                        "Set preferredNotifChannel to '{}' in {}",
+                        value,
+                        prefs.path.display()
+                    );
+                }
+                None => {
+                    prefs.root.remove("preferredNotifChannel");
+                    log::info!(
+                        "Removed preferredNotifChannel (auto) in {}",
+                        prefs.path.display()
+                    );
+                }
             }
+            prefs.write()?;
         }
-        Self::write_settings(&path, &prefs)?;
         Ok(())
     }
 
-    /// Ensure Claude Code's settings.json has the required OTEL env vars.
+    /// OTLP endpoint matching the daemon's configured address.
+    fn expected_otel_endpoint() -> Result<String> {
+        let address = shared::daemon_address::server_address()?;
+        Ok(shared::daemon_address::otlp_endpoint(&address)?)
+    }
+
+    /// Ensure every profile's settings.json has the required OTEL env vars.
     /// Preserves all existing settings — only adds/updates the OTEL keys.
     pub fn ensure_otel_config() -> Result<()> {
-        let path = Self::settings_path()?;
-        let mut root = Self::read_settings(&path)?;
+        for profile in Self::existing_profiles()? {
+            let mut settings = SettingsFile::read(&profile.settings_path())?;
+
+            let changes = Self::apply_otel_config(&mut settings.root)?;
+            if changes.is_empty() {
+                log::info!(
+                    "Claude Code OTEL config already up to date in {}",
+                    settings.path.display()
+                );
+                continue;
+            }
+
+            Self::write_settings(&settings, &changes)?;
+            log::info!(
+                "Updated Claude Code OTEL config at {}",
+                settings.path.display()
+            );
+        }
+        Ok(())
+    }
+
+    /// Write settings.json and record the file and the env vars Lumo
+    /// changed, so uninstall can find it and restore the user's previous
+    /// values.
+    fn write_settings(settings: &SettingsFile, changes: &[EnvChange]) -> Result<()> {
+        settings.write()?;
+
+        let mut record = ManagedSettingsRecord::load()?;
+        record.track_file(&settings.path);
+        for change in changes {
+            record.record_env(
+                &settings.path,
+                change.key,
+                change.previous.as_ref(),
+                &change.value,
+            );
+        }
+        record.save()?;
+        Ok(())
+    }
+
+    /// Add/update the OTEL env vars in `root`. Returns what changed.
+    fn apply_otel_config(root: &mut Map<String, Value>) -> Result<Vec<EnvChange>> {
+        let endpoint = Self::expected_otel_endpoint()?;
 
         let env_obj = root
             .entry("env")
@@ -109,37 +160,39 @@ impl ClaudeConfigService {
             .as_object_mut()
             .context("'env' field in Claude settings is not an object")?;
 
-        let mut changed = false;
-        for &(key, value) in OTEL_ENV_VARS {
+        let expected_vars = OTEL_ENV_VARS
+            .iter()
+            .copied()
+            .chain(std::iter::once((OTEL_ENDPOINT_VAR, endpoint.as_str())));
+
+        let mut changes = Vec::new();
+        for (key, value) in expected_vars {
             let expected = Value::String(value.to_string());
             if env_map.get(key) != Some(&expected) {
-                env_map.insert(key.to_string(), expected);
-                changed = true;
+                let previous = env_map.insert(key.to_string(), expected);
+                changes.push(EnvChange {
+                    key,
+                    previous,
+                    value: value.to_string(),
+                });
             }
         }
 
-        if !changed {
-            log::info!("Claude Code OTEL config already up to date");
-            return Ok(());
-        }
-
-        Self::write_settings(&path, &root)?;
-        log::info!("Updated Claude Code OTEL config at {}", path.display());
-        Ok(())
+        Ok(changes)
     }
 
-    /// Check if a JSON value (at any nesting level) contains the Lumo hook marker.
-    fn contains_hook_marker(value: &Value) -> bool {
-        match value {
-            Value::String(s) => s.contains(HOOK_MARKER),
-            Value::Array(arr) => arr.iter().any(Self::contains_hook_marker),
-            Value::Object(map) => map.values().any(Self::contains_hook_marker),
-            _ => false,
-        }
+    /// Hook command for an event, e.g. `"/home/me/.lumo/bin/lumo-daemon" hook Stop`.
+    fn hook_command(event_name: &str) -> Result<String> {
+        let home = dirs::home_dir().context("Could not find home directory")?;
+        Ok(format!(
+            "\"{}\" hook {}",
+            home.join(HOOK_BINARY).display(),
+            event_name
+        ))
     }
 
     /// Ensure Claude Code's settings.json has hooks that forward events
-    /// to the Lumo daemon's `/notify` endpoint.
+    /// to the Lumo daemon's `/notify` endpoint via `lumo-daemon hook`.
     ///
     /// Claude Code hooks use a three-level structure:
     /// ```json
@@ -149,12 +202,31 @@ impl ClaudeConfigService {
     /// - Level 2: each matcher group has optional `matcher` + `hooks` array
     /// - Level 3: each hook handler has `type`, `command`, etc.
     ///
-    /// This method cleans up any old-format (flat) Lumo entries before
-    /// writing the correct nested format.
+    /// This method cleans up any old-format (flat or curl-based) Lumo
+    /// entries before writing the correct nested format.
     pub fn ensure_hooks_config() -> Result<()> {
-        let path = Self::settings_path()?;
-        let mut root = Self::read_settings(&path)?;
+        for profile in Self::existing_profiles()? {
+            let mut settings = SettingsFile::read(&profile.settings_path())?;
+
+            if !Self::apply_hooks_config(&mut settings.root)? {
+                log::info!(
+                    "Claude Code hooks config already up to date in {}",
+                    settings.path.display()
+                );
+                continue;
+            }
+
+            Self::write_settings(&settings, &[])?;
+            log::info!(
+                "Updated Claude Code hooks config at {}",
+                settings.path.display()
+            );
+        }
+        Ok(())
+    }
 
+    /// Install Lumo's hooks in `root`. Returns whether anything changed.
+    fn apply_hooks_config(root: &mut Map<String, Value>) -> Result<bool> {
         let hooks_obj = root
             .entry("hooks")
             .or_insert_with(|| Value::Object(Map::new()));
@@ -162,18 +234,18 @@ impl ClaudeConfigService {
             .as_object_mut()
             .context("'hooks' field in Claude settings is not an object")?;
 
-        let expected_hook = json!({
-            "hooks": [
-                {
-                    "type": "command",
-                    "command": HOOK_COMMAND,
-                }
-            ]
-        });
-
         let mut changed = false;
 
         for &event_name in HOOK_EVENTS {
+            let expected_hook = json!({
+                "hooks": [
+                    {
+                        "type": "command",
+                        "command": Self::hook_command(event_name)?,
+                    }
+                ]
+            });
+
             let event_arr = hooks_map
                 .entry(event_name)
                 .or_insert_with(|| Value::Array(Vec::new()));
@@ -187,20 +259,387 @@ impl ClaudeConfigService {
             }
 
             // Remove any old-format or malformed Lumo entries
-            arr.retain(|entry| !Self::contains_hook_marker(entry));
+            arr.retain(|entry| !claude_settings::contains_hook_marker(entry));
 
             // Add the correctly structured matcher group
-            arr.push(expected_hook.clone());
+            arr.push(expected_hook);
             changed = true;
         }
 
-        if !changed {
-            log::info!("Claude Code hooks config already up to date");
-            return Ok(());
+        Ok(changed)
+    }
+
+    /// Status line setting, e.g.
+    /// `{ "type": "command", "command": "\"/home/me/.lumo/bin/lumo\" statusline" }`.
+    fn statusline_setting() -> Result<Value> {
+        let home = dirs::home_dir().context("Could not find home directory")?;
+        Ok(json!({
+            "type": "command",
+            "command": format!("\"{}\" statusline", home.join(STATUSLINE_BINARY).display()),
+            "padding": 0,
+        }))
+    }
+
+    /// Whether the primary profile shows Lumo's status line.
+    pub fn is_statusline_installed() -> Result<bool> {
+        let path = ClaudeProfileService::primary()?.settings_path();
+        Ok(SettingsFile::read(&path)?
+            .root
+            .get("statusLine")
+            .is_some_and(claude_settings::is_lumo_status_line))
+    }
+
+    /// Install or remove Lumo's status line in every profile. Installing
+    /// replaces the user's own status line, which is recorded and put back
+    /// on removal (or uninstall).
+    pub fn set_statusline(enabled: bool) -> Result<()> {
+        let expected = Self::statusline_setting()?;
+        let mut record = ManagedSettingsRecord::load()?;
+
+        let mut changed = Vec::new();
+        for profile in Self::existing_profiles()? {
+            let mut settings = SettingsFile::read(&profile.settings_path())?;
+            let current = settings.root.get("statusLine").cloned();
+            let is_lumo = current
+                .as_ref()
+                .is_some_and(claude_settings::is_lumo_status_line);
+
+            if enabled {
+                if current.as_ref() == Some(&expected) {
+                    continue;
+                }
+                if !is_lumo {
+                    record.record_status_line(&settings.path, current.as_ref());
+                }
+                record.track_file(&settings.path);
+                settings
+                    .root
+                    .insert("statusLine".to_string(), expected.clone());
+            } else {
+                if !is_lumo {
+                    continue;
+                }
+                match record
+                    .take_status_line(&settings.path)
+                    .and_then(|s| s.original)
+                {
+                    Some(original) => settings.root.insert("statusLine".to_string(), original),
+                    None => settings.root.remove("statusLine"),
+                };
+            }
+            changed.push(settings);
+        }
+
+        // Record the files and replaced status lines before editing, so
+        // uninstall can revert every one even if a write below fails.
+        // Removal forgets the originals only once the files are written.
+        if enabled && !changed.is_empty() {
+            record.save()?;
+        }
+
+        for settings in changed {
+            settings.write()?;
+            log::info!(
+                "{} Lumo status line in {}",
+                if enabled { "Installed" } else { "Removed" },
+                settings.path.display()
+            );
+        }
+
+        if !enabled {
+            record.save()?;
+        }
+        Ok(())
+    }
+
+    /// MCP server entry, e.g.
+    /// `{ "type": "stdio", "command": "/home/me/.lumo/bin/lumo-daemon", "args": ["mcp"] }`.
+    fn mcp_server_setting() -> Result<Value> {
+        let home = dirs::home_dir().context("Could not find home directory")?;
+        Ok(json!({
+            "type": "stdio",
+            "command": home.join(HOOK_BINARY).display().to_string(),
+            "args": ["mcp"],
+            "env": {},
+        }))
+    }
+
+    /// Whether the primary profile's `.claude.json` registers Lumo's MCP
+    /// server.
+    pub fn is_mcp_server_installed() -> Result<bool> {
+        let path = ClaudeProfileService::primary()?.user_prefs_path();
+        Ok(SettingsFile::read(&path)?
+            .root
+            .get("mcpServers")
+            .and_then(|servers| servers.get(MCP_SERVER_NAME))
+            .is_some_and(claude_settings::is_lumo_mcp_server))
+    }
+
+    /// Register or remove Lumo's MCP server in `.claude.json` of every
+    /// profile, so Claude Code can query its own usage. A user's own server
+    /// named `lumo` is never replaced.
+    pub fn set_mcp_server(enabled: bool) -> Result<()> {
+        let expected = Self::mcp_server_setting()?;
+
+        // Check every profile before writing any, so a conflict in one does
+        // not leave the others half registered
+        let mut changed = Vec::new();
+        for profile in Self::existing_profiles()? {
+            let mut prefs = SettingsFile::read(&profile.user_prefs_path())?;
+            let current = prefs
+                .root
+                .get("mcpServers")
+                .and_then(|servers| servers.get(MCP_SERVER_NAME))
+                .cloned();
+            let is_lumo = current
+                .as_ref()
+                .is_some_and(claude_settings::is_lumo_mcp_server);
+
+            if enabled {
+                if current.as_ref() == Some(&expected) {
+                    continue;
+                }
+                if current.is_some() && !is_lumo {
+                    anyhow::bail!(
+                        "{} already has an MCP server named '{}'",
+                        prefs.path.display(),
+                        MCP_SERVER_NAME
+                    );
+                }
+                let servers = prefs.root.entry("mcpServers").or_insert_with(|| json!({}));
+                let Some(servers) = servers.as_object_mut() else {
+                    anyhow::bail!("mcpServers in {} is not an object", prefs.path.display());
+                };
+                servers.insert(MCP_SERVER_NAME.to_string(), expected.clone());
+            } else {
+                if !is_lumo {
+                    continue;
+                }
+                if let Some(servers) = prefs
+                    .root
+                    .get_mut("mcpServers")
+                    .and_then(Value::as_object_mut)
+                {
+                    servers.remove(MCP_SERVER_NAME);
+                    if servers.is_empty() {
+                        prefs.root.remove("mcpServers");
+                    }
+                }
+            }
+            changed.push(prefs);
+        }
+
+        // Record the files before editing them, so uninstall finds every
+        // one even if a write below fails
+        if enabled && !changed.is_empty() {
+            let mut record = ManagedSettingsRecord::load()?;
+            for prefs in &changed {
+                record.track_file(&prefs.path);
+            }
+            record.save()?;
+        }
+
+        for prefs in changed {
+            prefs.write()?;
+            log::info!(
+                "{} Lumo MCP server in {}",
+                if enabled { "Registered" } else { "Removed" },
+                prefs.path.display()
+            );
+        }
+        Ok(())
+    }
+
+    /// Diff of the OTEL and hook changes Lumo would make to a profile's
+    /// settings.json (the primary profile's by default).
+    pub fn preview_changes(profile: Option<&str>) -> Result<SettingsPreview> {
+        let profile = ClaudeProfileService::select(profile)?.remove(0);
+        let mut settings = SettingsFile::read(&profile.settings_path())?;
+        let otel_changes = Self::apply_otel_config(&mut settings.root)?;
+        let hooks_changed = Self::apply_hooks_config(&mut settings.root)?;
+        let has_changes = !otel_changes.is_empty() || hooks_changed;
+
+        let before = settings.original();
+        let after = if has_changes {
+            settings.render()?
+        } else {
+            before.to_string()
+        };
+        let diff = settings_file::diff_lines(before, &after)
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(SettingsPreview {
+            path: settings.path.display().to_string(),
+            fingerprint: settings.fingerprint(),
+            has_changes,
+            diff,
+        })
+    }
+
+    /// Apply the previewed changes, refusing if settings.json changed since
+    /// the preview with the given fingerprint was taken.
+    pub fn apply_changes(profile: Option<&str>, fingerprint: &str) -> Result<()> {
+        let profile = ClaudeProfileService::select(profile)?.remove(0);
+        let mut settings = SettingsFile::read(&profile.settings_path())?;
+        if settings.fingerprint() != fingerprint {
+            anyhow::bail!(
+                "Claude Code settings changed since the preview; review the changes again"
+            );
+        }
+
+        let otel_changes = Self::apply_otel_config(&mut settings.root)?;
+        let hooks_changed = Self::apply_hooks_config(&mut settings.root)?;
+        if !otel_changes.is_empty() || hooks_changed {
+            Self::write_settings(&settings, &otel_changes)?;
+            log::info!("Applied Lumo config to {}", settings.path.display());
         }
+        Ok(())
+    }
+
+    /// Backups of the Claude Code settings files Lumo edits, newest first.
+    pub fn list_backups() -> Result<Vec<SettingsBackup>> {
+        let backups = settings_file::list_backups(&Self::managed_files()?)?;
+        Ok(backups.into_iter().map(Into::into).collect())
+    }
 
-        Self::write_settings(&path, &root)?;
-        log::info!("Updated Claude Code hooks config at {}", path.display());
+    /// Restore a settings file from a backup in `~/.lumo/backups/`.
+    pub fn restore_backup(file_name: &str) -> Result<()> {
+        let target = settings_file::restore_backup(file_name, &Self::managed_files()?)?;
+        log::info!("Restored {} from {}", target.display(), file_name);
         Ok(())
     }
+
+    /// Compare the daemon address in `~/.lumo/daemon.json` with the Claude
+    /// Code settings of every profile and the addresses the running daemon
+    /// reports.
+    ///
+    /// `daemon_listeners` is None when the daemon is unreachable.
+    pub fn check_address_drift(
+        daemon_listeners: Option<&[String]>,
+    ) -> Result<DaemonAddressStatus> {
+        let address = shared::daemon_address::server_address()?;
+        let expected_endpoint = shared::daemon_address::otlp_endpoint(&address)?;
+
+        let mut issues = Vec::new();
+        if std::env::var("LUMO_SERVER_ADDRESS").is_ok() {
+            issues.push(
+                "LUMO_SERVER_ADDRESS is set in the app environment and overrides daemon.json"
+                    .to_string(),
+            );
+        }
+
+        let profiles = Self::existing_profiles()?;
+        let labelled = profiles.len() > 1;
+        let mut claude_endpoint = None;
+        let mut hooks_installed = true;
+        for (index, profile) in profiles.iter().enumerate() {
+            let root = SettingsFile::read(&profile.settings_path())?.root;
+            let endpoint = root
+                .get("env")
+                .and_then(|env| env.get(OTEL_ENDPOINT_VAR))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+            let hooks = root.get("hooks");
+            let profile_hooks_installed = HOOK_EVENTS.iter().all(|event| {
+                hooks
+                    .and_then(|h| h.get(*event))
+                    .is_some_and(Self::has_current_hook)
+            });
+
+            let settings_name = if labelled {
+                format!("Claude Code settings ({})", profile.label)
+            } else {
+                "Claude Code settings".to_string()
+            };
+            match &endpoint {
+                Some(endpoint) if *endpoint == expected_endpoint => {}
+                Some(endpoint) => issues.push(format!(
+                    "{} export to {} but the daemon is configured for {}",
+                    settings_name, endpoint, expected_endpoint
+                )),
+                None => issues.push(format!(
+                    "{} is not set in {}",
+                    OTEL_ENDPOINT_VAR, settings_name
+                )),
+            }
+            if hooks.is_some_and(Self::contains_legacy_hook) {
+                issues.push(format!(
+                    "Hooks in {} still use the legacy curl command",
+                    settings_name
+                ));
+            } else if !profile_hooks_installed {
+                issues.push(format!("Hooks are not installed in {}", settings_name));
+            }
+
+            if index == 0 {
+                claude_endpoint = endpoint;
+            }
+            hooks_installed &= profile_hooks_installed;
+        }
+
+        let listeners = daemon_listeners.map(|l| l.to_vec()).unwrap_or_default();
+        match daemon_listeners {
+            None => issues.push(format!("Daemon is not reachable at {}", address)),
+            Some(listeners) => {
+                let expected = address.parse::<std::net::SocketAddr>().ok();
+                let listening = listeners.iter().any(|l| {
+                    l.strip_prefix("http://")
+                        .and_then(|a| a.parse::<std::net::SocketAddr>().ok())
+                        == expected
+                });
+                if !listening {
+                    issues.push(format!(
+                        "Daemon is listening on {} instead of {}; restart it to apply the change",
+                        listeners.join(", "),
+                        address
+                    ));
+                }
+            }
+        }
+
+        Ok(DaemonAddressStatus {
+            address,
+            expected_endpoint,
+            claude_endpoint,
+            hooks_installed,
+            daemon_listeners: listeners,
+            in_sync: issues.is_empty(),
+            issues,
+        })
+    }
+
+    /// Config roots for `shared::doctor` to check: the profiles Lumo
+    /// configures.
+    pub fn doctor_roots() -> Result<Vec<ClaudeConfigRoot>> {
+        Ok(Self::existing_profiles()?
+            .into_iter()
+            .map(|profile| ClaudeConfigRoot {
+                settings_path: profile.settings_path(),
+                user_prefs_path: profile.user_prefs_path(),
+                label: profile.label,
+            })
+            .collect())
+    }
+
+    /// Whether a hook event array holds the current `lumo-daemon hook` entry.
+    fn has_current_hook(value: &Value) -> bool {
+        match value {
+            Value::String(s) => s.contains(HOOK_MARKER),
+            Value::Array(arr) => arr.iter().any(Self::has_current_hook),
+            Value::Object(map) => map.values().any(Self::has_current_hook),
+            _ => false,
+        }
+    }
+
+    /// Whether any hook still uses the old curl command.
+    fn contains_legacy_hook(value: &Value) -> bool {
+        match value {
+            Value::String(s) => s.contains(LEGACY_HOOK_MARKER),
+            Value::Array(arr) => arr.iter().any(Self::contains_legacy_hook),
+            Value::Object(map) => map.values().any(Self::contains_legacy_hook),
+            _ => false,
+        }
+    }
 }
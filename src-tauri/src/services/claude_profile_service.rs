@@ -0,0 +1,205 @@
+//! Claude profile service
+//!
+//! Claude Code keeps its settings, sessions, skills and plugins under one
+//! config root: `~/.claude`, or `$CLAUDE_CONFIG_DIR` when set. Users who run
+//! several accounts keep one root per account. The roots Lumo reads are
+//! registered in `~/.lumo/config.json`; with none registered, the single
+//! root Claude Code itself would use is the `default` profile.
+
+use anyhow::{bail, Context, Result};
+use shared::claude_settings;
+use std::path::{Path, PathBuf};
+
+use crate::services::config_service::ClaudeProfileEntry;
+use crate::services::ConfigService;
+use crate::types::ClaudeProfileInfo;
+
+/// Label of the implicit profile used when none are registered
+pub const DEFAULT_PROFILE_LABEL: &str = "default";
+
+/// A Claude Code config root
+#[derive(Debug, Clone)]
+pub struct ClaudeProfile {
+    pub label: String,
+    pub config_dir: PathBuf,
+}
+
+impl ClaudeProfile {
+    /// `<root>/projects`, where session transcripts live
+    pub fn projects_dir(&self) -> PathBuf {
+        self.config_dir.join("projects")
+    }
+
+    /// `<root>/plugins`
+    pub fn plugins_dir(&self) -> PathBuf {
+        self.config_dir.join("plugins")
+    }
+
+    /// `<root>/settings.json`
+    pub fn settings_path(&self) -> PathBuf {
+        self.config_dir.join("settings.json")
+    }
+
+    /// User preferences file. Claude Code keeps it next to the root
+    /// (`~/.claude.json`) for `~/.claude`, and inside it otherwise.
+    pub fn user_prefs_path(&self) -> PathBuf {
+        claude_settings::user_prefs_path(&self.config_dir)
+    }
+
+    /// Whether this is `~/.claude`, which needs no `CLAUDE_CONFIG_DIR`
+    pub fn is_home_root(&self) -> bool {
+        claude_settings::is_home_root(&self.config_dir)
+    }
+
+    /// Point a `claude` CLI invocation at this profile
+    pub fn apply_env(&self, cmd: &mut tokio::process::Command) {
+        if self.is_home_root() {
+            cmd.env_remove("CLAUDE_CONFIG_DIR");
+        } else {
+            cmd.env("CLAUDE_CONFIG_DIR", &self.config_dir);
+        }
+    }
+
+    fn to_info(&self) -> ClaudeProfileInfo {
+        ClaudeProfileInfo {
+            label: self.label.clone(),
+            path: self.config_dir.to_string_lossy().to_string(),
+            exists: self.config_dir.is_dir(),
+        }
+    }
+}
+
+/// Service for the Claude profile registry
+pub struct ClaudeProfileService;
+
+impl ClaudeProfileService {
+    /// Expand a leading `~` and reject relative paths
+    fn resolve_path(path: &str) -> Result<PathBuf> {
+        let path = path.trim();
+        let resolved = if path == "~" {
+            dirs::home_dir().context("Failed to get home directory")?
+        } else if let Some(rest) = path.strip_prefix("~/") {
+            dirs::home_dir()
+                .context("Failed to get home directory")?
+                .join(rest)
+        } else {
+            PathBuf::from(path)
+        };
+        if !resolved.is_absolute() {
+            bail!("Profile path must be absolute: {}", path);
+        }
+        Ok(resolved)
+    }
+
+    /// The root Claude Code uses without any registry
+    fn default_profile() -> Result<ClaudeProfile> {
+        let config_dir = match std::env::var("CLAUDE_CONFIG_DIR") {
+            Ok(dir) if !dir.trim().is_empty() => Self::resolve_path(&dir)?,
+            _ => dirs::home_dir()
+                .context("Failed to get home directory")?
+                .join(".claude"),
+        };
+        Ok(ClaudeProfile {
+            label: DEFAULT_PROFILE_LABEL.to_string(),
+            config_dir,
+        })
+    }
+
+    /// All profiles, in registration order. Never empty.
+    pub fn list() -> Result<Vec<ClaudeProfile>> {
+        let entries = ConfigService::get_claude_profiles()?;
+        if entries.is_empty() {
+            return Ok(vec![Self::default_profile()?]);
+        }
+        entries
+            .iter()
+            .map(|entry| {
+                Ok(ClaudeProfile {
+                    label: entry.label.clone(),
+                    config_dir: Self::resolve_path(&entry.path)?,
+                })
+            })
+            .collect()
+    }
+
+    /// The first profile, used where a single root is needed
+    pub fn primary() -> Result<ClaudeProfile> {
+        Ok(Self::list()?.remove(0))
+    }
+
+    /// Profiles matching `label`, or all of them when None
+    pub fn select(label: Option<&str>) -> Result<Vec<ClaudeProfile>> {
+        let profiles = Self::list()?;
+        match label {
+            None => Ok(profiles),
+            Some(label) => {
+                let profile = profiles
+                    .into_iter()
+                    .find(|p| p.label == label)
+                    .with_context(|| format!("Unknown Claude profile: {}", label))?;
+                Ok(vec![profile])
+            }
+        }
+    }
+
+    /// Profile whose root contains `path`
+    pub fn for_path(path: &Path) -> Result<Option<ClaudeProfile>> {
+        Ok(Self::list()?
+            .into_iter()
+            .find(|p| path.starts_with(&p.config_dir)))
+    }
+
+    pub fn list_info() -> Result<Vec<ClaudeProfileInfo>> {
+        Ok(Self::list()?.iter().map(ClaudeProfile::to_info).collect())
+    }
+
+    /// Register a config root. The first registration also registers the
+    /// implicit default profile, so it keeps being read.
+    pub fn add(label: &str, path: &str) -> Result<Vec<ClaudeProfileInfo>> {
+        let label = label.trim();
+        if label.is_empty() {
+            bail!("Profile label must not be empty");
+        }
+        let config_dir = Self::resolve_path(path)?;
+
+        let mut entries = ConfigService::get_claude_profiles()?;
+        if entries.is_empty() {
+            let default = Self::default_profile()?;
+            entries.push(ClaudeProfileEntry {
+                label: default.label,
+                path: default.config_dir.to_string_lossy().to_string(),
+            });
+        }
+        if entries.iter().any(|e| e.label == label) {
+            bail!("A Claude profile named '{}' already exists", label);
+        }
+        if entries
+            .iter()
+            .any(|e| Self::resolve_path(&e.path).ok().as_ref() == Some(&config_dir))
+        {
+            bail!("{} is already registered", config_dir.display());
+        }
+
+        entries.push(ClaudeProfileEntry {
+            label: label.to_string(),
+            path: config_dir.to_string_lossy().to_string(),
+        });
+        ConfigService::set_claude_profiles(entries)?;
+        Self::list_info()
+    }
+
+    /// Unregister a config root. Files on disk are left alone.
+    pub fn remove(label: &str) -> Result<Vec<ClaudeProfileInfo>> {
+        let mut entries = ConfigService::get_claude_profiles()?;
+        let before = entries.len();
+        entries.retain(|e| e.label != label);
+        if entries.len() == before {
+            bail!("Unknown Claude profile: {}", label);
+        }
+        if entries.is_empty() {
+            bail!("At least one Claude profile must remain");
+        }
+        ConfigService::set_claude_profiles(entries)?;
+        Self::list_info()
+    }
+}
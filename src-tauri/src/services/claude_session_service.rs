@@ -1,6 +1,7 @@
 //! Claude session service
 //!
-//! Service for reading and parsing Claude Code session data from ~/.claude folder.
+//! Service for reading and parsing Claude Code session data from every
+//! registered Claude profile (~/.claude by default).
 //! Discovers sessions by directly scanning .jsonl files (not sessions-index.json).
 
 use anyhow::{Context, Result};
@@ -8,6 +9,8 @@ use std::fs;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
+use crate::services::claude_profile_service::ClaudeProfile;
+use crate::services::ClaudeProfileService;
 use crate::types::{
     ClaudeContentBlock, ClaudeMessage, ClaudeSession, ClaudeSessionDetail,
     ClaudeSessionPage, ClaudeSessionStats, ClaudeToolUse, RawClaudeMessage,
@@ -34,6 +37,7 @@ struct TitlePreview {
 struct SessionFileRef {
     path: PathBuf,
     project_path: String,
+    profile: String,
     mtime_ms: i64,
 }
 
@@ -41,17 +45,6 @@ struct SessionFileRef {
 pub struct ClaudeSessionService;
 
 impl ClaudeSessionService {
-    /// Get the path to the .claude directory
-    fn get_claude_dir() -> Result<PathBuf> {
-        let home = dirs::home_dir().context("Failed to get home directory")?;
-        Ok(home.join(".claude"))
-    }
-
-    /// Get the path to the projects directory
-    fn get_projects_dir() -> Result<PathBuf> {
-        Ok(Self::get_claude_dir()?.join("projects"))
-    }
-
     /// Convert a project path to its .claude folder name format
     /// e.g., "/Users/zhnd/dev/projects/lumo" -> "-Users-zhnd-dev-projects-lumo"
     fn project_path_to_folder_name(project_path: &str) -> String {
@@ -297,6 +290,7 @@ impl ClaudeSessionService {
     fn build_session_from_file(
         jsonl_path: &Path,
         project_path_hint: Option<&str>,
+        profile: &str,
     ) -> Option<ClaudeSession> {
         let meta = Self::extract_session_meta(jsonl_path)?;
         let project_path = project_path_hint
@@ -311,7 +305,7 @@ impl ClaudeSessionService {
             })
             .unwrap_or_default();
 
-        Some(Self::meta_to_session(&meta, &project_path))
+        Some(Self::meta_to_session(&meta, &project_path, profile))
     }
 
     fn file_mtime_ms(path: &Path) -> i64 {
@@ -323,7 +317,11 @@ impl ClaudeSessionService {
             .unwrap_or(0)
     }
 
-    fn list_session_files_in_project_dir(project_dir: &Path, project_path: &str) -> Vec<SessionFileRef> {
+    fn list_session_files_in_project_dir(
+        project_dir: &Path,
+        project_path: &str,
+        profile: &str,
+    ) -> Vec<SessionFileRef> {
         let entries = match fs::read_dir(project_dir) {
             Ok(entries) => entries,
             Err(_) => return vec![],
@@ -351,14 +349,18 @@ impl ClaudeSessionService {
                 mtime_ms: Self::file_mtime_ms(&path),
                 path,
                 project_path: project_path.to_string(),
+                profile: profile.to_string(),
             });
         }
 
         files
     }
 
-    fn list_session_files_for_scope(project_path: Option<&str>) -> Result<Vec<SessionFileRef>> {
-        let projects_dir = Self::get_projects_dir()?;
+    fn list_session_files_in_profile(
+        profile: &ClaudeProfile,
+        project_path: Option<&str>,
+    ) -> Result<Vec<SessionFileRef>> {
+        let projects_dir = profile.projects_dir();
         if !projects_dir.exists() {
             return Ok(vec![]);
         }
@@ -372,7 +374,11 @@ impl ClaudeSessionService {
                 return Ok(vec![]);
             }
 
-            files.extend(Self::list_session_files_in_project_dir(&project_dir, project_path));
+            files.extend(Self::list_session_files_in_project_dir(
+                &project_dir,
+                project_path,
+                &profile.label,
+            ));
         } else {
             for entry in fs::read_dir(&projects_dir)? {
                 let entry = entry?;
@@ -386,10 +392,26 @@ impl ClaudeSessionService {
                 };
 
                 let project_path = Self::folder_name_to_project_path(folder_name);
-                files.extend(Self::list_session_files_in_project_dir(&path, &project_path));
+                files.extend(Self::list_session_files_in_project_dir(
+                    &path,
+                    &project_path,
+                    &profile.label,
+                ));
             }
         }
 
+        Ok(files)
+    }
+
+    fn list_session_files_for_scope(
+        project_path: Option<&str>,
+        profile: Option<&str>,
+    ) -> Result<Vec<SessionFileRef>> {
+        let mut files = Vec::new();
+        for profile in ClaudeProfileService::select(profile)? {
+            files.extend(Self::list_session_files_in_profile(&profile, project_path)?);
+        }
+
         files.sort_by(|a, b| b.mtime_ms.cmp(&a.mtime_ms));
         Ok(files)
     }
@@ -398,9 +420,11 @@ impl ClaudeSessionService {
         let mut sessions = Vec::with_capacity(file_refs.len());
 
         for file_ref in file_refs {
-            let Some(session) =
-                Self::build_session_from_file(&file_ref.path, Some(&file_ref.project_path))
-            else {
+            let Some(session) = Self::build_session_from_file(
+                &file_ref.path,
+                Some(&file_ref.project_path),
+                &file_ref.profile,
+            ) else {
                 continue;
             };
 
@@ -413,7 +437,7 @@ impl ClaudeSessionService {
     }
 
     /// Convert a SessionMeta into a ClaudeSession, using the project path for context.
-    fn meta_to_session(meta: &SessionMeta, project_path: &str) -> ClaudeSession {
+    fn meta_to_session(meta: &SessionMeta, project_path: &str, profile: &str) -> ClaudeSession {
         let created = meta
             .first_timestamp
             .clone()
@@ -433,16 +457,19 @@ impl ClaudeSessionService {
             git_branch: meta.git_branch.clone(),
             project_path: project_path.to_string(),
             is_sidechain: meta.is_sidechain,
+            profile: profile.to_string(),
         }
     }
 
+    /// Sessions newest first, across all profiles unless `profile` is given.
     pub fn get_sessions_page(
         project_path: Option<&str>,
+        profile: Option<&str>,
         offset: usize,
         limit: usize,
     ) -> Result<ClaudeSessionPage> {
         let normalized_limit = limit.clamp(1, 100);
-        let file_refs = Self::list_session_files_for_scope(project_path)?;
+        let file_refs = Self::list_session_files_for_scope(project_path, profile)?;
         let total_count = file_refs.len();
         let start = offset.min(total_count);
         let end = (start + normalized_limit).min(total_count);
@@ -468,11 +495,14 @@ impl ClaudeSessionService {
         let parent = path.parent().context("Invalid session path")?;
         let folder_name = parent.file_name().and_then(|n| n.to_str()).unwrap_or("");
         let project_path_decoded = Self::folder_name_to_project_path(folder_name);
+        let profile = ClaudeProfileService::for_path(&path)?
+            .map(|p| p.label)
+            .unwrap_or_default();
 
         // Build session metadata from the file itself
         let session = if let Some(meta) = Self::extract_session_meta(&path) {
             let project_path = meta.cwd.clone().unwrap_or(project_path_decoded);
-            Self::meta_to_session(&meta, &project_path)
+            Self::meta_to_session(&meta, &project_path, &profile)
         } else {
             let file_name = path
                 .file_stem()
@@ -492,6 +522,7 @@ impl ClaudeSessionService {
                 git_branch: None,
                 project_path: project_path_decoded,
                 is_sidechain: false,
+                profile,
             }
         };
 
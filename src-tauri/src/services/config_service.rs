@@ -4,13 +4,36 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use shared::calendar::Calendar;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::types::CalendarSettings;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub anthropic_api_key: Option<String>,
+    /// Registered Claude Code config roots. Empty means the default profile.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub claude_profiles: Vec<ClaudeProfileEntry>,
+    /// Daily spend target in USD, shown by `lumo statusline`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daily_spend_target: Option<f64>,
+    /// IANA timezone days and hours are bucketed in; system zone if unset.
+    /// Read by `shared::calendar`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// First day of the week, e.g. "sunday"; Monday if unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub week_start: Option<String>,
+}
+
+/// A Claude Code config root registered by the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaudeProfileEntry {
+    pub label: String,
+    pub path: String,
 }
 
 pub struct ConfigService;
@@ -62,4 +85,47 @@ impl ConfigService {
         config.anthropic_api_key = None;
         Self::write_config(&config)
     }
+
+    pub fn get_claude_profiles() -> Result<Vec<ClaudeProfileEntry>> {
+        Ok(Self::read_config()?.claude_profiles)
+    }
+
+    pub fn set_claude_profiles(profiles: Vec<ClaudeProfileEntry>) -> Result<()> {
+        let mut config = Self::read_config()?;
+        config.claude_profiles = profiles;
+        Self::write_config(&config)
+    }
+
+    pub fn get_daily_spend_target() -> Result<Option<f64>> {
+        Ok(Self::read_config()?.daily_spend_target)
+    }
+
+    pub fn set_daily_spend_target(target: Option<f64>) -> Result<()> {
+        let mut config = Self::read_config()?;
+        config.daily_spend_target = target.filter(|t| *t > 0.0);
+        Self::write_config(&config)
+    }
+
+    pub fn get_calendar_settings() -> Result<CalendarSettings> {
+        let config = Self::read_config()?;
+        Ok(CalendarSettings {
+            timezone: config.timezone,
+            week_start: config.week_start,
+        })
+    }
+
+    /// Set or clear the calendar settings, rejecting unknown names
+    pub fn set_calendar_settings(settings: CalendarSettings) -> Result<()> {
+        let timezone = settings.timezone.filter(|t| !t.trim().is_empty());
+        let week_start = settings
+            .week_start
+            .filter(|w| !w.trim().is_empty())
+            .map(|w| w.to_lowercase());
+        Calendar::parse(timezone.as_deref(), week_start.as_deref())?;
+
+        let mut config = Self::read_config()?;
+        config.timezone = timezone;
+        config.week_start = week_start;
+        Self::write_config(&config)
+    }
 }
@@ -0,0 +1,322 @@
+//! Log service
+//!
+//! Reads the daemon's rotated log files (`~/.lumo/logs/daemon-*.log`, text or
+//! JSON lines) and the app's own log files, and returns the most recent
+//! entries matching a level, time range and search filter.
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDateTime};
+use serde_json::Value;
+use shared::daemon_logs;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::types::{LogEntry, LogLevel, LogQuery, LogSource};
+
+/// Entries returned when the query sets no limit
+const DEFAULT_LIMIT: usize = 500;
+
+pub struct LogService;
+
+impl LogService {
+    /// Most recent entries matching `query`, oldest first
+    pub fn get_entries(app_log_dir: &Path, query: &LogQuery) -> Result<Vec<LogEntry>> {
+        let limit = query.limit.map_or(DEFAULT_LIMIT, |l| l as usize);
+        let mut entries = Vec::new();
+
+        if query.source != Some(LogSource::App) {
+            let files = daemon_logs::log_files(&daemon_logs::log_dir()?)?;
+            entries.extend(read_source(LogSource::Daemon, &files, query, limit));
+        }
+        if query.source != Some(LogSource::Daemon) {
+            let files = app_log_files(app_log_dir)?;
+            entries.extend(read_source(LogSource::App, &files, query, limit));
+        }
+
+        entries.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+        let skip = entries.len().saturating_sub(limit);
+        Ok(entries.split_off(skip))
+    }
+}
+
+/// App log files (the current file and rotated ones), oldest first
+fn app_log_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<(PathBuf, f64)> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "log"))
+        .map(|path| {
+            let modified = modified_ms(&path).unwrap_or(0.0);
+            (path, modified)
+        })
+        .collect();
+    files.sort_by(|a, b| a.1.total_cmp(&b.1));
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+fn modified_ms(path: &Path) -> Option<f64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as f64)
+}
+
+/// Read `files` newest first until `limit` matching entries are found
+fn read_source(
+    source: LogSource,
+    files: &[PathBuf],
+    query: &LogQuery,
+    limit: usize,
+) -> Vec<LogEntry> {
+    let mut chunks = Vec::new();
+    let mut count = 0;
+
+    for path in files.iter().rev() {
+        // Nothing in a file last written before the range starts can match
+        if let (Some(start), Some(modified)) = (query.start_time, modified_ms(path)) {
+            if modified < start {
+                break;
+            }
+        }
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        let matching: Vec<LogEntry> = parse_lines(source, &content)
+            .into_iter()
+            .filter(|entry| matches(entry, query))
+            .collect();
+        count += matching.len();
+        chunks.push(matching);
+        if count >= limit {
+            break;
+        }
+    }
+
+    chunks.into_iter().rev().flatten().collect()
+}
+
+fn matches(entry: &LogEntry, query: &LogQuery) -> bool {
+    if query.level.is_some_and(|level| entry.level < level) {
+        return false;
+    }
+    if query
+        .start_time
+        .is_some_and(|start| entry.timestamp < start)
+    {
+        return false;
+    }
+    if query.end_time.is_some_and(|end| entry.timestamp >= end) {
+        return false;
+    }
+    match query
+        .search
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        Some(search) => {
+            let search = search.to_lowercase();
+            entry.message.to_lowercase().contains(&search)
+                || entry.target.to_lowercase().contains(&search)
+        }
+        None => true,
+    }
+}
+
+/// Parse a log file. Lines that don't start an entry are appended to the
+/// previous entry's message.
+fn parse_lines(source: LogSource, content: &str) -> Vec<LogEntry> {
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for line in content.lines() {
+        let parsed = match source {
+            LogSource::Daemon => parse_daemon_line(line),
+            LogSource::App => parse_app_line(line),
+        };
+        match parsed {
+            Some(entry) => entries.push(entry),
+            None if !line.trim().is_empty() => {
+                if let Some(last) = entries.last_mut() {
+                    last.message.push('\n');
+                    last.message.push_str(line);
+                }
+            }
+            None => {}
+        }
+    }
+    entries
+}
+
+/// Daemon text line: `2025-01-01T12:00:00.123456Z  INFO target: message`,
+/// possibly with `span{fields}:` before the target, or a JSON line with
+/// `timestamp`, `level`, `target`, `message` and `fields`
+fn parse_daemon_line(line: &str) -> Option<LogEntry> {
+    if line.starts_with('{') {
+        return parse_daemon_json_line(line);
+    }
+
+    let (timestamp, rest) = line.split_once(' ')?;
+    let timestamp = DateTime::parse_from_rfc3339(timestamp).ok()?;
+    let (level, rest) = rest.trim_start().split_once(' ')?;
+    let level = LogLevel::parse(level)?;
+    let rest = strip_spans(rest);
+    let (target, message) = rest.split_once(": ").unwrap_or(("", rest));
+
+    Some(LogEntry {
+        source: LogSource::Daemon,
+        timestamp: timestamp.timestamp_millis() as f64,
+        level,
+        target: target.to_string(),
+        message: message.to_string(),
+    })
+}
+
+/// Skip the `span{fields}:inner{fields}: ` prefix of events inside spans.
+/// Field values may contain spaces and `: `, so braces are matched.
+fn strip_spans(rest: &str) -> &str {
+    // A span name is followed by its fields, a target by `: `
+    if !rest
+        .split([' ', ':'])
+        .next()
+        .is_some_and(|name| name.contains('{'))
+    {
+        return rest;
+    }
+
+    let mut depth = 0usize;
+    for (i, c) in rest.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ' ' if depth == 0 => {
+                return if rest[..i].ends_with(':') {
+                    &rest[i + 1..]
+                } else {
+                    rest
+                };
+            }
+            _ => {}
+        }
+    }
+    rest
+}
+
+fn parse_daemon_json_line(line: &str) -> Option<LogEntry> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    let timestamp = DateTime::parse_from_rfc3339(value.get("timestamp")?.as_str()?).ok()?;
+    let level = LogLevel::parse(value.get("level")?.as_str()?)?;
+    let target = value.get("target").and_then(Value::as_str).unwrap_or("");
+
+    // Render structured fields like the text format does
+    let mut message = match value.get("message") {
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+        None => String::new(),
+    };
+    if let Some(fields) = value.get("fields").and_then(Value::as_object) {
+        for (key, field) in fields {
+            let field = match field {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            message.push_str(&format!(" {}={}", key, field));
+        }
+    }
+
+    Some(LogEntry {
+        source: LogSource::Daemon,
+        timestamp: timestamp.timestamp_millis() as f64,
+        level,
+        target: target.to_string(),
+        message,
+    })
+}
+
+/// App line as written by tauri-plugin-log (UTC):
+/// `[2025-01-01][12:00:00][target][INFO] message`
+fn parse_app_line(line: &str) -> Option<LogEntry> {
+    let (date, rest) = bracketed(line)?;
+    let (time, rest) = bracketed(rest)?;
+    let (target, rest) = bracketed(rest)?;
+    let (level, message) = bracketed(rest)?;
+
+    let timestamp =
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M:%S").ok()?;
+
+    Some(LogEntry {
+        source: LogSource::App,
+        timestamp: timestamp.and_utc().timestamp_millis() as f64,
+        level: LogLevel::parse(level)?,
+        target: target.to_string(),
+        message: message.strip_prefix(' ').unwrap_or(message).to_string(),
+    })
+}
+
+/// Split `[value]rest` into `(value, rest)`
+fn bracketed(s: &str) -> Option<(&str, &str)> {
+    s.strip_prefix('[')?.split_once(']')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_daemon_text_and_json_lines() {
+        let content = "\
+2025-01-01T12:00:00.500000Z  INFO lumo_daemon: Starting Lumo Daemon v0.3.1
+2025-01-01T12:00:01.000000Z ERROR lumo_daemon::server: Failed to bind
+caused by: address in use
+2025-01-01T12:00:01.500000Z  INFO request{method=POST uri=/v1/logs note=\"a: b\"}:ingest: tower_http::trace::on_response: finished processing request status=200
+{\"timestamp\":\"2025-01-01T12:00:02Z\",\"level\":\"WARN\",\"target\":\"lumo_daemon::handlers\",\"message\":\"Quarantined\",\"fields\":{\"count\":3}}
+";
+        let entries = parse_lines(LogSource::Daemon, content);
+        assert_eq!(entries.len(), 4);
+
+        assert_eq!(entries[0].timestamp, 1735732800500.0);
+        assert_eq!(entries[0].level, LogLevel::Info);
+        assert_eq!(entries[0].target, "lumo_daemon");
+        assert_eq!(entries[0].message, "Starting Lumo Daemon v0.3.1");
+
+        assert_eq!(entries[1].level, LogLevel::Error);
+        assert_eq!(
+            entries[1].message,
+            "Failed to bind\ncaused by: address in use"
+        );
+
+        assert_eq!(entries[2].target, "tower_http::trace::on_response");
+        assert_eq!(entries[2].message, "finished processing request status=200");
+
+        assert_eq!(entries[3].level, LogLevel::Warn);
+        assert_eq!(entries[3].target, "lumo_daemon::handlers");
+        assert_eq!(entries[3].message, "Quarantined count=3");
+    }
+
+    #[test]
+    fn test_parse_app_line_and_filter() {
+        let content = "\
+[2025-01-01][12:00:00][app_lib::daemon][INFO] Daemon is healthy
+[2025-01-01][12:00:05][app_lib][ERROR] Failed to initialize database: locked
+";
+        let entries = parse_lines(LogSource::App, content);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].timestamp, 1735732800000.0);
+        assert_eq!(entries[0].target, "app_lib::daemon");
+        assert_eq!(entries[0].message, "Daemon is healthy");
+
+        let query = LogQuery {
+            level: Some(LogLevel::Warn),
+            ..LogQuery::default()
+        };
+        assert!(!matches(&entries[0], &query));
+        assert!(matches(&entries[1], &query));
+
+        let query = LogQuery {
+            start_time: Some(1735732801000.0),
+            search: Some("DATABASE".to_string()),
+            ..LogQuery::default()
+        };
+        assert!(!matches(&entries[0], &query));
+        assert!(matches(&entries[1], &query));
+    }
+}
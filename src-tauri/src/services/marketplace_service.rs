@@ -1,7 +1,8 @@
 //! Marketplace service
 //!
 //! Service for browsing and managing Claude Code plugin marketplaces.
-//! Reads marketplace data from ~/.claude/plugins/marketplaces/.
+//! Reads marketplace data from the plugins/ directory of every Claude profile
+//! (~/.claude/plugins/ by default). CLI commands run against one profile.
 
 use anyhow::{Context, Result};
 use serde::Deserialize;
@@ -9,6 +10,8 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::services::claude_profile_service::ClaudeProfile;
+use crate::services::ClaudeProfileService;
 use crate::types::{MarketplaceCommandResult, MarketplaceInfo, MarketplacePlugin, PluginInstalledScope};
 
 /// Marketplace manifest JSON structure
@@ -60,6 +63,9 @@ struct KnownMarketplaceEntry {
     install_location: String,
     #[serde(default)]
     last_updated: Option<String>,
+    /// Profiles that know this marketplace, filled in after reading
+    #[serde(skip)]
+    profiles: Vec<String>,
 }
 
 /// installed_plugins.json structure
@@ -93,72 +99,92 @@ struct InstallCountEntry {
 pub struct MarketplaceService;
 
 impl MarketplaceService {
-    fn get_plugins_dir() -> Result<PathBuf> {
-        let home = dirs::home_dir().context("Failed to get home directory")?;
-        Ok(home.join(".claude").join("plugins"))
+    /// Profile a CLI command should run against (the primary one by default)
+    fn target_profile(profile: Option<&str>) -> Result<ClaudeProfile> {
+        Ok(ClaudeProfileService::select(profile)?.remove(0))
     }
 
+    /// Known marketplaces of all profiles, merged by name
     fn read_known_marketplaces() -> Result<HashMap<String, KnownMarketplaceEntry>> {
-        let known_path = Self::get_plugins_dir()?.join("known_marketplaces.json");
-        Ok(fs::read_to_string(&known_path)
-            .ok()
-            .and_then(|c| serde_json::from_str(&c).ok())
-            .unwrap_or_default())
+        let mut result: HashMap<String, KnownMarketplaceEntry> = HashMap::new();
+        for profile in ClaudeProfileService::list()? {
+            let known_path = profile.plugins_dir().join("known_marketplaces.json");
+            let known: HashMap<String, KnownMarketplaceEntry> = fs::read_to_string(&known_path)
+                .ok()
+                .and_then(|c| serde_json::from_str(&c).ok())
+                .unwrap_or_default();
+            for (name, entry) in known {
+                result
+                    .entry(name)
+                    .or_insert(entry)
+                    .profiles
+                    .push(profile.label.clone());
+            }
+        }
+        Ok(result)
     }
 
-    /// Read all installed scopes per plugin name.
+    /// Read all installed scopes per plugin name, across all profiles.
     fn read_installed_scopes() -> Result<HashMap<String, Vec<PluginInstalledScope>>> {
-        let installed_path = Self::get_plugins_dir()?.join("installed_plugins.json");
-        let installed: InstalledPluginsFile = fs::read_to_string(&installed_path)
-            .ok()
-            .and_then(|c| serde_json::from_str(&c).ok())
-            .unwrap_or(InstalledPluginsFile {
-                plugins: HashMap::new(),
-            });
-
         let mut result: HashMap<String, Vec<PluginInstalledScope>> = HashMap::new();
-        for (key, records) in &installed.plugins {
-            let plugin_name = key.split('@').next().unwrap_or(key).to_string();
-            let scopes = result.entry(plugin_name).or_default();
-            for r in records {
-                // Deduplicate: skip if we already have this exact scope+path
-                let already_exists = scopes.iter().any(|s| {
-                    s.scope == r.scope && s.project_path == r.project_path
+        for profile in ClaudeProfileService::list()? {
+            let installed_path = profile.plugins_dir().join("installed_plugins.json");
+            let installed: InstalledPluginsFile = fs::read_to_string(&installed_path)
+                .ok()
+                .and_then(|c| serde_json::from_str(&c).ok())
+                .unwrap_or(InstalledPluginsFile {
+                    plugins: HashMap::new(),
                 });
-                if !already_exists {
-                    scopes.push(PluginInstalledScope {
-                        scope: r.scope.clone(),
-                        project_path: r.project_path.clone(),
+
+            for (key, records) in &installed.plugins {
+                let plugin_name = key.split('@').next().unwrap_or(key).to_string();
+                let scopes = result.entry(plugin_name).or_default();
+                for r in records {
+                    // Deduplicate: skip if we already have this exact scope+path
+                    let already_exists = scopes.iter().any(|s| {
+                        s.scope == r.scope
+                            && s.project_path == r.project_path
+                            && s.profile == profile.label
                     });
+                    if !already_exists {
+                        scopes.push(PluginInstalledScope {
+                            scope: r.scope.clone(),
+                            project_path: r.project_path.clone(),
+                            profile: profile.label.clone(),
+                        });
+                    }
                 }
             }
         }
         Ok(result)
     }
 
-    /// Read install counts, keyed by plugin name (aggregated across marketplaces).
+    /// Read install counts, keyed by plugin name (aggregated across
+    /// marketplaces and profiles).
     fn read_install_counts() -> Result<HashMap<String, f64>> {
-        let counts_path = Self::get_plugins_dir()?.join("install-counts-cache.json");
-        Ok(fs::read_to_string(&counts_path)
-            .ok()
-            .and_then(|c| serde_json::from_str::<InstallCountsCache>(&c).ok())
-            .map(|cache| {
-                let mut map: HashMap<String, f64> = HashMap::new();
-                for entry in cache.counts {
-                    let name = entry
-                        .plugin
-                        .split('@')
-                        .next()
-                        .unwrap_or(&entry.plugin)
-                        .to_string();
-                    let current = map.entry(name).or_insert(0.0);
-                    if entry.unique_installs > *current {
-                        *current = entry.unique_installs;
-                    }
+        let mut map: HashMap<String, f64> = HashMap::new();
+        for profile in ClaudeProfileService::list()? {
+            let counts_path = profile.plugins_dir().join("install-counts-cache.json");
+            let Some(cache) = fs::read_to_string(&counts_path)
+                .ok()
+                .and_then(|c| serde_json::from_str::<InstallCountsCache>(&c).ok())
+            else {
+                continue;
+            };
+            for entry in cache.counts {
+                let name = entry
+                    .plugin
+                    .split('@')
+                    .next()
+                    .unwrap_or(&entry.plugin)
+                    .to_string();
+                let current = map.entry(name).or_insert(0.0);
+                if entry.unique_installs > *current {
+                    *current = entry.unique_installs;
                 }
-                map
-            })
-            .unwrap_or_default())
+            }
+        }
+        Ok(map)
     }
 
     fn read_manifest(install_location: &str) -> Option<MarketplaceManifest> {
@@ -179,12 +205,14 @@ impl MarketplaceService {
     async fn run_claude_command_in_dir(
         args: &[&str],
         cwd: &str,
+        profile: &ClaudeProfile,
     ) -> Result<MarketplaceCommandResult> {
         let claude_bin = Self::find_claude_binary()?;
 
-        let output = tokio::process::Command::new(&claude_bin)
-            .args(args)
-            .current_dir(cwd)
+        let mut cmd = tokio::process::Command::new(&claude_bin);
+        cmd.args(args).current_dir(cwd);
+        profile.apply_env(&mut cmd);
+        let output = cmd
             .output()
             .await
             .context("Failed to execute claude command")?;
@@ -213,11 +241,16 @@ impl MarketplaceService {
         }
     }
 
-    async fn run_claude_command(args: &[&str]) -> Result<MarketplaceCommandResult> {
+    async fn run_claude_command(
+        args: &[&str],
+        profile: &ClaudeProfile,
+    ) -> Result<MarketplaceCommandResult> {
         let claude_bin = Self::find_claude_binary()?;
 
-        let output = tokio::process::Command::new(&claude_bin)
-            .args(args)
+        let mut cmd = tokio::process::Command::new(&claude_bin);
+        cmd.args(args);
+        profile.apply_env(&mut cmd);
+        let output = cmd
             .output()
             .await
             .context("Failed to execute claude command")?;
@@ -334,6 +367,7 @@ impl MarketplaceService {
                 owner_name,
                 plugin_count,
                 last_updated: entry.last_updated.clone(),
+                profiles: entry.profiles.clone(),
             });
         }
 
@@ -345,15 +379,17 @@ impl MarketplaceService {
     pub async fn install_plugin(
         name: &str,
         project_path: Option<&str>,
+        profile: Option<&str>,
     ) -> Result<MarketplaceCommandResult> {
+        let profile = Self::target_profile(profile)?;
         match project_path {
             Some(path) => {
                 let args = ["plugin", "install", name, "--scope", "project"];
-                Self::run_claude_command_in_dir(&args, path).await
+                Self::run_claude_command_in_dir(&args, path, &profile).await
             }
             None => {
                 let args = ["plugin", "install", name];
-                Self::run_claude_command(&args).await
+                Self::run_claude_command(&args, &profile).await
             }
         }
     }
@@ -362,31 +398,58 @@ impl MarketplaceService {
     pub async fn uninstall_plugin(
         name: &str,
         project_path: Option<&str>,
+        profile: Option<&str>,
     ) -> Result<MarketplaceCommandResult> {
+        let profile = Self::target_profile(profile)?;
         match project_path {
             Some(path) => {
                 let args = ["plugin", "uninstall", name, "--scope", "project"];
-                Self::run_claude_command_in_dir(&args, path).await
+                Self::run_claude_command_in_dir(&args, path, &profile).await
             }
             None => {
                 let args = ["plugin", "uninstall", name];
-                Self::run_claude_command(&args).await
+                Self::run_claude_command(&args, &profile).await
             }
         }
     }
 
     /// Add a marketplace source (URL, path, or GitHub repo).
-    pub async fn add_marketplace(source: &str) -> Result<MarketplaceCommandResult> {
-        Self::run_claude_command(&["plugin", "marketplace", "add", source]).await
+    pub async fn add_marketplace(
+        source: &str,
+        profile: Option<&str>,
+    ) -> Result<MarketplaceCommandResult> {
+        let profile = Self::target_profile(profile)?;
+        Self::run_claude_command(&["plugin", "marketplace", "add", source], &profile).await
     }
 
     /// Remove a configured marketplace by name.
-    pub async fn remove_marketplace(name: &str) -> Result<MarketplaceCommandResult> {
-        Self::run_claude_command(&["plugin", "marketplace", "remove", name]).await
+    pub async fn remove_marketplace(
+        name: &str,
+        profile: Option<&str>,
+    ) -> Result<MarketplaceCommandResult> {
+        let profile = Self::target_profile(profile)?;
+        Self::run_claude_command(&["plugin", "marketplace", "remove", name], &profile).await
     }
 
-    /// Update all marketplace sources.
+    /// Update the marketplace sources of every profile.
     pub async fn update_marketplaces() -> Result<MarketplaceCommandResult> {
-        Self::run_claude_command(&["plugin", "marketplace", "update"]).await
+        let mut messages = Vec::new();
+        let mut success = true;
+        let profiles = ClaudeProfileService::list()?;
+        let labelled = profiles.len() > 1;
+        for profile in &profiles {
+            let result =
+                Self::run_claude_command(&["plugin", "marketplace", "update"], profile).await?;
+            success &= result.success;
+            messages.push(if labelled {
+                format!("[{}] {}", profile.label, result.message)
+            } else {
+                result.message
+            });
+        }
+        Ok(MarketplaceCommandResult {
+            success,
+            message: messages.join("\n"),
+        })
     }
 }
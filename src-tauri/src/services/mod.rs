@@ -3,16 +3,18 @@
 //! These services contain business logic, data aggregation, and calculations.
 
 mod analytics_service;
+mod budget_service;
 mod claude_config_service;
+pub mod claude_profile_service;
 mod claude_session_service;
-mod config_service;
+pub mod config_service;
+mod log_service;
 pub mod notification_poller;
 mod notification_settings_service;
 pub mod session_cache;
 pub mod session_watcher;
 mod stats_service;
 mod subscription_usage_service;
-pub mod time_range;
 mod tools_service;
 mod trends_service;
 mod usage_service;
@@ -22,9 +24,12 @@ mod skills_service;
 mod wrapped_service;
 
 pub use analytics_service::AnalyticsService;
+pub use budget_service::BudgetService;
 pub use claude_config_service::ClaudeConfigService;
+pub use claude_profile_service::ClaudeProfileService;
 pub use claude_session_service::ClaudeSessionService;
 pub use config_service::ConfigService;
+pub use log_service::LogService;
 pub use notification_settings_service::NotificationSettingsService;
 pub use stats_service::StatsService;
 pub use subscription_usage_service::SubscriptionUsageService;
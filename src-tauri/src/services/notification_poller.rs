@@ -112,6 +112,8 @@ async fn poll_and_notify(app_handle: &AppHandle) -> anyhow::Result<()> {
 /// - Notification events: use Claude Code's original title/message.
 /// - Stop: "Claude Code" / "Task completed · {project}"
 /// - SubagentStop: "Claude Code" / "{agent} completed · {project}"
+/// - Anomaly: the daemon's title / "{message} · {project}"
+/// - Budget and other events: their own title/message
 fn format_notification(notif: &Notification) -> (String, String) {
     match notif.hook_event.as_str() {
         "Notification" => {
@@ -139,6 +141,13 @@ fn format_notification(notif: &Notification) -> (String, String) {
                 format!("{} completed · {}", agent, project),
             )
         }
+        "Anomaly" => {
+            let project = extract_project_name(notif.cwd.as_deref());
+            (
+                notif.title.clone(),
+                format!("{} · {}", notif.message, project),
+            )
+        }
         _ => {
             let title = if notif.title.is_empty() {
                 "Claude Code".to_string()
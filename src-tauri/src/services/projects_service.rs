@@ -1,26 +1,29 @@
 //! Projects service
 //!
 //! Service for discovering and summarizing Claude Code projects.
-//! Projects are derived from the ~/.claude/projects/ directory structure.
+//! Projects are derived from the projects/ directory of every Claude profile
+//! (~/.claude/projects/ by default); a project used from several profiles is
+//! listed once.
 
-use anyhow::{Context, Result};
+use anyhow::Result;
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
-use crate::services::SkillsService;
+use crate::services::{ClaudeProfileService, SkillsService};
 use crate::types::ClaudeProjectSummary;
 
+/// Sessions of one project, merged across profiles
+#[derive(Default)]
+struct ProjectSessions {
+    session_count: i32,
+    latest_ms: i64,
+    profiles: Vec<String>,
+}
+
 pub struct ProjectsService;
 
 impl ProjectsService {
-    fn get_claude_dir() -> Result<PathBuf> {
-        let home = dirs::home_dir().context("Failed to get home directory")?;
-        Ok(home.join(".claude"))
-    }
-
-    fn get_projects_dir() -> Result<PathBuf> {
-        Ok(Self::get_claude_dir()?.join("projects"))
-    }
 
     /// Decode a folder name back to a project path.
     /// e.g., "-Users-zhnd-dev-projects-lumo" -> "/Users/zhnd/dev/projects/lumo"
@@ -45,14 +48,60 @@ impl ProjectsService {
     }
 
     pub fn get_projects_summary() -> Result<Vec<ClaudeProjectSummary>> {
-        let projects_dir = Self::get_projects_dir()?;
-        if !projects_dir.exists() {
-            return Ok(vec![]);
+        let mut by_path: HashMap<String, ProjectSessions> = HashMap::new();
+        for profile in ClaudeProfileService::list()? {
+            let projects_dir = profile.projects_dir();
+            if projects_dir.exists() {
+                Self::collect_project_sessions(&projects_dir, &profile.label, &mut by_path)?;
+            }
         }
 
         let mut projects = Vec::new();
 
-        for entry in fs::read_dir(&projects_dir)? {
+        for (project_path, sessions) in by_path {
+            let project_name = std::path::Path::new(&project_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(&project_path)
+                .to_string();
+
+            let last_updated = Self::timestamp_to_rfc3339(sessions.latest_ms)
+                .unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+
+            // Count skills for this project
+            let claude_dir = PathBuf::from(&project_path).join(".claude");
+            let skill_count = if claude_dir.exists() {
+                SkillsService::count_in_dir(&claude_dir)
+            } else {
+                0
+            };
+
+            projects.push(ClaudeProjectSummary {
+                project_path,
+                project_name,
+                session_count: sessions.session_count,
+                skill_count,
+                last_updated,
+                profiles: sessions.profiles,
+            });
+        }
+
+        projects.sort_by(|a, b| {
+            let a_time = Self::parse_time_millis(&a.last_updated);
+            let b_time = Self::parse_time_millis(&b.last_updated);
+            b_time.cmp(&a_time)
+        });
+
+        Ok(projects)
+    }
+
+    /// Count sessions per project in one profile's projects directory
+    fn collect_project_sessions(
+        projects_dir: &std::path::Path,
+        profile: &str,
+        by_path: &mut HashMap<String, ProjectSessions>,
+    ) -> Result<()> {
+        for entry in fs::read_dir(projects_dir)? {
             let entry = entry?;
             let path = entry.path();
             if !path.is_dir() {
@@ -114,44 +163,20 @@ impl ProjectsService {
                     .unwrap_or(0);
             }
 
-            let project_name = std::path::Path::new(&project_path)
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or(&project_path)
-                .to_string();
-
-            let last_updated = Self::timestamp_to_rfc3339(latest_ms)
-                .unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
-
-            // Count skills for this project
-            let claude_dir = PathBuf::from(&project_path).join(".claude");
-            let skill_count = if claude_dir.exists() {
-                SkillsService::count_in_dir(&claude_dir)
-            } else {
-                0
-            };
-
-            projects.push(ClaudeProjectSummary {
-                project_path,
-                project_name,
-                session_count,
-                skill_count,
-                last_updated,
-            });
+            let merged = by_path.entry(project_path).or_default();
+            merged.session_count += session_count;
+            merged.latest_ms = merged.latest_ms.max(latest_ms);
+            merged.profiles.push(profile.to_string());
         }
 
-        projects.sort_by(|a, b| {
-            let a_time = Self::parse_time_millis(&a.last_updated);
-            let b_time = Self::parse_time_millis(&b.last_updated);
-            b_time.cmp(&a_time)
-        });
-
-        Ok(projects)
+        Ok(())
     }
 
-    /// Count skills in the global ~/.claude directory
+    /// Count personal skills across all Claude profiles
     pub fn get_global_skill_count() -> Result<i32> {
-        let claude_dir = Self::get_claude_dir()?;
-        Ok(SkillsService::count_in_dir(&claude_dir))
+        Ok(ClaudeProfileService::list()?
+            .iter()
+            .map(|profile| SkillsService::count_in_dir(&profile.config_dir))
+            .sum())
     }
 }
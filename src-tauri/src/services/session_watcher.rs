@@ -1,10 +1,12 @@
 //! Session file watcher
 //!
-//! Watches `~/.claude/projects/` for changes to session files (.jsonl) and
-//! session index files, then emits Tauri events so the frontend can refresh
-//! in real time.
+//! Watches the `projects/` directory of every Claude profile
+//! (`~/.claude/projects/` by default) for changes to session files (.jsonl)
+//! and session index files, then emits Tauri events so the frontend can
+//! refresh in real time.
 
 use crate::services::session_cache::SessionDetailCache;
+use crate::services::ClaudeProfileService;
 use notify::{EventKind, RecursiveMode, Watcher};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -20,6 +22,15 @@ const RETRY_INTERVAL: Duration = Duration::from_secs(5);
 pub struct SessionFileChangedPayload {
     pub session_id: String,
     pub file_path: String,
+    /// Label of the Claude profile the session belongs to
+    pub profile: String,
+}
+
+/// A profile's projects directory being watched
+#[derive(Clone, PartialEq)]
+struct WatchDir {
+    profile: String,
+    path: PathBuf,
 }
 
 /// Start the session file watcher.
@@ -30,30 +41,42 @@ pub fn start(app_handle: AppHandle) {
     });
 }
 
-fn claude_projects_dir() -> Option<PathBuf> {
-    dirs::home_dir().map(|h| h.join(".claude").join("projects"))
+/// Existing projects directories of all registered profiles
+fn claude_projects_dirs() -> Option<Vec<WatchDir>> {
+    match ClaudeProfileService::list() {
+        Ok(profiles) => Some(
+            profiles
+                .into_iter()
+                .map(|p| WatchDir {
+                    path: p.projects_dir(),
+                    profile: p.label,
+                })
+                .filter(|dir| dir.path.exists())
+                .collect(),
+        ),
+        Err(e) => {
+            log::warn!("Session watcher: could not list Claude profiles: {}", e);
+            None
+        }
+    }
 }
 
 fn run_watcher(app_handle: AppHandle) {
     loop {
-        let Some(watch_dir) = claude_projects_dir() else {
-            log::warn!("Session watcher: could not determine home directory, retrying...");
+        let Some(watch_dirs) = claude_projects_dirs() else {
             std::thread::sleep(RETRY_INTERVAL);
             continue;
         };
 
-        if !watch_dir.exists() {
+        if watch_dirs.is_empty() {
             log::info!(
-                "Session watcher: {} does not exist yet, retrying in {}s...",
-                watch_dir.display(),
+                "Session watcher: no Claude projects directory exists yet, retrying in {}s...",
                 RETRY_INTERVAL.as_secs()
             );
             std::thread::sleep(RETRY_INTERVAL);
             continue;
         }
 
-        log::info!("Session watcher: watching {}", watch_dir.display());
-
         let (tx, rx) = mpsc::channel();
 
         let mut watcher = match notify::recommended_watcher(tx) {
@@ -65,13 +88,30 @@ fn run_watcher(app_handle: AppHandle) {
             }
         };
 
-        if let Err(e) = watcher.watch(&watch_dir, RecursiveMode::Recursive) {
-            log::error!("Session watcher: failed to watch directory: {}", e);
+        let mut failed = false;
+        for dir in &watch_dirs {
+            if let Err(e) = watcher.watch(&dir.path, RecursiveMode::Recursive) {
+                log::error!(
+                    "Session watcher: failed to watch {}: {}",
+                    dir.path.display(),
+                    e
+                );
+                failed = true;
+                break;
+            }
+            log::info!(
+                "Session watcher: watching {} ({})",
+                dir.path.display(),
+                dir.profile
+            );
+        }
+        if failed {
             std::thread::sleep(RETRY_INTERVAL);
             continue;
         }
 
         let mut last_emitted: HashMap<PathBuf, Instant> = HashMap::new();
+        let mut last_rescan = Instant::now();
 
         loop {
             match rx.recv_timeout(Duration::from_millis(500)) {
@@ -84,17 +124,27 @@ fn run_watcher(app_handle: AppHandle) {
                     }
 
                     for path in event.paths {
-                        handle_path_change(&path, &app_handle, &mut last_emitted);
+                        let profile = watch_dirs
+                            .iter()
+                            .find(|dir| path.starts_with(&dir.path))
+                            .map(|dir| dir.profile.as_str())
+                            .unwrap_or_default();
+                        handle_path_change(&path, profile, &app_handle, &mut last_emitted);
                     }
                 }
                 Ok(Err(e)) => {
                     log::warn!("Session watcher: watch error: {}", e);
                 }
                 Err(mpsc::RecvTimeoutError::Timeout) => {
-                    // Check if directory still exists
-                    if !watch_dir.exists() {
+                    // Restart when a profile is added or removed, or a
+                    // projects directory appears or disappears
+                    if last_rescan.elapsed() < RETRY_INTERVAL {
+                        continue;
+                    }
+                    last_rescan = Instant::now();
+                    if claude_projects_dirs().is_some_and(|dirs| dirs != watch_dirs) {
                         log::info!(
-                            "Session watcher: watch directory removed, restarting watcher..."
+                            "Session watcher: watched directories changed, restarting watcher..."
                         );
                         break;
                     }
@@ -112,6 +162,7 @@ fn run_watcher(app_handle: AppHandle) {
 
 fn handle_path_change(
     path: &PathBuf,
+    profile: &str,
     app_handle: &AppHandle,
     last_emitted: &mut HashMap<PathBuf, Instant>,
 ) {
@@ -153,6 +204,7 @@ fn handle_path_change(
             SessionFileChangedPayload {
                 session_id,
                 file_path: file_path_str,
+                profile: profile.to_string(),
             },
         ) {
             log::warn!(
@@ -1,7 +1,8 @@
 //! Skills service
 //!
 //! Service for managing Claude Code skills from the filesystem.
-//! Supports both global (~/.claude/) and project-level (.claude/) skills.
+//! Supports both personal skills in every Claude profile (~/.claude/ by
+//! default) and project-level (.claude/) skills.
 //! Skills are directories containing SKILL.md files.
 //! Legacy commands are individual .md files in commands/ directories.
 
@@ -11,6 +12,7 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::services::ClaudeProfileService;
 use crate::types::{CodexSkillSummary, SkillCommandResult, SkillDetail, SkillScope, SkillSummary};
 
 /// YAML frontmatter from SKILL.md
@@ -62,16 +64,12 @@ struct ManifestEntry {
 pub struct SkillsService;
 
 impl SkillsService {
-    fn get_claude_dir() -> Result<PathBuf> {
-        let home = dirs::home_dir().context("Failed to get home directory")?;
-        Ok(home.join(".claude"))
-    }
-
-    /// Get the base .claude directory for a given scope
-    fn get_base_dir(project_path: Option<&str>) -> Result<PathBuf> {
+    /// Get the base .claude directory for a given scope. Personal skills
+    /// go to the given profile's root, or the primary profile's.
+    fn get_base_dir(project_path: Option<&str>, profile: Option<&str>) -> Result<PathBuf> {
         match project_path {
             Some(path) => Ok(PathBuf::from(path).join(".claude")),
-            None => Self::get_claude_dir(),
+            None => Ok(ClaudeProfileService::select(profile)?.remove(0).config_dir),
         }
     }
 
@@ -197,6 +195,7 @@ impl SkillsService {
                 package_name: manifest_entry.and_then(|e| e.package_name.clone()),
                 installed_at: manifest_entry.and_then(|e| e.installed_at.clone()),
                 path: path.to_string_lossy().to_string(),
+                profile: None,
             });
         }
     }
@@ -252,25 +251,33 @@ impl SkillsService {
                 package_name: None,
                 installed_at: None,
                 path: path.to_string_lossy().to_string(),
+                profile: None,
             });
         }
     }
 
     /// List skills for a given scope.
-    /// If project_path is None, lists global (personal) skills.
+    /// If project_path is None, lists personal skills of every Claude profile,
+    /// labelled with the profile.
     /// If project_path is Some, lists skills for that project.
     pub async fn list_skills(project_path: Option<&str>) -> Result<Vec<SkillSummary>> {
         let mut skills = Vec::new();
 
-        let base_dir = Self::get_base_dir(project_path)?;
-        let scope = if project_path.is_some() {
-            SkillScope::Project
+        if let Some(project_path) = project_path {
+            let base_dir = PathBuf::from(project_path).join(".claude");
+            Self::scan_skills_dir(&base_dir.join("skills"), SkillScope::Project, &mut skills);
+            Self::scan_commands_dir(&base_dir.join("commands"), &mut skills);
         } else {
-            SkillScope::Personal
-        };
-
-        Self::scan_skills_dir(&base_dir.join("skills"), scope, &mut skills);
-        Self::scan_commands_dir(&base_dir.join("commands"), &mut skills);
+            for profile in ClaudeProfileService::list()? {
+                let start = skills.len();
+                let base_dir = &profile.config_dir;
+                Self::scan_skills_dir(&base_dir.join("skills"), SkillScope::Personal, &mut skills);
+                Self::scan_commands_dir(&base_dir.join("commands"), &mut skills);
+                for skill in &mut skills[start..] {
+                    skill.profile = Some(profile.label.clone());
+                }
+            }
+        }
 
         skills.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
         Ok(skills)
@@ -331,9 +338,7 @@ impl SkillsService {
         }
 
         // Determine scope from path
-        let home = dirs::home_dir().unwrap_or_default();
-        let global_claude = home.join(".claude");
-        let scope = if skill_path.starts_with(&global_claude) {
+        let scope = if ClaudeProfileService::for_path(&skill_path)?.is_some() {
             if skill_path
                 .to_string_lossy()
                 .contains("/commands/")
@@ -439,13 +444,15 @@ impl SkillsService {
     }
 
     /// Create a new skill.
-    /// If project_path is None, creates in ~/.claude/skills/.
+    /// If project_path is None, creates in <profile root>/skills/ of the given
+    /// Claude profile (the primary one when None).
     /// If project_path is Some, creates in <project>/.claude/skills/.
     pub async fn create_skill(
         name: &str,
         project_path: Option<&str>,
+        profile: Option<&str>,
     ) -> Result<SkillCommandResult> {
-        let base_dir = Self::get_base_dir(project_path)?;
+        let base_dir = Self::get_base_dir(project_path, profile)?;
         let skills_dir = base_dir.join("skills");
 
         if !skills_dir.exists() {
@@ -505,6 +512,7 @@ Add your skill instructions here.
 
         let mut cmd = tokio::process::Command::new(&claude_bin);
         cmd.args(args);
+        ClaudeProfileService::primary()?.apply_env(&mut cmd);
 
         if let Some(dir) = cwd {
             cmd.current_dir(dir);
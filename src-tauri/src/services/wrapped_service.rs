@@ -3,27 +3,38 @@
 //! Business logic for the personal report card (Wrapped) feature.
 
 use anyhow::Result;
-use chrono::{Datelike, Local, NaiveDate, TimeZone};
+use chrono::{NaiveDate, Utc};
+use shared::analytics::time_range::get_time_range_bounds;
+use shared::analytics::{format_model_display_name, TimeRange};
+use shared::calendar::Calendar;
 use sqlx::SqlitePool;
 
-use crate::types::{format_model_display_name, WrappedData, WrappedPeriod};
+use crate::types::{WrappedData, WrappedPeriod};
 
 /// Service for wrapped report operations
 pub struct WrappedService;
 
 impl WrappedService {
     /// Get all wrapped data for a period
-    pub async fn get_wrapped_data(pool: &SqlitePool, period: WrappedPeriod) -> Result<WrappedData> {
+    pub async fn get_wrapped_data(
+        pool: &SqlitePool,
+        period: WrappedPeriod,
+        agent: Option<&str>,
+    ) -> Result<WrappedData> {
         let (start_time, end_time) = Self::get_period_bounds(period);
 
-        let session_stats = Self::get_session_stats(pool, start_time, end_time).await?;
-        let active_seconds = Self::get_active_time_seconds(pool, start_time, end_time).await?;
-        let output_stats = Self::get_output_stats(pool, start_time, end_time).await?;
-        let (top_model, top_model_pct) = Self::get_top_model(pool, start_time, end_time).await?;
-        let (top_tool, top_tool_count) = Self::get_top_tool(pool, start_time, end_time).await?;
-        let longest_streak = Self::get_longest_streak(pool, start_time, end_time).await?;
-        let (peak_hour, peak_hour_label) = Self::get_peak_hour(pool, start_time, end_time).await?;
-        let cost_sparkline = Self::get_cost_sparkline(pool, start_time, end_time).await?;
+        let session_stats = Self::get_session_stats(pool, start_time, end_time, agent).await?;
+        let active_seconds =
+            Self::get_active_time_seconds(pool, start_time, end_time, agent).await?;
+        let output_stats = Self::get_output_stats(pool, start_time, end_time, agent).await?;
+        let (top_model, top_model_pct) =
+            Self::get_top_model(pool, start_time, end_time, agent).await?;
+        let (top_tool, top_tool_count) =
+            Self::get_top_tool(pool, start_time, end_time, agent).await?;
+        let longest_streak = Self::get_longest_streak(pool, start_time, end_time, agent).await?;
+        let (peak_hour, peak_hour_label) =
+            Self::get_peak_hour(pool, start_time, end_time, agent).await?;
+        let cost_sparkline = Self::get_cost_sparkline(pool, start_time, end_time, agent).await?;
 
         let active_days = cost_sparkline.iter().filter(|&&c| c > 0.0).count().max(1);
         let daily_avg_cost = session_stats.total_cost / active_days as f32;
@@ -57,6 +68,7 @@ impl WrappedService {
         pool: &SqlitePool,
         start_time: i64,
         end_time: i64,
+        agent: Option<&str>,
     ) -> Result<SessionAgg> {
         // Use events table directly (consistent with overview/stats_service)
         let row: Option<SessionAggRow> = sqlx::query_as(
@@ -66,12 +78,14 @@ impl WrappedService {
                 CAST(COALESCE(SUM(cost_usd), 0.0) AS REAL) as total_cost
             FROM events
             WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
                 AND name = 'claude_code.api_request'
                 AND session_id != 'unknown'
             "#,
         )
         .bind(start_time)
         .bind(end_time)
+        .bind(agent)
         .fetch_optional(pool)
         .await?;
 
@@ -88,6 +102,7 @@ impl WrappedService {
         pool: &SqlitePool,
         start_time: i64,
         end_time: i64,
+        agent: Option<&str>,
     ) -> Result<f64> {
         let row: Option<(f64,)> = sqlx::query_as(
             r#"
@@ -95,10 +110,12 @@ impl WrappedService {
             FROM metrics
             WHERE name = 'claude_code.active_time.total'
               AND timestamp >= ? AND timestamp <= ?
+              AND agent = COALESCE(?, agent)
             "#,
         )
         .bind(start_time)
         .bind(end_time)
+        .bind(agent)
         .fetch_optional(pool)
         .await?;
 
@@ -110,6 +127,7 @@ impl WrappedService {
         pool: &SqlitePool,
         start_time: i64,
         end_time: i64,
+        agent: Option<&str>,
     ) -> Result<OutputStats> {
         let lines_row: Option<LinesRow> = sqlx::query_as(
             r#"
@@ -119,10 +137,12 @@ impl WrappedService {
             FROM metrics
             WHERE name = 'claude_code.lines_of_code.count'
                 AND timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
             "#,
         )
         .bind(start_time)
         .bind(end_time)
+        .bind(agent)
         .fetch_optional(pool)
         .await?;
 
@@ -132,10 +152,12 @@ impl WrappedService {
             FROM metrics
             WHERE name = 'claude_code.commit.count'
                 AND timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
             "#,
         )
         .bind(start_time)
         .bind(end_time)
+        .bind(agent)
         .fetch_optional(pool)
         .await?;
 
@@ -150,6 +172,7 @@ impl WrappedService {
         pool: &SqlitePool,
         start_time: i64,
         end_time: i64,
+        agent: Option<&str>,
     ) -> Result<(String, f32)> {
         let row: Option<TopModelRow> = sqlx::query_as(
             r#"
@@ -158,6 +181,7 @@ impl WrappedService {
                 COUNT(*) as count
             FROM events
             WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
                 AND name = 'claude_code.api_request'
                 AND model IS NOT NULL
             GROUP BY model
@@ -167,6 +191,7 @@ impl WrappedService {
         )
         .bind(start_time)
         .bind(end_time)
+        .bind(agent)
         .fetch_optional(pool)
         .await?;
 
@@ -175,11 +200,13 @@ impl WrappedService {
             SELECT COUNT(*)
             FROM events
             WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
                 AND name = 'claude_code.api_request'
             "#,
         )
         .bind(start_time)
         .bind(end_time)
+        .bind(agent)
         .fetch_optional(pool)
         .await?;
 
@@ -198,6 +225,7 @@ impl WrappedService {
         pool: &SqlitePool,
         start_time: i64,
         end_time: i64,
+        agent: Option<&str>,
     ) -> Result<(String, i32)> {
         let row: Option<TopToolRow> = sqlx::query_as(
             r#"
@@ -206,6 +234,7 @@ impl WrappedService {
                 COUNT(*) as count
             FROM events
             WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
                 AND name = 'claude_code.tool_result'
                 AND tool_name IS NOT NULL
             GROUP BY tool_name
@@ -215,6 +244,7 @@ impl WrappedService {
         )
         .bind(start_time)
         .bind(end_time)
+        .bind(agent)
         .fetch_optional(pool)
         .await?;
 
@@ -224,20 +254,29 @@ impl WrappedService {
         }
     }
 
-    async fn get_longest_streak(pool: &SqlitePool, start_time: i64, end_time: i64) -> Result<i32> {
-        let rows: Vec<DateRow> = sqlx::query_as(
+    async fn get_longest_streak(
+        pool: &SqlitePool,
+        start_time: i64,
+        end_time: i64,
+        agent: Option<&str>,
+    ) -> Result<i32> {
+        let query = format!(
             r#"
             SELECT DISTINCT
-                strftime('%Y-%m-%d', datetime(timestamp / 1000, 'unixepoch', 'localtime')) as date
+                strftime('%Y-%m-%d', {}, 'unixepoch') as date
             FROM events
             WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
             ORDER BY date ASC
             "#,
-        )
-        .bind(start_time)
-        .bind(end_time)
-        .fetch_all(pool)
-        .await?;
+            Calendar::current().local_seconds_sql("timestamp", start_time, end_time)
+        );
+        let rows: Vec<DateRow> = sqlx::query_as(&query)
+            .bind(start_time)
+            .bind(end_time)
+            .bind(agent)
+            .fetch_all(pool)
+            .await?;
 
         if rows.is_empty() {
             return Ok(0);
@@ -267,24 +306,29 @@ impl WrappedService {
         pool: &SqlitePool,
         start_time: i64,
         end_time: i64,
+        agent: Option<&str>,
     ) -> Result<(i32, String)> {
-        let row: Option<HourRow> = sqlx::query_as(
+        let query = format!(
             r#"
             SELECT
-                CAST(strftime('%H', datetime(timestamp / 1000, 'unixepoch', 'localtime')) AS INTEGER) as hour,
+                CAST(strftime('%H', {}, 'unixepoch') AS INTEGER) as hour,
                 COUNT(*) as count
             FROM events
             WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
                 AND name = 'claude_code.api_request'
             GROUP BY hour
             ORDER BY count DESC
             LIMIT 1
             "#,
-        )
-        .bind(start_time)
-        .bind(end_time)
-        .fetch_optional(pool)
-        .await?;
+            Calendar::current().local_seconds_sql("timestamp", start_time, end_time)
+        );
+        let row: Option<HourRow> = sqlx::query_as(&query)
+            .bind(start_time)
+            .bind(end_time)
+            .bind(agent)
+            .fetch_optional(pool)
+            .await?;
 
         match row {
             Some(r) => {
@@ -307,23 +351,28 @@ impl WrappedService {
         pool: &SqlitePool,
         start_time: i64,
         end_time: i64,
+        agent: Option<&str>,
     ) -> Result<Vec<f32>> {
-        let rows: Vec<DailyCostRow> = sqlx::query_as(
+        let query = format!(
             r#"
             SELECT
-                strftime('%Y-%m-%d', datetime(timestamp / 1000, 'unixepoch', 'localtime')) as date,
+                strftime('%Y-%m-%d', {}, 'unixepoch') as date,
                 COALESCE(SUM(cost_usd), 0.0) as cost
             FROM events
             WHERE timestamp >= ? AND timestamp <= ?
+                AND agent = COALESCE(?, agent)
                 AND name = 'claude_code.api_request'
             GROUP BY date
             ORDER BY date ASC
             "#,
-        )
-        .bind(start_time)
-        .bind(end_time)
-        .fetch_all(pool)
-        .await?;
+            Calendar::current().local_seconds_sql("timestamp", start_time, end_time)
+        );
+        let rows: Vec<DailyCostRow> = sqlx::query_as(&query)
+            .bind(start_time)
+            .bind(end_time)
+            .bind(agent)
+            .fetch_all(pool)
+            .await?;
 
         if rows.is_empty() {
             return Ok(vec![]);
@@ -358,36 +407,20 @@ impl WrappedService {
         Ok(sparkline)
     }
 
+    /// Period bounds in the configured calendar
     fn get_period_bounds(period: WrappedPeriod) -> (i64, i64) {
-        let now = Local::now();
-        let end_time = now.timestamp_millis();
-
-        let start = match period {
-            WrappedPeriod::Today => now.date_naive().and_hms_opt(0, 0, 0).unwrap(),
-            WrappedPeriod::Week => {
-                let days_since_monday = now.weekday().num_days_from_monday() as i64;
-                (now - chrono::Duration::days(days_since_monday))
-                    .date_naive()
-                    .and_hms_opt(0, 0, 0)
-                    .unwrap()
+        let time_range = match period {
+            WrappedPeriod::Today => TimeRange::Today,
+            WrappedPeriod::Week => TimeRange::Week,
+            WrappedPeriod::Month => TimeRange::Month,
+            WrappedPeriod::All => {
+                let start = Calendar::current()
+                    .start_of_day(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap())
+                    .timestamp_millis();
+                return (start, Utc::now().timestamp_millis());
             }
-            WrappedPeriod::Month => now
-                .date_naive()
-                .with_day(1)
-                .unwrap()
-                .and_hms_opt(0, 0, 0)
-                .unwrap(),
-            WrappedPeriod::All => NaiveDate::from_ymd_opt(2020, 1, 1)
-                .unwrap()
-                .and_hms_opt(0, 0, 0)
-                .unwrap(),
         };
-
-        let start_time = Local
-            .from_local_datetime(&start)
-            .unwrap()
-            .timestamp_millis();
-        (start_time, end_time)
+        get_time_range_bounds(time_range)
     }
 }
 
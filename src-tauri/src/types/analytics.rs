@@ -3,6 +3,7 @@
 //! Types for analytics insights and deep analysis.
 
 use serde::{Deserialize, Serialize};
+use shared::analytics;
 use typeshare::typeshare;
 
 /// Hourly activity distribution
@@ -50,3 +51,163 @@ pub struct CacheHitTrend {
     pub date: String,
     pub rate: f32,
 }
+
+/// Value aggregated per breakdown row
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BreakdownMeasure {
+    Cost,
+    InputTokens,
+    OutputTokens,
+    CacheReadTokens,
+    CacheCreationTokens,
+    /// API requests, tool calls or code edit decisions
+    Requests,
+    /// Errors per request, 0 to 1
+    ErrorRate,
+    /// Duration percentiles in milliseconds
+    DurationP50,
+    DurationP90,
+    DurationP99,
+}
+
+/// Attribute breakdown rows are split or filtered by
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BreakdownDimension {
+    Model,
+    Tool,
+    /// Working directory reported by the session's hooks
+    Project,
+    Terminal,
+    AppVersion,
+    Account,
+    Language,
+    /// "00" to "23"
+    Hour,
+    /// "0" (Sunday) to "6"
+    Weekday,
+    Agent,
+}
+
+/// Size of the time buckets a breakdown is split into
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BreakdownBucket {
+    Hour,
+    Day,
+    Week,
+    Month,
+}
+
+/// Keep rows whose dimension is one of the values
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakdownFilter {
+    pub dimension: BreakdownDimension,
+    pub values: Vec<String>,
+}
+
+/// Breakdown query; the time range is passed alongside
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakdownRequest {
+    pub measures: Vec<BreakdownMeasure>,
+    pub dimensions: Vec<BreakdownDimension>,
+    #[serde(default)]
+    pub filters: Vec<BreakdownFilter>,
+    pub bucket: Option<BreakdownBucket>,
+    pub limit: Option<i32>,
+}
+
+/// One combination of dimension values and bucket, with the measures in
+/// request order
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakdownRow {
+    pub dimensions: Vec<String>,
+    pub bucket: Option<String>,
+    pub values: Vec<Option<f64>>,
+}
+
+impl From<BreakdownMeasure> for analytics::Measure {
+    fn from(measure: BreakdownMeasure) -> Self {
+        match measure {
+            BreakdownMeasure::Cost => analytics::Measure::Cost,
+            BreakdownMeasure::InputTokens => analytics::Measure::InputTokens,
+            BreakdownMeasure::OutputTokens => analytics::Measure::OutputTokens,
+            BreakdownMeasure::CacheReadTokens => analytics::Measure::CacheReadTokens,
+            BreakdownMeasure::CacheCreationTokens => analytics::Measure::CacheCreationTokens,
+            BreakdownMeasure::Requests => analytics::Measure::Requests,
+            BreakdownMeasure::ErrorRate => analytics::Measure::ErrorRate,
+            BreakdownMeasure::DurationP50 => analytics::Measure::DurationP50,
+            BreakdownMeasure::DurationP90 => analytics::Measure::DurationP90,
+            BreakdownMeasure::DurationP99 => analytics::Measure::DurationP99,
+        }
+    }
+}
+
+impl From<BreakdownDimension> for analytics::Dimension {
+    fn from(dimension: BreakdownDimension) -> Self {
+        match dimension {
+            BreakdownDimension::Model => analytics::Dimension::Model,
+            BreakdownDimension::Tool => analytics::Dimension::Tool,
+            BreakdownDimension::Project => analytics::Dimension::Project,
+            BreakdownDimension::Terminal => analytics::Dimension::Terminal,
+            BreakdownDimension::AppVersion => analytics::Dimension::AppVersion,
+            BreakdownDimension::Account => analytics::Dimension::Account,
+            BreakdownDimension::Language => analytics::Dimension::Language,
+            BreakdownDimension::Hour => analytics::Dimension::Hour,
+            BreakdownDimension::Weekday => analytics::Dimension::Weekday,
+            BreakdownDimension::Agent => analytics::Dimension::Agent,
+        }
+    }
+}
+
+impl From<BreakdownBucket> for analytics::Granularity {
+    fn from(bucket: BreakdownBucket) -> Self {
+        match bucket {
+            BreakdownBucket::Hour => analytics::Granularity::Hour,
+            BreakdownBucket::Day => analytics::Granularity::Day,
+            BreakdownBucket::Week => analytics::Granularity::Week,
+            BreakdownBucket::Month => analytics::Granularity::Month,
+        }
+    }
+}
+
+impl BreakdownRequest {
+    /// The shared query over `time_range`
+    pub fn into_query(self, time_range: analytics::TimeRange) -> analytics::BreakdownQuery {
+        analytics::BreakdownQuery {
+            measures: self.measures.into_iter().map(Into::into).collect(),
+            dimensions: self.dimensions.into_iter().map(Into::into).collect(),
+            filters: self
+                .filters
+                .into_iter()
+                .map(|f| analytics::Filter {
+                    dimension: f.dimension.into(),
+                    values: f.values,
+                })
+                .collect(),
+            time_range,
+            bucket: self.bucket.map(Into::into),
+            limit: self.limit.and_then(|limit| u32::try_from(limit).ok()),
+        }
+    }
+}
+
+impl From<analytics::BreakdownRow> for BreakdownRow {
+    fn from(row: analytics::BreakdownRow) -> Self {
+        Self {
+            dimensions: row.dimensions,
+            bucket: row.bucket,
+            values: row.values,
+        }
+    }
+}
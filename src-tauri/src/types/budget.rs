@@ -0,0 +1,238 @@
+//! Budget types
+//!
+//! Types for spend budgets, their status and burn-down, and spend
+//! forecasts.
+
+use serde::{Deserialize, Serialize};
+use shared::analytics;
+use typeshare::typeshare;
+
+/// Calendar period a budget's limit applies to
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// Spend a budget counts
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetScope {
+    Global,
+    Project,
+    Model,
+}
+
+/// A spend budget
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Budget {
+    pub id: i32,
+    pub name: String,
+    pub period: BudgetPeriod,
+    pub scope: BudgetScope,
+    /// Project path or model name; unset for global budgets
+    pub scope_value: Option<String>,
+    pub limit_usd: f64,
+    pub enabled: bool,
+}
+
+/// Request to create or update a budget
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetRequest {
+    pub name: String,
+    pub period: BudgetPeriod,
+    pub scope: BudgetScope,
+    pub scope_value: Option<String>,
+    pub limit_usd: f64,
+    pub enabled: bool,
+}
+
+/// A budget with its spend in the current period
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetStatus {
+    pub budget: Budget,
+    /// Unix ms start of the current period
+    pub period_start: f64,
+    /// Unix ms start of the next period
+    pub period_end: f64,
+    pub spent: f64,
+    pub percent: f64,
+    pub remaining: f64,
+}
+
+/// Cumulative spend at the end of one bucket of a budget period
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BurnDownPoint {
+    pub date: String,
+    /// Unset for buckets that have not started yet
+    pub spent: Option<f64>,
+    pub remaining: Option<f64>,
+    pub ideal_remaining: f64,
+}
+
+/// Projected spend for the current period
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendForecast {
+    pub period_start: String,
+    pub period_end: String,
+    pub spent: f64,
+    pub projected: f64,
+    /// Edges of the 95% confidence band
+    pub lower: f64,
+    pub upper: f64,
+    pub weekday_average: f64,
+    pub weekend_average: f64,
+    pub limit: Option<f64>,
+    /// Day projected spend first goes over the limit within the period
+    pub exceeds_on: Option<String>,
+    pub days: Vec<ForecastDay>,
+}
+
+/// Cumulative spend at the end of one day of a forecast period
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastDay {
+    pub date: String,
+    /// Unset after today
+    pub actual: Option<f64>,
+    pub projected: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl From<shared::BudgetPeriod> for BudgetPeriod {
+    fn from(period: shared::BudgetPeriod) -> Self {
+        match period {
+            shared::BudgetPeriod::Daily => Self::Daily,
+            shared::BudgetPeriod::Weekly => Self::Weekly,
+            shared::BudgetPeriod::Monthly => Self::Monthly,
+        }
+    }
+}
+
+impl From<BudgetPeriod> for shared::BudgetPeriod {
+    fn from(period: BudgetPeriod) -> Self {
+        match period {
+            BudgetPeriod::Daily => Self::Daily,
+            BudgetPeriod::Weekly => Self::Weekly,
+            BudgetPeriod::Monthly => Self::Monthly,
+        }
+    }
+}
+
+impl From<shared::BudgetScope> for BudgetScope {
+    fn from(scope: shared::BudgetScope) -> Self {
+        match scope {
+            shared::BudgetScope::Global => Self::Global,
+            shared::BudgetScope::Project => Self::Project,
+            shared::BudgetScope::Model => Self::Model,
+        }
+    }
+}
+
+impl From<BudgetScope> for shared::BudgetScope {
+    fn from(scope: BudgetScope) -> Self {
+        match scope {
+            BudgetScope::Global => Self::Global,
+            BudgetScope::Project => Self::Project,
+            BudgetScope::Model => Self::Model,
+        }
+    }
+}
+
+impl From<shared::Budget> for Budget {
+    fn from(b: shared::Budget) -> Self {
+        Self {
+            id: b.id as i32,
+            name: b.name,
+            period: b.period.into(),
+            scope: b.scope.into(),
+            scope_value: b.scope_value,
+            limit_usd: b.limit_usd,
+            enabled: b.enabled,
+        }
+    }
+}
+
+impl From<BudgetRequest> for shared::NewBudget {
+    fn from(r: BudgetRequest) -> Self {
+        Self {
+            name: r.name,
+            period: r.period.into(),
+            scope: r.scope.into(),
+            // Blank inputs mean no value
+            scope_value: r.scope_value.filter(|v| !v.trim().is_empty()),
+            limit_usd: r.limit_usd,
+            enabled: r.enabled,
+        }
+    }
+}
+
+impl From<analytics::BudgetStatus> for BudgetStatus {
+    fn from(s: analytics::BudgetStatus) -> Self {
+        Self {
+            budget: s.budget.into(),
+            period_start: s.period_start as f64,
+            period_end: s.period_end as f64,
+            spent: s.spent,
+            percent: s.percent,
+            remaining: s.remaining,
+        }
+    }
+}
+
+impl From<analytics::SpendForecast> for SpendForecast {
+    fn from(f: analytics::SpendForecast) -> Self {
+        Self {
+            period_start: f.period_start,
+            period_end: f.period_end,
+            spent: f.spent,
+            projected: f.projected,
+            lower: f.lower,
+            upper: f.upper,
+            weekday_average: f.weekday_average,
+            weekend_average: f.weekend_average,
+            limit: f.limit,
+            exceeds_on: f.exceeds_on,
+            days: f.days.into_iter().map(ForecastDay::from).collect(),
+        }
+    }
+}
+
+impl From<analytics::ForecastDay> for ForecastDay {
+    fn from(d: analytics::ForecastDay) -> Self {
+        Self {
+            date: d.date,
+            actual: d.actual,
+            projected: d.projected,
+            lower: d.lower,
+            upper: d.upper,
+        }
+    }
+}
+
+impl From<analytics::BurnDownPoint> for BurnDownPoint {
+    fn from(p: analytics::BurnDownPoint) -> Self {
+        Self {
+            date: p.date,
+            spent: p.spent,
+            remaining: p.remaining,
+            ideal_remaining: p.ideal_remaining,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! Claude profile types
+//!
+//! Claude Code config roots (`~/.claude`, `CLAUDE_CONFIG_DIR`) that Lumo reads.
+
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+/// A registered Claude Code config root
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeProfileInfo {
+    pub label: String,
+    pub path: String,
+    /// Whether the directory exists on disk
+    pub exists: bool,
+}
@@ -28,6 +28,9 @@ pub struct ClaudeSession {
     pub project_path: String,
     #[serde(default)]
     pub is_sidechain: bool,
+    /// Label of the Claude profile the session belongs to
+    #[serde(default)]
+    pub profile: String,
 }
 
 /// Claude project summary (aggregated from sessions-index.json)
@@ -40,6 +43,8 @@ pub struct ClaudeProjectSummary {
     pub session_count: i32,
     pub skill_count: i32,
     pub last_updated: String,
+    /// Labels of the Claude profiles with sessions for this project
+    pub profiles: Vec<String>,
 }
 
 /// Paginated Claude session list response
@@ -0,0 +1,145 @@
+//! Comparison types
+//!
+//! Types for statistics compared with the previous period.
+
+use serde::{Deserialize, Serialize};
+use shared::analytics;
+use typeshare::typeshare;
+
+/// A measure in the current and previous period
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comparison {
+    pub current: f64,
+    pub previous: f64,
+    pub delta: f64,
+    /// Unset when the previous value is zero
+    pub delta_percent: Option<f64>,
+}
+
+/// Summary statistics against the previous period
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SummaryComparison {
+    pub total_cost: Comparison,
+    pub total_tokens: Comparison,
+    pub cache_tokens: Comparison,
+    pub cache_percentage: Comparison,
+    pub active_time_seconds: Comparison,
+    pub total_sessions: Comparison,
+    pub lines_of_code_added: Comparison,
+    pub lines_of_code_removed: Comparison,
+    pub pull_requests: Comparison,
+    pub commits: Comparison,
+    pub code_edit_accepts: Comparison,
+    pub code_edit_rejects: Comparison,
+}
+
+/// Model usage against the previous period
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelComparison {
+    pub model: String,
+    pub display_name: String,
+    pub cost: Comparison,
+    pub requests: Comparison,
+    pub tokens: Comparison,
+}
+
+/// Tool usage against the previous period
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolComparison {
+    pub tool_name: String,
+    pub count: Comparison,
+    pub successes: Comparison,
+    pub failures: Comparison,
+    pub avg_duration_ms: Comparison,
+}
+
+/// Usage trend bucket against the previous period's bucket at the same
+/// position
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageTrendComparison {
+    pub date: String,
+    pub previous_date: Option<String>,
+    pub cost: Comparison,
+    pub input_tokens: Comparison,
+    pub output_tokens: Comparison,
+    pub cache_read_tokens: Comparison,
+    pub cache_creation_tokens: Comparison,
+}
+
+impl From<analytics::Comparison> for Comparison {
+    fn from(c: analytics::Comparison) -> Self {
+        Self {
+            current: c.current,
+            previous: c.previous,
+            delta: c.delta,
+            delta_percent: c.delta_percent,
+        }
+    }
+}
+
+impl From<analytics::SummaryComparison> for SummaryComparison {
+    fn from(s: analytics::SummaryComparison) -> Self {
+        Self {
+            total_cost: s.total_cost.into(),
+            total_tokens: s.total_tokens.into(),
+            cache_tokens: s.cache_tokens.into(),
+            cache_percentage: s.cache_percentage.into(),
+            active_time_seconds: s.active_time_seconds.into(),
+            total_sessions: s.total_sessions.into(),
+            lines_of_code_added: s.lines_of_code_added.into(),
+            lines_of_code_removed: s.lines_of_code_removed.into(),
+            pull_requests: s.pull_requests.into(),
+            commits: s.commits.into(),
+            code_edit_accepts: s.code_edit_accepts.into(),
+            code_edit_rejects: s.code_edit_rejects.into(),
+        }
+    }
+}
+
+impl From<analytics::ModelComparison> for ModelComparison {
+    fn from(m: analytics::ModelComparison) -> Self {
+        Self {
+            model: m.model,
+            display_name: m.display_name,
+            cost: m.cost.into(),
+            requests: m.requests.into(),
+            tokens: m.tokens.into(),
+        }
+    }
+}
+
+impl From<analytics::ToolComparison> for ToolComparison {
+    fn from(t: analytics::ToolComparison) -> Self {
+        Self {
+            tool_name: t.tool_name,
+            count: t.count.into(),
+            successes: t.successes.into(),
+            failures: t.failures.into(),
+            avg_duration_ms: t.avg_duration_ms.into(),
+        }
+    }
+}
+
+impl From<analytics::UsageTrendComparison> for UsageTrendComparison {
+    fn from(t: analytics::UsageTrendComparison) -> Self {
+        Self {
+            date: t.date,
+            previous_date: t.previous_date,
+            cost: t.cost.into(),
+            input_tokens: t.input_tokens.into(),
+            output_tokens: t.output_tokens.into(),
+            cache_read_tokens: t.cache_read_tokens.into(),
+            cache_creation_tokens: t.cache_creation_tokens.into(),
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Daemon status types
+
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+/// Ingest counters for one telemetry signal since the daemon started
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaemonSignalStatus {
+    /// "metrics", "logs", "notify" or "codex"
+    pub signal: String,
+    /// Unix ms of the last successful ingest
+    pub last_ingest_at: Option<f64>,
+    pub requests: f64,
+    pub rows: f64,
+    pub quarantined: f64,
+    pub rejected: f64,
+}
+
+/// Daemon connectivity plus, when reachable, its ingest and database details
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaemonStatus {
+    pub connected: bool,
+    pub version: Option<String>,
+    pub uptime_seconds: Option<f64>,
+    pub listeners: Vec<String>,
+    pub signals: Vec<DaemonSignalStatus>,
+    pub database_status: Option<String>,
+    pub database_size_bytes: Option<f64>,
+    pub wal_size_bytes: Option<f64>,
+    pub migration_version: Option<String>,
+}
+
+/// Daemon address configuration and whether Claude Code and the running
+/// daemon agree with it
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaemonAddressStatus {
+    /// Configured listen address from `~/.lumo/daemon.json`
+    pub address: String,
+    /// OTLP endpoint Claude Code should export to
+    pub expected_endpoint: String,
+    /// `OTEL_EXPORTER_OTLP_ENDPOINT` currently in Claude Code settings
+    pub claude_endpoint: Option<String>,
+    pub hooks_installed: bool,
+    /// Addresses the running daemon reports, empty if unreachable
+    pub daemon_listeners: Vec<String>,
+    pub in_sync: bool,
+    /// Human-readable description of each mismatch
+    pub issues: Vec<String>,
+}
+
+/// Outcome of a diagnostic check
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoctorCheckStatus {
+    Pass,
+    Warn,
+    Fail,
+    /// Not run because a check it depends on failed
+    Skip,
+}
+
+/// A diagnostic check from `lumo-daemon doctor`, with fixes when it did
+/// not pass
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: DoctorCheckStatus,
+    pub message: String,
+    pub fixes: Vec<String>,
+}
+
+/// End-to-end diagnostics of settings, daemon, auth and database
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+    pub has_failures: bool,
+}
+
+impl From<shared::doctor::DoctorReport> for DoctorReport {
+    fn from(report: shared::doctor::DoctorReport) -> Self {
+        use shared::doctor::CheckStatus;
+
+        Self {
+            has_failures: report.has_failures(),
+            checks: report
+                .checks
+                .into_iter()
+                .map(|c| DoctorCheck {
+                    name: c.name,
+                    status: match c.status {
+                        CheckStatus::Pass => DoctorCheckStatus::Pass,
+                        CheckStatus::Warn => DoctorCheckStatus::Warn,
+                        CheckStatus::Fail => DoctorCheckStatus::Fail,
+                        CheckStatus::Skip => DoctorCheckStatus::Skip,
+                    },
+                    message: c.message,
+                    fixes: c.fixes,
+                })
+                .collect(),
+        }
+    }
+}
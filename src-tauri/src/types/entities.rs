@@ -28,6 +28,7 @@ pub struct Session {
     pub organization_id: Option<String>,
     pub terminal_type: Option<String>,
     pub app_version: Option<String>,
+    pub agent: String,
 }
 
 impl From<shared::Session> for Session {
@@ -50,6 +51,7 @@ impl From<shared::Session> for Session {
             organization_id: s.organization_id,
             terminal_type: s.terminal_type,
             app_version: s.app_version,
+            agent: s.agent,
         }
     }
 }
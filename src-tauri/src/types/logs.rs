@@ -0,0 +1,74 @@
+//! Log viewer types
+//!
+//! Parsed lines from the daemon's rotated log files and the app's log files.
+
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+/// Which log files an entry came from
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogSource {
+    Daemon,
+    App,
+}
+
+/// Log severity, ordered from least to most severe
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Parse a level name as written by tracing or the `log` crate
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "TRACE" => Some(Self::Trace),
+            "DEBUG" => Some(Self::Debug),
+            "INFO" => Some(Self::Info),
+            "WARN" | "WARNING" => Some(Self::Warn),
+            "ERROR" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+/// A single log entry. Continuation lines (e.g. multi-line errors) are
+/// folded into the message of the entry they follow.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    pub source: LogSource,
+    /// Unix timestamp in milliseconds
+    pub timestamp: f64,
+    pub level: LogLevel,
+    pub target: String,
+    pub message: String,
+}
+
+/// Filters for reading log entries. Unset fields don't filter.
+#[typeshare]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogQuery {
+    /// Only entries from this source
+    pub source: Option<LogSource>,
+    /// Minimum severity
+    pub level: Option<LogLevel>,
+    /// Inclusive start, Unix milliseconds
+    pub start_time: Option<f64>,
+    /// Exclusive end, Unix milliseconds
+    pub end_time: Option<f64>,
+    /// Case-insensitive substring of the target or message
+    pub search: Option<String>,
+    /// Maximum number of (most recent) entries, default 500
+    pub limit: Option<u32>,
+}
@@ -14,6 +14,8 @@ pub struct PluginInstalledScope {
     pub scope: String,
     /// Project path (only when scope is "project")
     pub project_path: Option<String>,
+    /// Claude profile the plugin is installed in
+    pub profile: String,
 }
 
 /// A plugin available in a marketplace
@@ -43,6 +45,8 @@ pub struct MarketplaceInfo {
     pub owner_name: Option<String>,
     pub plugin_count: f64,
     pub last_updated: Option<String>,
+    /// Claude profiles that have this marketplace configured
+    pub profiles: Vec<String>,
 }
 
 /// Result of a marketplace command
@@ -3,9 +3,15 @@
 //! These types are used for API responses and are exported to TypeScript via typeshare.
 
 mod analytics;
+mod budget;
+mod claude_profile;
 mod claude_session;
+mod comparison;
+mod daemon;
 mod entities;
+mod logs;
 mod notification_settings;
+mod settings;
 mod stats;
 mod subscription_usage;
 mod tools;
@@ -16,9 +22,15 @@ mod skills;
 mod wrapped;
 
 pub use analytics::*;
+pub use budget::*;
+pub use claude_profile::*;
 pub use claude_session::*;
+pub use comparison::*;
+pub use daemon::*;
 pub use entities::*;
+pub use logs::*;
 pub use notification_settings::*;
+pub use settings::*;
 pub use stats::*;
 pub use subscription_usage::*;
 pub use tools::*;
@@ -0,0 +1,64 @@
+//! Claude Code settings file types
+//!
+//! Types for previewing Lumo's edits to Claude Code settings and restoring
+//! backups.
+
+use serde::{Deserialize, Serialize};
+use shared::settings_file::{self, DiffKind};
+use typeshare::typeshare;
+
+/// One line of a settings diff
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsDiffLine {
+    /// "context", "added" or "removed"
+    pub kind: String,
+    pub text: String,
+}
+
+/// Pending Lumo changes to a settings file
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsPreview {
+    pub path: String,
+    /// Fingerprint of the file the preview was computed from; pass it back
+    /// when applying so a concurrent edit is not overwritten
+    pub fingerprint: String,
+    pub has_changes: bool,
+    pub diff: Vec<SettingsDiffLine>,
+}
+
+/// A backup of a settings file in `~/.lumo/backups/`
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsBackup {
+    pub file_name: String,
+    /// Settings file the backup restores to
+    pub target_path: String,
+    /// Unix ms
+    pub created_at: f64,
+    pub size_bytes: f64,
+}
+
+impl From<(DiffKind, &str)> for SettingsDiffLine {
+    fn from((kind, text): (DiffKind, &str)) -> Self {
+        Self {
+            kind: kind.as_str().to_string(),
+            text: text.to_string(),
+        }
+    }
+}
+
+impl From<settings_file::Backup> for SettingsBackup {
+    fn from(b: settings_file::Backup) -> Self {
+        Self {
+            file_name: b.file_name(),
+            target_path: b.target.display().to_string(),
+            created_at: b.created_at as f64,
+            size_bytes: b.size_bytes as f64,
+        }
+    }
+}
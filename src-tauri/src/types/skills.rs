@@ -28,6 +28,8 @@ pub struct SkillSummary {
     pub package_name: Option<String>,
     pub installed_at: Option<String>,
     pub path: String,
+    /// Claude profile of a personal skill, None for project skills
+    pub profile: Option<String>,
 }
 
 /// Full detail of a skill for viewing/editing
@@ -3,9 +3,10 @@
 //! Types for summary statistics, model breakdowns, and token statistics.
 
 use serde::{Deserialize, Serialize};
+use shared::analytics;
 use typeshare::typeshare;
 
-/// Time range filter for queries
+/// Named time range for queries
 #[typeshare]
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -13,6 +14,43 @@ pub enum TimeRange {
     Today,
     Week,
     Month,
+    /// Rolling windows ending now
+    Last24h,
+    Last7d,
+    Last30d,
+    Last90d,
+    Year,
+    All,
+}
+
+/// Time range between two Unix millisecond timestamps
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomTimeRange {
+    pub start: f64,
+    pub end: f64,
+}
+
+/// Time range argument of the stats, trends, tools and analytics commands:
+/// a [`TimeRange`] name or a [`CustomTimeRange`] object
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TimeRangeFilter {
+    Named(TimeRange),
+    Custom(CustomTimeRange),
+}
+
+/// Timezone and first day of the week that days, weeks and hours are
+/// bucketed in. Unset fields use the system timezone and Monday.
+#[typeshare]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarSettings {
+    /// IANA name, e.g. "Europe/Berlin"
+    pub timezone: Option<String>,
+    /// Weekday name, e.g. "sunday"
+    pub week_start: Option<String>,
 }
 
 /// Summary statistics for a time period
@@ -62,37 +100,112 @@ pub struct TokenStats {
     pub cache_creation: i32,
 }
 
-/// Format model identifier to display name
-pub fn format_model_display_name(model: &str) -> String {
-    let model_lower = model.to_lowercase();
-
-    if model_lower.contains("opus") {
-        if model_lower.contains("4-5") || model_lower.contains("4.5") {
-            "Opus 4.5".to_string()
-        } else if model_lower.contains("4") {
-            "Opus 4".to_string()
-        } else {
-            "Opus".to_string()
+/// Usage statistics per coding agent (Claude Code, Codex, ...)
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentStats {
+    pub agent: String,
+    pub display_name: String,
+    pub cost: f32,
+    pub requests: i32,
+    pub tokens: i32,
+    pub sessions: i32,
+}
+
+impl From<TimeRange> for analytics::TimeRange {
+    fn from(time_range: TimeRange) -> Self {
+        match time_range {
+            TimeRange::Today => analytics::TimeRange::Today,
+            TimeRange::Week => analytics::TimeRange::Week,
+            TimeRange::Month => analytics::TimeRange::Month,
+            TimeRange::Last24h => analytics::TimeRange::Last24Hours,
+            TimeRange::Last7d => analytics::TimeRange::Last7Days,
+            TimeRange::Last30d => analytics::TimeRange::Last30Days,
+            TimeRange::Last90d => analytics::TimeRange::Last90Days,
+            TimeRange::Year => analytics::TimeRange::Year,
+            TimeRange::All => analytics::TimeRange::All,
+        }
+    }
+}
+
+impl TryFrom<TimeRangeFilter> for analytics::TimeRange {
+    type Error = shared::Error;
+
+    fn try_from(filter: TimeRangeFilter) -> Result<Self, Self::Error> {
+        match filter {
+            TimeRangeFilter::Named(range) => Ok(range.into()),
+            TimeRangeFilter::Custom(CustomTimeRange { start, end }) => {
+                if start > end {
+                    return Err(shared::Error::InvalidData(format!(
+                        "Time range starts after it ends: {} > {}",
+                        start, end
+                    )));
+                }
+                Ok(analytics::TimeRange::Custom {
+                    start: start as i64,
+                    end: end as i64,
+                })
+            }
+        }
+    }
+}
+
+impl From<analytics::SummaryStats> for SummaryStats {
+    fn from(s: analytics::SummaryStats) -> Self {
+        Self {
+            total_cost: s.total_cost as f32,
+            total_tokens: s.total_tokens as i32,
+            cache_tokens: s.cache_tokens as i32,
+            cache_percentage: s.cache_percentage as f32,
+            active_time_seconds: s.active_time_seconds as i32,
+            total_sessions: s.total_sessions as i32,
+            today_sessions: s.today_sessions as i32,
+            cost_change_percent: s.cost_change_percent as f32,
+            lines_of_code_added: s.lines_of_code_added as i32,
+            lines_of_code_removed: s.lines_of_code_removed as i32,
+            pull_requests: s.pull_requests as i32,
+            commits: s.commits as i32,
+            code_edit_accepts: s.code_edit_accepts as i32,
+            code_edit_rejects: s.code_edit_rejects as i32,
         }
-    } else if model_lower.contains("sonnet") {
-        if model_lower.contains("4-5") || model_lower.contains("4.5") {
-            "Sonnet 4.5".to_string()
-        } else if model_lower.contains("-4-") || model_lower.ends_with("-4") {
-            "Sonnet 4".to_string()
-        } else if model_lower.contains("3-5") || model_lower.contains("3.5") {
-            "Sonnet 3.5".to_string()
-        } else {
-            "Sonnet".to_string()
+    }
+}
+
+impl From<analytics::ModelStats> for ModelStats {
+    fn from(s: analytics::ModelStats) -> Self {
+        Self {
+            model: s.model,
+            display_name: s.display_name,
+            cost: s.cost as f32,
+            requests: s.requests as i32,
+            tokens: s.tokens as i32,
+        }
+    }
+}
+
+impl From<analytics::TokenStats> for TokenStats {
+    fn from(s: analytics::TokenStats) -> Self {
+        Self {
+            model: s.model,
+            display_name: s.display_name,
+            input: s.input as i32,
+            output: s.output as i32,
+            cache_read: s.cache_read as i32,
+            cache_creation: s.cache_creation as i32,
         }
-    } else if model_lower.contains("haiku") {
-        if model_lower.contains("3-5") || model_lower.contains("3.5") {
-            "Haiku 3.5".to_string()
-        } else if model_lower.contains("3") {
-            "Haiku 3".to_string()
-        } else {
-            "Haiku".to_string()
+    }
+}
+
+impl From<analytics::AgentStats> for AgentStats {
+    fn from(s: analytics::AgentStats) -> Self {
+        Self {
+            agent: s.agent,
+            display_name: s.display_name,
+            cost: s.cost as f32,
+            requests: s.requests as i32,
+            tokens: s.tokens as i32,
+            sessions: s.sessions as i32,
         }
-    } else {
-        model.to_string()
     }
 }
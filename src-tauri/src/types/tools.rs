@@ -3,6 +3,7 @@
 //! Types for tool usage statistics and analysis.
 
 use serde::{Deserialize, Serialize};
+use shared::analytics;
 use typeshare::typeshare;
 
 /// Tool usage statistics (frequency, success rate, duration)
@@ -36,3 +37,35 @@ pub struct ToolTrend {
     pub date: String,
     pub count: i32,
 }
+
+impl From<analytics::ToolUsageStats> for ToolUsageStats {
+    fn from(s: analytics::ToolUsageStats) -> Self {
+        Self {
+            tool_name: s.tool_name,
+            count: s.count as i32,
+            successes: s.successes as i32,
+            failures: s.failures as i32,
+            avg_duration_ms: s.avg_duration_ms.map(|v| v as f32),
+        }
+    }
+}
+
+impl From<analytics::CodeEditLanguageStats> for CodeEditLanguageStats {
+    fn from(s: analytics::CodeEditLanguageStats) -> Self {
+        Self {
+            language: s.language,
+            accepts: s.accepts as i32,
+            rejects: s.rejects as i32,
+        }
+    }
+}
+
+impl From<analytics::ToolTrend> for ToolTrend {
+    fn from(t: analytics::ToolTrend) -> Self {
+        Self {
+            tool_name: t.tool_name,
+            date: t.date,
+            count: t.count as i32,
+        }
+    }
+}
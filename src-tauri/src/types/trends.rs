@@ -3,6 +3,7 @@
 //! Types for usage trends and time series data.
 
 use serde::{Deserialize, Serialize};
+use shared::analytics;
 use typeshare::typeshare;
 
 /// Usage trend data point (for charts)
@@ -37,3 +38,36 @@ pub struct CostEfficiencyTrend {
     pub cost_per_session: f32,
     pub session_count: i32,
 }
+
+impl From<analytics::UsageTrend> for UsageTrend {
+    fn from(t: analytics::UsageTrend) -> Self {
+        Self {
+            date: t.date,
+            cost: t.cost as f32,
+            input_tokens: t.input_tokens as i32,
+            output_tokens: t.output_tokens as i32,
+            cache_read_tokens: t.cache_read_tokens as i32,
+            cache_creation_tokens: t.cache_creation_tokens as i32,
+        }
+    }
+}
+
+impl From<analytics::CostByModelTrend> for CostByModelTrend {
+    fn from(t: analytics::CostByModelTrend) -> Self {
+        Self {
+            date: t.date,
+            model: t.model,
+            cost: t.cost as f32,
+        }
+    }
+}
+
+impl From<analytics::CostEfficiencyTrend> for CostEfficiencyTrend {
+    fn from(t: analytics::CostEfficiencyTrend) -> Self {
+        Self {
+            date: t.date,
+            cost_per_session: t.cost_per_session as f32,
+            session_count: t.session_count as i32,
+        }
+    }
+}